@@ -0,0 +1,46 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use meshx::geometry::{Aabb, Ray, Triangle, Vector3};
+use meshx::mesh::half_edge::HeMesh;
+use meshx::spatial::{Bvh, Octree};
+use rand::prelude::*;
+
+/// Number of rays cast per iteration
+const N_RAYS: usize = 1000;
+
+/// Benchmark for casting rays against the triangles of sphere.obj indexed
+/// by an Octree and by a Bvh
+pub fn benchmark_raycast(c: &mut Criterion) {
+    let mesh = HeMesh::from_obj("tests/fixtures/sphere.obj").unwrap();
+    let triangles = mesh.triangles().map(|(_, t)| t).collect::<Vec<Triangle>>();
+    let rays = generate_rays(N_RAYS);
+
+    let aabb = mesh.aabb();
+    let aabb = Aabb::new(aabb.center(), aabb.halfsize() * 1.1);
+    let octree = Octree::from_items(aabb, triangles.clone());
+    let bvh = Bvh::build(triangles);
+
+    c.bench_function("Octree Raycast", |b| {
+        b.iter(|| rays.iter().filter_map(|ray| octree.raycast(ray)).count())
+    });
+
+    c.bench_function("Bvh Raycast", |b| {
+        b.iter(|| rays.iter().filter_map(|ray| bvh.raycast(ray)).count())
+    });
+}
+
+/// Generate random rays from outside the unit sphere aimed near its center.
+fn generate_rays(n: usize) -> Vec<Ray> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut point = || Vector3::new(rng.gen(), rng.gen(), rng.gen()) - Vector3::ones() * 0.5;
+
+    (0..n)
+        .map(|_| {
+            let origin = point().unit() * 2.;
+            let target = point() * 0.5;
+            Ray::new(origin, target - origin)
+        })
+        .collect()
+}
+
+criterion_group!(benches, benchmark_raycast);
+criterion_main!(benches);
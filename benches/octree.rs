@@ -0,0 +1,57 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use meshx::geometry::{Aabb, Triangle, Vector3};
+use meshx::spatial::Octree;
+use rand::prelude::*;
+
+/// Number of triangles indexed by each benchmark
+const N_TRIANGLES: usize = 100_000;
+
+/// Benchmark for building an Octree by repeated insertion
+pub fn benchmark_octree_insert(c: &mut Criterion) {
+    let triangles = generate_triangles(N_TRIANGLES);
+
+    c.bench_function("Octree Insert", |b| {
+        b.iter(|| {
+            let mut octree = Octree::<Triangle>::new(Aabb::unit());
+
+            for triangle in triangles.iter() {
+                octree.insert(*triangle);
+            }
+        })
+    });
+}
+
+/// Benchmark for building an Octree by bulk loading
+pub fn benchmark_octree_from_items(c: &mut Criterion) {
+    let triangles = generate_triangles(N_TRIANGLES);
+
+    c.bench_function("Octree From Items", |b| {
+        b.iter_batched(
+            || triangles.clone(),
+            |triangles| Octree::from_items(Aabb::unit(), triangles),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+/// Generate small random triangles strictly inside the unit Aabb.
+fn generate_triangles(n: usize) -> Vec<Triangle> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut point = || Vector3::new(rng.gen(), rng.gen(), rng.gen()) - Vector3::ones() * 0.5;
+
+    (0..n)
+        .map(|_| {
+            let p = point() * 0.98;
+            let q = p + point() * 0.01;
+            let r = p + point() * 0.01;
+            Triangle::new(p, q, r)
+        })
+        .collect()
+}
+
+criterion_group!(
+    benches,
+    benchmark_octree_insert,
+    benchmark_octree_from_items
+);
+criterion_main!(benches);
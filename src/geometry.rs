@@ -1,21 +1,25 @@
 pub mod aabb;
 pub mod collision;
 pub mod line;
+pub mod matrix3;
 pub mod plane;
 pub mod polygon;
 pub mod ray;
 pub mod sphere;
+pub mod transform;
 pub mod triangle;
 pub mod vector3;
 
 // Re-exports
-pub use aabb::Aabb;
+pub use aabb::{Aabb, Bounded};
 pub use collision::{Clip, Distance, Intersection, Intersects};
 pub use line::Line;
+pub use matrix3::Matrix3;
 pub use plane::Plane;
 pub use polygon::Polygon;
-pub use ray::Ray;
+pub use ray::{Ray, RayHit};
 pub use sphere::Sphere;
+pub use transform::Transform;
 pub use triangle::Triangle;
 pub use vector3::Vector3;
 
@@ -1,8 +1,11 @@
 use crate::geometry::collision;
-use crate::geometry::{Intersects, Plane, Ray, Sphere, Vector3};
+use crate::geometry::{
+    Distance, Intersects, Line, Matrix3, Plane, Ray, Sphere, Transform, Vector3,
+};
 
 /// Axis-aligned bounding box in three-dimensional Cartesian space.
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Aabb {
     center: Vector3,
     halfsize: Vector3,
@@ -60,6 +63,20 @@ impl Aabb {
         Aabb::new(center, h)
     }
 
+    /// Compute all eight child octants in octant order
+    pub fn subdivide(&self) -> [Aabb; 8] {
+        let h = self.halfsize() * 0.5;
+
+        std::array::from_fn(|octant| {
+            let dx = if (octant & 4) == 0 { -h[0] } else { h[0] };
+            let dy = if (octant & 2) == 0 { -h[1] } else { h[1] };
+            let dz = if (octant & 1) == 0 { -h[2] } else { h[2] };
+            let center = self.center + Vector3::new(dx, dy, dz);
+
+            Aabb::new(center, h)
+        })
+    }
+
     /// Get the inward-facing Planes defining the boundary
     pub fn planes(&self) -> Vec<Plane> {
         let min = self.min();
@@ -80,6 +97,41 @@ impl Aabb {
 
         planes
     }
+
+    /// Check if another Aabb lies entirely inside (touching allowed)
+    pub fn contains_aabb(&self, other: &Aabb) -> bool {
+        let (min, max) = (self.min(), self.max());
+        let (other_min, other_max) = (other.min(), other.max());
+
+        (0..3).all(|i| min[i] <= other_min[i] && other_max[i] <= max[i])
+    }
+}
+
+/// Compute the axis-aligned bounding box of a geometry.
+pub trait Bounded {
+    fn aabb(&self) -> Aabb;
+}
+
+impl Bounded for Aabb {
+    fn aabb(&self) -> Aabb {
+        *self
+    }
+}
+
+impl Transform for Aabb {
+    /// Compute the Aabb bounding the transformed box
+    fn transformed(&self, m: &Matrix3, t: &Vector3) -> Aabb {
+        let center = self.center.transformed(m, t);
+        let mut halfsize = Vector3::zeros();
+
+        for i in 0..3 {
+            for j in 0..3 {
+                halfsize[i] += m[(i, j)].abs() * self.halfsize[j];
+            }
+        }
+
+        Aabb::new(center, halfsize)
+    }
 }
 
 impl Intersects<Aabb> for Aabb {
@@ -88,6 +140,12 @@ impl Intersects<Aabb> for Aabb {
     }
 }
 
+impl Intersects<Line> for Aabb {
+    fn intersects(&self, line: &Line) -> bool {
+        collision::intersects_aabb_line(self, line)
+    }
+}
+
 impl Intersects<Ray> for Aabb {
     fn intersects(&self, ray: &Ray) -> bool {
         collision::intersects_aabb_ray(self, ray)
@@ -106,14 +164,18 @@ impl Intersects<Vector3> for Aabb {
     }
 }
 
+impl Distance<Vector3> for Aabb {
+    fn distance(&self, point: &Vector3) -> f64 {
+        collision::distance_aabb_vector3(self, point)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
     #[test]
     fn test_aabb_planes() {
-        use crate::geometry::Distance;
-
         let planes = Aabb::unit().planes();
 
         assert_eq!(planes.len(), 6);
@@ -124,4 +186,42 @@ mod test {
         assert_eq!(planes[4].distance(&Vector3::new(0., 0., -0.5)), 0.);
         assert_eq!(planes[5].distance(&Vector3::new(0., 0., 0.5)), 0.);
     }
+
+    #[test]
+    fn test_aabb_contains_aabb() {
+        let aabb = Aabb::unit();
+        let inside = Aabb::new(Vector3::new(0.25, 0., 0.), Vector3::ones() * 0.25);
+        let straddling = Aabb::new(Vector3::new(0.5, 0., 0.), Vector3::ones() * 0.25);
+
+        assert!(aabb.contains_aabb(&aabb));
+        assert!(aabb.contains_aabb(&inside));
+        assert!(!aabb.contains_aabb(&straddling));
+        assert!(!inside.contains_aabb(&aabb));
+    }
+
+    #[test]
+    fn test_aabb_subdivide() {
+        let aabb = Aabb::new(Vector3::new(1., 2., 3.), Vector3::new(1., 2., 4.));
+        let children = aabb.subdivide();
+
+        let mut min = Vector3::ones() * f64::INFINITY;
+        let mut max = Vector3::ones() * f64::NEG_INFINITY;
+        let mut volume = 0.;
+
+        for (i, child) in children.iter().enumerate() {
+            assert_eq!(*child, aabb.octant(i));
+
+            for j in 0..3 {
+                min[j] = min[j].min(child.min()[j]);
+                max[j] = max[j].max(child.max()[j]);
+            }
+
+            let h = child.halfsize();
+            volume += 8. * h[0] * h[1] * h[2];
+        }
+
+        assert_eq!(min, aabb.min());
+        assert_eq!(max, aabb.max());
+        assert_eq!(volume, 64.);
+    }
 }
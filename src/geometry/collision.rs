@@ -1,4 +1,5 @@
 pub mod aabb_aabb;
+pub mod aabb_line;
 pub mod aabb_ray;
 pub mod aabb_sphere;
 pub mod aabb_triangle;
@@ -15,19 +16,20 @@ pub mod triangle_vector3;
 
 /// Re-exports
 pub use aabb_aabb::intersects_aabb_aabb;
-pub use aabb_ray::intersects_aabb_ray;
+pub use aabb_line::intersects_aabb_line;
+pub use aabb_ray::{intersection_aabb_ray, intersects_aabb_ray};
 pub use aabb_sphere::intersects_aabb_sphere;
 pub use aabb_triangle::intersects_aabb_triangle;
-pub use aabb_vector3::intersects_aabb_vector3;
+pub use aabb_vector3::{distance_aabb_vector3, intersects_aabb_vector3};
 pub use line_plane::*;
 pub use plane_vector3::distance_plane_vector3;
-pub use ray_sphere::intersects_ray_sphere;
-pub use ray_triangle::intersects_ray_triangle;
+pub use ray_sphere::{intersection_ray_sphere, intersects_ray_sphere};
+pub use ray_triangle::{hit_ray_triangle, intersection_ray_triangle, intersects_ray_triangle};
 pub use sphere_sphere::intersects_sphere_sphere;
-pub use sphere_triangle::intersects_sphere_triangle;
+pub use sphere_triangle::{intersects_sphere_triangle, sphere_triangle_contact};
 pub use sphere_vector3::intersects_sphere_vector3;
-pub use triangle_triangle::intersects_triangle_triangle;
-pub use triangle_vector3::intersects_triangle_vector3;
+pub use triangle_triangle::{intersects_coplanar_triangle_triangle, intersects_triangle_triangle};
+pub use triangle_vector3::{distance_triangle_vector3, intersects_triangle_vector3};
 
 /// Check if the two geometries spatially intersect.
 pub trait Intersects<T> {
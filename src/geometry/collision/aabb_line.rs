@@ -0,0 +1,79 @@
+use crate::geometry::{Aabb, Line, EPSILON};
+
+/// Check if the Aabb and Line (segment) intersect
+pub fn intersects_aabb_line(aabb: &Aabb, line: &Line) -> bool {
+    let min = aabb.min();
+    let max = aabb.max();
+    let origin = line.p();
+    let direction = line.q() - line.p();
+
+    let mut tmin: f64 = 0.;
+    let mut tmax: f64 = 1.;
+
+    for i in 0..3 {
+        if direction[i].abs() < EPSILON {
+            if origin[i] < min[i] - EPSILON || origin[i] > max[i] + EPSILON {
+                return false;
+            }
+        } else {
+            let inv = 1. / direction[i];
+            let t1 = (min[i] - origin[i]) * inv;
+            let t2 = (max[i] - origin[i]) * inv;
+            tmin = tmin.max(t1.min(t2));
+            tmax = tmax.min(t1.max(t2));
+        }
+    }
+
+    tmax >= tmin
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::Vector3;
+
+    #[test]
+    fn test_aabb_line_ok_inside() {
+        let p = Vector3::new(-0.1, 0., 0.);
+        let q = Vector3::new(0.1, 0.2, 0.);
+        let line = Line::new(p, q);
+
+        assert!(intersects_aabb_line(&Aabb::unit(), &line));
+    }
+
+    #[test]
+    fn test_aabb_line_ok_crossing() {
+        let p = Vector3::new(-1., -1., -1.);
+        let q = Vector3::new(1., 1., 1.);
+        let line = Line::new(p, q);
+
+        assert!(intersects_aabb_line(&Aabb::unit(), &line));
+    }
+
+    #[test]
+    fn test_aabb_line_ok_axis_aligned_face() {
+        let p = Vector3::new(0.5, 0.5, -1.);
+        let q = Vector3::new(0.5, 0.5, 1.);
+        let line = Line::new(p, q);
+
+        assert!(intersects_aabb_line(&Aabb::unit(), &line));
+    }
+
+    #[test]
+    fn test_aabb_line_fail_short() {
+        let p = Vector3::new(-2., 0., 0.);
+        let q = Vector3::new(-1., 0., 0.);
+        let line = Line::new(p, q);
+
+        assert!(!intersects_aabb_line(&Aabb::unit(), &line));
+    }
+
+    #[test]
+    fn test_aabb_line_fail_beside() {
+        let p = Vector3::new(-1., 1., 0.);
+        let q = Vector3::new(1., 1., 0.);
+        let line = Line::new(p, q);
+
+        assert!(!intersects_aabb_line(&Aabb::unit(), &line));
+    }
+}
@@ -2,20 +2,87 @@ use crate::geometry::{Aabb, Ray};
 
 /// Check if the Aabb and Ray intersect
 pub fn intersects_aabb_ray(aabb: &Aabb, ray: &Ray) -> bool {
+    intersection_aabb_ray(aabb, ray).is_some()
+}
+
+/// Compute the parameter along the Ray (in units of the ray direction) at
+/// which it enters the Aabb. If the ray originates inside the box, this is
+/// zero.
+pub fn intersection_aabb_ray(aabb: &Aabb, ray: &Ray) -> Option<f64> {
     let min = aabb.min();
     let max = aabb.max();
-    let inv = ray.direction().inv();
+    let direction = ray.direction();
+    let inv = direction.inv();
     let origin = ray.origin();
 
-    let mut tmin = std::f64::NEG_INFINITY;
-    let mut tmax = std::f64::INFINITY;
+    let mut tmin = f64::NEG_INFINITY;
+    let mut tmax = f64::INFINITY;
 
     for i in 0..3 {
+        // A ray parallel to the slab only intersects from within it. This
+        // also avoids the NaN from zero times infinity on the slab boundary.
+        if direction[i] == 0. {
+            if origin[i] < min[i] || origin[i] > max[i] {
+                return None;
+            }
+
+            continue;
+        }
+
         let t1 = (min[i] - origin[i]) * inv[i];
         let t2 = (max[i] - origin[i]) * inv[i];
         tmin = tmin.max(t1.min(t2));
-        tmax = tmax.min(t1.min(t2));
+        tmax = tmax.min(t1.max(t2));
     }
 
-    tmax >= tmin.max(0.)
+    let tmin = tmin.max(0.);
+
+    if tmax >= tmin {
+        Some(tmin)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::Vector3;
+
+    #[test]
+    fn test_intersection_aabb_ray() {
+        let aabb = Aabb::unit();
+        let ray = Ray::new(Vector3::new(-2., 0.1, 0.2), Vector3::new(2., 0., 0.));
+
+        assert_eq!(intersection_aabb_ray(&aabb, &ray), Some(0.75));
+        assert!(intersects_aabb_ray(&aabb, &ray));
+    }
+
+    #[test]
+    fn test_intersection_aabb_ray_inside() {
+        let aabb = Aabb::unit();
+        let ray = Ray::new(Vector3::zeros(), Vector3::new(1., 1., 0.));
+
+        assert_eq!(intersection_aabb_ray(&aabb, &ray), Some(0.));
+    }
+
+    #[test]
+    fn test_intersection_aabb_ray_on_face() {
+        let aabb = Aabb::new(Vector3::new(0.25, 0.25, 0.25), Vector3::ones() * 0.25);
+        let ray = Ray::new(Vector3::new(-1., 0., 0.), Vector3::new(1., 0., 0.));
+
+        assert_eq!(intersection_aabb_ray(&aabb, &ray), Some(1.));
+    }
+
+    #[test]
+    fn test_intersection_aabb_ray_miss() {
+        let aabb = Aabb::unit();
+
+        let ray = Ray::new(Vector3::new(-2., 0., 0.), Vector3::new(-1., 0., 0.));
+        assert_eq!(intersection_aabb_ray(&aabb, &ray), None);
+
+        let ray = Ray::new(Vector3::new(-2., 0., 0.), Vector3::new(1., 1.5, 0.));
+        assert_eq!(intersection_aabb_ray(&aabb, &ray), None);
+        assert!(!intersects_aabb_ray(&aabb, &ray));
+    }
 }
@@ -12,3 +12,37 @@ pub fn intersects_aabb_vector3(aabb: &Aabb, v: &Vector3) -> bool {
         && v.z() >= min.z() - EPSILON
         && v.z() <= max.z() + EPSILON
 }
+
+/// Compute the distance from the Vector3 to the closest point of the Aabb.
+/// This is zero for points inside the Aabb.
+pub fn distance_aabb_vector3(aabb: &Aabb, v: &Vector3) -> f64 {
+    let min = aabb.min();
+    let max = aabb.max();
+    let mut closest = *v;
+
+    for i in 0..3 {
+        closest[i] = v[i].clamp(min[i], max[i]);
+    }
+
+    (*v - closest).mag()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_distance_aabb_vector3() {
+        let aabb = Aabb::unit();
+
+        assert_eq!(
+            distance_aabb_vector3(&aabb, &Vector3::new(0.1, 0.2, 0.)),
+            0.
+        );
+        assert_eq!(distance_aabb_vector3(&aabb, &Vector3::new(1.5, 0., 0.)), 1.);
+        assert_eq!(
+            distance_aabb_vector3(&aabb, &Vector3::new(3.5, 4.5, 0.)),
+            5.
+        );
+    }
+}
@@ -1,10 +1,153 @@
 use crate::geometry::{Ray, Sphere, Vector3};
 
-/// Check if the Ray/Sphere intersect
+/// Check if the Ray/Sphere intersect using the point of closest approach
 pub fn intersects_ray_sphere(ray: &Ray, sphere: &Sphere) -> bool {
+    let d = ray.direction().unit();
     let u = sphere.center() - ray.origin();
-    let v = ray.direction().unit();
+    let r2 = sphere.radius() * sphere.radius();
+
+    let dist2 = Vector3::dot(&u, &u);
+
+    // The ray originates inside the sphere
+    if dist2 <= r2 {
+        return true;
+    }
+
+    let projection = Vector3::dot(&u, &d);
+
+    // The sphere is behind the ray origin
+    if projection < 0. {
+        return false;
+    }
+
+    dist2 - projection * projection <= r2
+}
+
+/// Compute the entry and exit parameters of the Ray through the Sphere in
+/// units of the ray direction. If the ray originates inside the sphere, the
+/// entry parameter is negative.
+pub fn intersection_ray_sphere(ray: &Ray, sphere: &Sphere) -> Option<(f64, f64)> {
+    let d = ray.direction();
+    let m = ray.origin() - sphere.center();
     let r = sphere.radius();
 
-    r * r - (Vector3::dot(&u, &u) - Vector3::dot(&u, &v)) >= 0.
+    let a = Vector3::dot(&d, &d);
+    let b = Vector3::dot(&m, &d);
+    let c = Vector3::dot(&m, &m) - r * r;
+
+    // The ray originates outside of and points away from the sphere
+    if c > 0. && b > 0. {
+        return None;
+    }
+
+    let discriminant = b * b - a * c;
+
+    if discriminant < 0. {
+        return None;
+    }
+
+    let s = discriminant.sqrt();
+    let t0 = (-b - s) / a;
+    let t1 = (-b + s) / a;
+
+    Some((t0, t1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_intersects_ray_sphere_ahead() {
+        let sphere = Sphere::new(Vector3::new(5., 0.5, 0.), 1.);
+        let ray = Ray::new(Vector3::zeros(), Vector3::new(3., 0., 0.));
+
+        assert!(intersects_ray_sphere(&ray, &sphere));
+    }
+
+    #[test]
+    fn test_intersects_ray_sphere_behind() {
+        let sphere = Sphere::new(Vector3::new(-5., 0., 0.), 1.);
+        let ray = Ray::new(Vector3::zeros(), Vector3::new(3., 0., 0.));
+
+        assert!(!intersects_ray_sphere(&ray, &sphere));
+    }
+
+    #[test]
+    fn test_intersects_ray_sphere_grazing() {
+        let sphere = Sphere::new(Vector3::new(5., 2., 0.), 2.);
+        let ray = Ray::new(Vector3::zeros(), Vector3::new(1., 0., 0.));
+
+        assert!(intersects_ray_sphere(&ray, &sphere));
+
+        let ray = Ray::new(Vector3::new(0., -1e-6, 0.), Vector3::new(1., 0., 0.));
+
+        assert!(!intersects_ray_sphere(&ray, &sphere));
+    }
+
+    #[test]
+    fn test_intersection_ray_sphere() {
+        let sphere = Sphere::new(Vector3::zeros(), 1.);
+        let ray = Ray::new(Vector3::new(-3., 0., 0.), Vector3::new(2., 0., 0.));
+
+        let (t0, t1) = intersection_ray_sphere(&ray, &sphere).unwrap();
+
+        assert_eq!((t0, t1), (1., 2.));
+        assert_eq!(ray.point_at(t0), Vector3::new(-1., 0., 0.));
+        assert_eq!(ray.point_at(t1), Vector3::new(1., 0., 0.));
+        assert!(intersects_ray_sphere(&ray, &sphere));
+
+        let (p, q) = ray.sphere_points(&sphere).unwrap();
+
+        assert_eq!(p, Vector3::new(-1., 0., 0.));
+        assert_eq!(q, Vector3::new(1., 0., 0.));
+    }
+
+    #[test]
+    fn test_intersection_ray_sphere_tangent() {
+        let sphere = Sphere::new(Vector3::zeros(), 1.);
+        let ray = Ray::new(Vector3::new(-3., 1., 0.), Vector3::new(1., 0., 0.));
+
+        let (t0, t1) = intersection_ray_sphere(&ray, &sphere).unwrap();
+
+        assert_eq!(t0, t1);
+        assert_eq!(ray.point_at(t0), Vector3::new(0., 1., 0.));
+
+        let (p, q) = ray.sphere_points(&sphere).unwrap();
+
+        assert_eq!(p, q);
+        assert_eq!(p, Vector3::new(0., 1., 0.));
+    }
+
+    #[test]
+    fn test_intersection_ray_sphere_miss() {
+        let sphere = Sphere::new(Vector3::zeros(), 1.);
+
+        let ray = Ray::new(Vector3::new(-3., 2., 0.), Vector3::new(1., 0., 0.));
+        assert!(intersection_ray_sphere(&ray, &sphere).is_none());
+        assert!(ray.sphere_points(&sphere).is_none());
+        assert!(!intersects_ray_sphere(&ray, &sphere));
+
+        let ray = Ray::new(Vector3::new(-3., 0., 0.), Vector3::new(-1., 0., 0.));
+        assert!(intersection_ray_sphere(&ray, &sphere).is_none());
+        assert!(!intersects_ray_sphere(&ray, &sphere));
+    }
+
+    #[test]
+    fn test_intersection_ray_sphere_inside() {
+        let sphere = Sphere::new(Vector3::zeros(), 1.);
+        let ray = Ray::new(Vector3::new(0., 0., 0.5), Vector3::new(0., 0., 1.));
+
+        let (t0, t1) = intersection_ray_sphere(&ray, &sphere).unwrap();
+
+        assert_eq!((t0, t1), (-1.5, 0.5));
+        assert_eq!(ray.point_at(t1), Vector3::new(0., 0., 1.));
+
+        let ray = Ray::new(Vector3::zeros(), Vector3::new(0., 0., 1.));
+
+        let (p, q) = ray.sphere_points(&sphere).unwrap();
+
+        assert_eq!(p, Vector3::zeros());
+        assert_eq!(q, Vector3::new(0., 0., 1.));
+    }
 }
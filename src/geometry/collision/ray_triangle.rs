@@ -1,4 +1,4 @@
-use crate::geometry::{Ray, Triangle, Vector3, EPSILON};
+use crate::geometry::{Ray, RayHit, Triangle, Vector3, EPSILON};
 
 /// Check if the Ray/Triangle intersect
 pub fn intersects_ray_triangle(ray: &Ray, triangle: &Triangle) -> bool {
@@ -31,3 +31,119 @@ pub fn intersects_ray_triangle(ray: &Ray, triangle: &Triangle) -> bool {
 
     d_inv * Vector3::dot(&e2, &q) > EPSILON
 }
+
+/// Compute the distance along the Ray to its intersection with the Triangle.
+/// Unlike intersects_ray_triangle, this does not cull back faces.
+pub fn intersection_ray_triangle(ray: &Ray, triangle: &Triangle) -> Option<f64> {
+    hit_ray_triangle(ray, triangle).map(|hit| hit.t())
+}
+
+/// Compute the Ray/Triangle hit including its distance along the Ray, the
+/// hit point, and the barycentric coordinates of the hit point. Back faces
+/// are not culled.
+pub fn hit_ray_triangle(ray: &Ray, triangle: &Triangle) -> Option<RayHit> {
+    let e1 = triangle[1] - triangle[0];
+    let e2 = triangle[2] - triangle[0];
+    let direction = ray.direction();
+    let origin = ray.origin();
+
+    let p = Vector3::cross(&direction, &e2);
+    let d = Vector3::dot(&e1, &p);
+
+    if d.abs() < EPSILON {
+        return None;
+    }
+
+    let d_inv = 1. / d;
+    let s = origin - triangle[0];
+    let u = d_inv * Vector3::dot(&s, &p);
+
+    if !(0. ..=1.).contains(&u) {
+        return None;
+    }
+
+    let q = Vector3::cross(&s, &e1);
+    let v = d_inv * Vector3::dot(&direction, &q);
+
+    if v < 0. || u + v > 1. {
+        return None;
+    }
+
+    let t = d_inv * Vector3::dot(&e2, &q);
+
+    if t > EPSILON {
+        let point = ray.point_at(t);
+        let barycentric = Vector3::new(1. - u - v, u, v);
+        Some(RayHit::new(t, point, barycentric))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_intersection_ray_triangle() {
+        let p = Vector3::new(0., 0., 0.);
+        let q = Vector3::new(1., 0., 0.);
+        let r = Vector3::new(0., 1., 0.);
+        let triangle = Triangle::new(p, q, r);
+
+        let origin = Vector3::new(0.25, 0.25, 2.);
+        let direction = Vector3::new(0., 0., -1.);
+        let ray = Ray::new(origin, direction);
+
+        assert_eq!(intersection_ray_triangle(&ray, &triangle), Some(2.));
+
+        let origin = Vector3::new(0.25, 0.25, -2.);
+        let direction = Vector3::new(0., 0., 1.);
+        let ray = Ray::new(origin, direction);
+
+        assert_eq!(intersection_ray_triangle(&ray, &triangle), Some(2.));
+    }
+
+    #[test]
+    fn test_intersection_ray_triangle_miss() {
+        let p = Vector3::new(0., 0., 0.);
+        let q = Vector3::new(1., 0., 0.);
+        let r = Vector3::new(0., 1., 0.);
+        let triangle = Triangle::new(p, q, r);
+
+        let origin = Vector3::new(0.75, 0.75, 2.);
+        let direction = Vector3::new(0., 0., -1.);
+        let ray = Ray::new(origin, direction);
+
+        assert_eq!(intersection_ray_triangle(&ray, &triangle), None);
+
+        let origin = Vector3::new(0.25, 0.25, 2.);
+        let direction = Vector3::new(0., 0., 1.);
+        let ray = Ray::new(origin, direction);
+
+        assert_eq!(intersection_ray_triangle(&ray, &triangle), None);
+    }
+
+    #[test]
+    fn test_hit_ray_triangle() {
+        let p = Vector3::new(0., 0., 0.);
+        let q = Vector3::new(2., 0., 0.);
+        let r = Vector3::new(0., 2., 0.);
+        let triangle = Triangle::new(p, q, r);
+
+        let origin = Vector3::new(0.5, 0.25, 3.);
+        let direction = Vector3::new(0., 0., -2.);
+        let ray = Ray::new(origin, direction);
+
+        let hit = hit_ray_triangle(&ray, &triangle).unwrap();
+
+        assert!((hit.t() - 1.5).abs() < EPSILON);
+        assert!((hit.point() - Vector3::new(0.5, 0.25, 0.)).mag() < EPSILON);
+        assert!((hit.barycentric() - Vector3::new(0.625, 0.25, 0.125)).mag() < EPSILON);
+        assert!((triangle.point_from_barycentric(hit.barycentric()) - hit.point()).mag() < EPSILON);
+
+        let ray = Ray::new(origin, -direction);
+
+        assert!(hit_ray_triangle(&ray, &triangle).is_none());
+    }
+}
@@ -1,6 +1,76 @@
-use crate::geometry::{Sphere, Triangle};
+use crate::geometry::{Sphere, Triangle, Vector3};
 
 /// Check for a spatial intersection between an Sphere and Triangle
-pub fn intersects_sphere_triangle(_sphere: &Sphere, _triangle: &Triangle) -> bool {
-    unimplemented!()
+pub fn intersects_sphere_triangle(sphere: &Sphere, triangle: &Triangle) -> bool {
+    let closest = triangle.closest_point(sphere.center());
+    (closest - sphere.center()).mag() <= sphere.radius()
+}
+
+/// Compute the contact between a Sphere and Triangle. This returns the
+/// closest point on the triangle and the penetration depth if they overlap.
+pub fn sphere_triangle_contact(sphere: &Sphere, triangle: &Triangle) -> Option<(Vector3, f64)> {
+    let closest = triangle.closest_point(sphere.center());
+    let distance = (closest - sphere.center()).mag();
+
+    if distance <= sphere.radius() {
+        Some((closest, sphere.radius() - distance))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::EPSILON;
+
+    fn get_triangle() -> Triangle {
+        let p = Vector3::new(0., 0., 0.);
+        let q = Vector3::new(1., 0., 0.);
+        let r = Vector3::new(0., 1., 0.);
+        Triangle::new(p, q, r)
+    }
+
+    #[test]
+    fn test_sphere_triangle_contact_face() {
+        let triangle = get_triangle();
+        let sphere = Sphere::new(Vector3::new(0.25, 0.25, 0.3), 0.5);
+
+        let (point, depth) = sphere_triangle_contact(&sphere, &triangle).unwrap();
+
+        assert_eq!(point, Vector3::new(0.25, 0.25, 0.));
+        assert!((depth - 0.2).abs() < EPSILON);
+        assert!(intersects_sphere_triangle(&sphere, &triangle));
+    }
+
+    #[test]
+    fn test_sphere_triangle_contact_edge() {
+        let triangle = get_triangle();
+        let sphere = Sphere::new(Vector3::new(0.75, 0.75, 0.), 0.5);
+
+        let (point, depth) = sphere_triangle_contact(&sphere, &triangle).unwrap();
+
+        assert!((point - Vector3::new(0.5, 0.5, 0.)).mag() < EPSILON);
+        assert!((depth - (0.5 - 0.125_f64.sqrt())).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_sphere_triangle_contact_vertex() {
+        let triangle = get_triangle();
+        let sphere = Sphere::new(Vector3::new(-0.3, -0.4, 0.), 0.6);
+
+        let (point, depth) = sphere_triangle_contact(&sphere, &triangle).unwrap();
+
+        assert_eq!(point, Vector3::new(0., 0., 0.));
+        assert!((depth - 0.1).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_sphere_triangle_contact_miss() {
+        let triangle = get_triangle();
+        let sphere = Sphere::new(Vector3::new(0.25, 0.25, 1.), 0.5);
+
+        assert!(sphere_triangle_contact(&sphere, &triangle).is_none());
+        assert!(!intersects_sphere_triangle(&sphere, &triangle));
+    }
 }
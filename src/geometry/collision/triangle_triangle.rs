@@ -102,6 +102,14 @@ pub fn intersects_triangle_triangle(t1: &Triangle, t2: &Triangle) -> bool {
     true
 }
 
+/// Check for an intersection between two coplanar Triangles by projecting
+/// both onto the axis-aligned plane most parallel to the first Triangle.
+pub fn intersects_coplanar_triangle_triangle(t1: &Triangle, t2: &Triangle) -> bool {
+    let (v0, v1, v2) = (t1.p(), t1.q(), t1.r());
+    let (u0, u1, u2) = (t2.p(), t2.q(), t2.r());
+    coplanar_tri_tri(t1.normal(), v0, v1, v2, u0, u1, u2)
+}
+
 #[derive(Debug, Copy, Clone, Default)]
 struct Interval {
     a: f64,
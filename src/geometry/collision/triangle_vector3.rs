@@ -1,4 +1,4 @@
-use crate::geometry::{Triangle, Vector3};
+use crate::geometry::{Triangle, Vector3, EPSILON};
 
 /// Check for a spatial intersection between the Triangle and Vector3
 pub fn intersects_triangle_vector3(triangle: &Triangle, v: &Vector3) -> bool {
@@ -6,53 +6,49 @@ pub fn intersects_triangle_vector3(triangle: &Triangle, v: &Vector3) -> bool {
     let q = triangle.q();
     let r = triangle.r();
 
+    // The tolerances are relative to the longest edge so the test is
+    // independent of the scale of the triangle.
+    let scale = (q - p).mag().max((r - q).mag()).max((p - r).mag());
+    let tol = EPSILON * scale;
+
     // Check if the point is inside the axis-aligned bounding box of the triangle
     // and if not, reject the intersection.
     for i in 0..3 {
-        if v[i] > p[i].max(q[i]).max(r[i]) {
+        if v[i] > p[i].max(q[i]).max(r[i]) + tol {
             return false;
         }
 
-        if v[i] < p[i].min(q[i]).min(r[i]) {
+        if v[i] < p[i].min(q[i]).min(r[i]) - tol {
             return false;
         }
     }
 
-    // For each triangle side, make a vector out of it by subtracting the
-    // vertices. Make another vector from one vertex to point v. The cross
-    // product of these two vectors is orthogonal to both and the signs of
-    // its components indicate whether v is inside or outside of the triangle.
-    let vect12 = p - q;
-    let vect1h = p - *v;
-    let cross12_1p = Vector3::cross(&vect12, &vect1h);
-    let sign12 = sign3(cross12_1p);
-
-    let vect23 = q - r;
-    let vect2h = q - *v;
-    let cross23_2p = Vector3::cross(&vect23, &vect2h);
-    let sign23 = sign3(cross23_2p);
+    // Reject the point if it is not in the plane of the triangle (or the
+    // triangle is degenerate).
+    let normal = Vector3::cross(&(q - p), &(r - p));
+    let mag = normal.mag();
 
-    let vect31 = r - p;
-    let vect3h = r - *v;
-    let cross31_3p = Vector3::cross(&vect31, &vect3h);
-    let sign31 = sign3(cross31_3p);
-
-    sign12 & sign23 & sign31 != 0
-}
+    if mag < tol * scale || (Vector3::dot(&normal, &(*v - p)) / mag).abs() > tol {
+        return false;
+    }
 
-fn sign3(a: Vector3) -> usize {
-    const EPSILON: f64 = 1e-5;
+    // For each triangle side, the point must not be outside of the side
+    // relative to the triangle normal.
+    for (a, b) in [(p, q), (q, r), (r, p)] {
+        let u = b - a;
+        let side = Vector3::dot(&Vector3::cross(&u, &(*v - a)), &normal);
 
-    let mut sign: usize = 0;
+        if side / (mag * u.mag()) < -EPSILON {
+            return false;
+        }
+    }
 
-    sign |= if a.x() < EPSILON { 4 } else { 0 };
-    sign |= if a.x() > -EPSILON { 32 } else { 0 };
-    sign |= if a.y() < EPSILON { 2 } else { 0 };
-    sign |= if a.y() > -EPSILON { 16 } else { 0 };
-    sign |= if a.z() < EPSILON { 1 } else { 0 };
-    sign |= if a.z() > -EPSILON { 8 } else { 0 };
+    true
+}
 
-    sign
+/// Compute the minimum distance between the Triangle and Vector3
+pub fn distance_triangle_vector3(triangle: &Triangle, v: &Vector3) -> f64 {
+    (triangle.closest_point(*v) - *v).mag()
 }
 
 #[cfg(test)]
@@ -60,9 +56,13 @@ mod test {
     use super::*;
 
     fn get_triangle() -> Triangle {
+        get_triangle_scaled(1.)
+    }
+
+    fn get_triangle_scaled(scale: f64) -> Triangle {
         let p = Vector3::new(0., 0., 0.);
-        let q = Vector3::new(1., 0., 0.);
-        let r = Vector3::new(1., 1., 0.);
+        let q = Vector3::new(1., 0., 0.) * scale;
+        let r = Vector3::new(1., 1., 0.) * scale;
         Triangle::new(p, q, r)
     }
 
@@ -105,4 +105,76 @@ mod test {
 
         assert!(!intersects);
     }
+
+    #[test]
+    fn test_triangle_vector3_fail_outside() {
+        let triangle = get_triangle();
+        let point = Vector3::new(0.1, 0.9, 0.);
+
+        let intersects = intersects_triangle_vector3(&triangle, &point);
+
+        assert!(!intersects);
+    }
+
+    #[test]
+    fn test_triangle_vector3_edge() {
+        let triangle = get_triangle();
+        let point = Vector3::new(0.5, 0.5, 0.);
+
+        let intersects = intersects_triangle_vector3(&triangle, &point);
+
+        assert!(intersects);
+    }
+
+    #[test]
+    fn test_triangle_vector3_small() {
+        let triangle = get_triangle_scaled(1e-6);
+
+        let point = Vector3::new(0.9e-6, 0.5e-6, 0.);
+        assert!(intersects_triangle_vector3(&triangle, &point));
+
+        let point = Vector3::new(0.9e-6, 0.5e-6, 1e-9);
+        assert!(!intersects_triangle_vector3(&triangle, &point));
+    }
+
+    #[test]
+    fn test_triangle_vector3_large() {
+        let triangle = get_triangle_scaled(1e6);
+
+        let point = Vector3::new(0.9e6, 0.5e6, 1e-3);
+        assert!(intersects_triangle_vector3(&triangle, &point));
+
+        let point = Vector3::new(0.9e6, 0.5e6, 1.);
+        assert!(!intersects_triangle_vector3(&triangle, &point));
+    }
+
+    #[test]
+    fn test_distance_triangle_vector3_face() {
+        let triangle = get_triangle();
+        let point = Vector3::new(0.75, 0.25, 2.);
+
+        let distance = distance_triangle_vector3(&triangle, &point);
+
+        assert!((distance - 2.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_distance_triangle_vector3_edge() {
+        let triangle = get_triangle();
+        let point = Vector3::new(0.5, -1., 0.);
+
+        let distance = distance_triangle_vector3(&triangle, &point);
+
+        assert!((distance - 1.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_distance_triangle_vector3_vertex() {
+        let triangle = get_triangle();
+        let point = Vector3::new(2., 2., 1.);
+
+        let distance = distance_triangle_vector3(&triangle, &point);
+
+        assert!((distance - 3f64.sqrt()).abs() < EPSILON);
+    }
 }
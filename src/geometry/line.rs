@@ -1,5 +1,7 @@
 use crate::geometry::collision;
-use crate::geometry::{Intersection, Plane, Vector3};
+use crate::geometry::{
+    Aabb, Bounded, Intersection, Intersects, Matrix3, Plane, Transform, Vector3,
+};
 
 #[derive(Debug, Copy, Clone)]
 pub struct Line {
@@ -22,6 +24,19 @@ impl Line {
     pub fn q(&self) -> Vector3 {
         self.q
     }
+
+    /// Compute the closest point on the Line (segment) to a point
+    pub fn closest_point(&self, v: Vector3) -> Vector3 {
+        let u = self.q - self.p;
+        let d = Vector3::dot(&u, &u);
+
+        if d == 0. {
+            return self.p;
+        }
+
+        let t = (Vector3::dot(&(v - self.p), &u) / d).clamp(0., 1.);
+        self.p + u * t
+    }
 }
 
 impl std::ops::Index<usize> for Line {
@@ -46,6 +61,35 @@ impl std::ops::IndexMut<usize> for Line {
     }
 }
 
+impl Bounded for Line {
+    fn aabb(&self) -> Aabb {
+        let min = Vector3::new(
+            self.p[0].min(self.q[0]),
+            self.p[1].min(self.q[1]),
+            self.p[2].min(self.q[2]),
+        );
+        let max = Vector3::new(
+            self.p[0].max(self.q[0]),
+            self.p[1].max(self.q[1]),
+            self.p[2].max(self.q[2]),
+        );
+
+        Aabb::from_bounds(min, max)
+    }
+}
+
+impl Transform for Line {
+    fn transformed(&self, m: &Matrix3, t: &Vector3) -> Line {
+        Line::new(self.p.transformed(m, t), self.q.transformed(m, t))
+    }
+}
+
+impl Intersects<Aabb> for Line {
+    fn intersects(&self, aabb: &Aabb) -> bool {
+        collision::intersects_aabb_line(aabb, self)
+    }
+}
+
 impl Intersection<Plane> for Line {
     type Output = Vector3;
 
@@ -53,3 +97,32 @@ impl Intersection<Plane> for Line {
         collision::intersection_line_plane(self, plane)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_closest_point_interior() {
+        let line = Line::new(Vector3::zeros(), Vector3::new(2., 0., 0.));
+        let point = line.closest_point(Vector3::new(0.5, 1., 0.));
+
+        assert_eq!(point, Vector3::new(0.5, 0., 0.));
+    }
+
+    #[test]
+    fn test_closest_point_endpoint() {
+        let line = Line::new(Vector3::zeros(), Vector3::new(2., 0., 0.));
+
+        assert_eq!(line.closest_point(Vector3::new(-1., 1., 0.)), line.p());
+        assert_eq!(line.closest_point(Vector3::new(3., 1., 0.)), line.q());
+    }
+
+    #[test]
+    fn test_closest_point_degenerate() {
+        let line = Line::new(Vector3::ones(), Vector3::ones());
+        let point = line.closest_point(Vector3::zeros());
+
+        assert_eq!(point, Vector3::ones());
+    }
+}
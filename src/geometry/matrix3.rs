@@ -0,0 +1,261 @@
+use crate::geometry::{Vector3, EPSILON};
+
+/// Matrix3 of 3x3 real values stored in row-major order.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Matrix3 {
+    data: [[f64; 3]; 3],
+}
+
+impl Matrix3 {
+    /// Construct a Matrix3 from its rows
+    pub fn new(data: [[f64; 3]; 3]) -> Matrix3 {
+        Matrix3 { data }
+    }
+
+    /// Construct a Matrix3 of all zeros
+    pub fn zeros() -> Matrix3 {
+        Matrix3::new([[0.; 3]; 3])
+    }
+
+    /// Construct the identity Matrix3
+    pub fn identity() -> Matrix3 {
+        Matrix3::new([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]])
+    }
+
+    /// Construct the outer product u v^T
+    pub fn outer(u: &Vector3, v: &Vector3) -> Matrix3 {
+        let mut matrix = Matrix3::zeros();
+
+        for i in 0..3 {
+            for j in 0..3 {
+                matrix.data[i][j] = u[i] * v[j];
+            }
+        }
+
+        matrix
+    }
+
+    /// Construct the rotation by an angle (radians) about an axis
+    pub fn rotation(axis: &Vector3, angle: f64) -> Matrix3 {
+        let u = axis.unit();
+        let (sin, cos) = angle.sin_cos();
+        let cross = Matrix3::new([[0., -u[2], u[1]], [u[2], 0., -u[0]], [-u[1], u[0], 0.]]);
+
+        Matrix3::identity() * cos + cross * sin + Matrix3::outer(&u, &u) * (1. - cos)
+    }
+
+    /// Compute the transpose
+    pub fn transpose(&self) -> Matrix3 {
+        let mut matrix = Matrix3::zeros();
+
+        for i in 0..3 {
+            for j in 0..3 {
+                matrix.data[i][j] = self.data[j][i];
+            }
+        }
+
+        matrix
+    }
+
+    /// Compute the determinant
+    pub fn determinant(&self) -> f64 {
+        let [a, b, c] = self.data;
+
+        a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+            + a[2] * (b[0] * c[1] - b[1] * c[0])
+    }
+
+    /// Compute the largest singular value
+    pub fn max_singular_value(&self) -> f64 {
+        let mut gram = Matrix3::zeros();
+
+        for row in self.data {
+            let row = Vector3::new(row[0], row[1], row[2]);
+            gram += Matrix3::outer(&row, &row);
+        }
+
+        let (values, _) = gram.symmetric_eigen();
+        values[2].max(0.).sqrt()
+    }
+
+    /// Compute the eigenvalues and eigenvectors of a symmetric matrix using
+    /// the cyclic Jacobi method. The eigenvalues are sorted in ascending order
+    /// and the unit eigenvectors are returned in the same order.
+    pub fn symmetric_eigen(&self) -> (Vector3, [Vector3; 3]) {
+        let mut a = self.data;
+        let mut v = Matrix3::identity().data;
+
+        for _ in 0..50 {
+            let off = a[0][1].powi(2) + a[0][2].powi(2) + a[1][2].powi(2);
+
+            if off.sqrt() < EPSILON * EPSILON {
+                break;
+            }
+
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                if a[p][q].abs() < f64::MIN_POSITIVE {
+                    continue;
+                }
+
+                let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+                let c = 1. / (t * t + 1.).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut() {
+                    let akp = row[p];
+                    let akq = row[q];
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+
+                let ap = a[p];
+                let aq = a[q];
+                a[p] = std::array::from_fn(|k| c * ap[k] - s * aq[k]);
+                a[q] = std::array::from_fn(|k| s * ap[k] + c * aq[k]);
+
+                for row in v.iter_mut() {
+                    let vkp = row[p];
+                    let vkq = row[q];
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+
+        let mut order = [0, 1, 2];
+        order.sort_by(|&i, &j| a[i][i].total_cmp(&a[j][j]));
+
+        let values = Vector3::new(
+            a[order[0]][order[0]],
+            a[order[1]][order[1]],
+            a[order[2]][order[2]],
+        );
+        let vectors = order.map(|j| Vector3::new(v[0][j], v[1][j], v[2][j]).unit());
+
+        (values, vectors)
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Matrix3 {
+    type Output = f64;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.data[index.0][index.1]
+    }
+}
+
+impl std::ops::IndexMut<(usize, usize)> for Matrix3 {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.data[index.0][index.1]
+    }
+}
+
+impl std::ops::Add<Matrix3> for Matrix3 {
+    type Output = Matrix3;
+
+    fn add(self, other: Matrix3) -> Self::Output {
+        let mut matrix = self;
+
+        for i in 0..3 {
+            for j in 0..3 {
+                matrix.data[i][j] += other.data[i][j];
+            }
+        }
+
+        matrix
+    }
+}
+
+impl std::ops::AddAssign<Matrix3> for Matrix3 {
+    fn add_assign(&mut self, other: Matrix3) {
+        *self = *self + other;
+    }
+}
+
+impl std::ops::Mul<f64> for Matrix3 {
+    type Output = Matrix3;
+
+    fn mul(self, other: f64) -> Self::Output {
+        Matrix3::new(self.data.map(|row| row.map(|value| value * other)))
+    }
+}
+
+impl std::ops::Mul<Vector3> for Matrix3 {
+    type Output = Vector3;
+
+    fn mul(self, other: Vector3) -> Self::Output {
+        let row = |i: usize| Vector3::new(self.data[i][0], self.data[i][1], self.data[i][2]);
+        Vector3::new(
+            Vector3::dot(&row(0), &other),
+            Vector3::dot(&row(1), &other),
+            Vector3::dot(&row(2), &other),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_outer() {
+        let u = Vector3::new(1., 2., 3.);
+        let v = Vector3::new(4., 5., 6.);
+        let matrix = Matrix3::outer(&u, &v);
+
+        assert_eq!(matrix[(0, 0)], 4.);
+        assert_eq!(matrix[(1, 2)], 12.);
+        assert_eq!(matrix.transpose(), Matrix3::outer(&v, &u));
+    }
+
+    #[test]
+    fn test_rotation() {
+        let matrix = Matrix3::rotation(&Vector3::new(0., 0., 2.), std::f64::consts::FRAC_PI_2);
+        let rotated = matrix * Vector3::new(1., 0., 0.);
+
+        assert!((rotated - Vector3::new(0., 1., 0.)).mag() < EPSILON);
+        assert!((matrix.max_singular_value() - 1.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_determinant() {
+        let matrix = Matrix3::new([[2., 1., 0.], [1., 3., 0.], [0., 4., -1.]]);
+
+        assert_eq!(matrix.determinant(), -5.);
+        assert_eq!(Matrix3::identity().determinant(), 1.);
+
+        let matrix = Matrix3::rotation(&Vector3::new(1., 2., 3.), 0.7);
+
+        assert!((matrix.determinant() - 1.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_max_singular_value() {
+        let matrix = Matrix3::new([[0., 3., 0.], [-2., 0., 0.], [0., 0., 1.]]);
+
+        assert!((matrix.max_singular_value() - 3.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let matrix = Matrix3::new([[2., 1., 0.], [1., 2., 0.], [0., 0., 5.]]);
+        let (values, vectors) = matrix.symmetric_eigen();
+
+        assert!((values - Vector3::new(1., 3., 5.)).mag() < EPSILON);
+
+        for i in 0..3 {
+            let residual = matrix * vectors[i] - vectors[i] * values[i];
+            assert!(residual.mag() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_symmetric_eigen_diagonal() {
+        let matrix = Matrix3::new([[3., 0., 0.], [0., 1., 0.], [0., 0., 2.]]);
+        let (values, vectors) = matrix.symmetric_eigen();
+
+        assert_eq!(values, Vector3::new(1., 2., 3.));
+        assert_eq!(vectors[0], Vector3::new(0., 1., 0.));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::geometry::collision;
-use crate::geometry::{Distance, Intersection, Line, Vector3};
+use crate::geometry::{Distance, Intersection, Line, Matrix3, Transform, Vector3};
 
 #[derive(Debug, Copy, Clone)]
 pub struct Plane {
@@ -31,6 +31,33 @@ impl Plane {
     pub fn d(&self) -> f64 {
         self.d
     }
+
+    /// Project a point onto the plane along the normal
+    pub fn project(&self, v: &Vector3) -> Vector3 {
+        let scale = Vector3::dot(&self.normal, &self.normal);
+        *v - self.normal * (self.distance(v) / scale)
+    }
+}
+
+impl Transform for Plane {
+    /// Transform a point and two tangents of the plane, which preserves the
+    /// normal magnitude under rigid transforms
+    fn transformed(&self, m: &Matrix3, t: &Vector3) -> Plane {
+        let scale = Vector3::dot(&self.normal, &self.normal);
+        let origin = self.normal * (-self.d / scale);
+
+        let mut axis = Vector3::zeros();
+        axis[self.normal.abs().argmin()] = 1.;
+
+        let u = Vector3::cross(&self.normal, &axis).unit();
+        let v = Vector3::cross(&self.normal, &u);
+
+        Plane::from_points(
+            origin.transformed(m, t),
+            (origin + u).transformed(m, t),
+            (origin + v).transformed(m, t),
+        )
+    }
 }
 
 impl Distance<Vector3> for Plane {
@@ -46,3 +73,20 @@ impl Intersection<Line> for Plane {
         collision::intersection_line_plane(line, self)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::EPSILON;
+
+    #[test]
+    fn test_project() {
+        let plane = Plane::new(Vector3::new(0., 0., 2.), -2.);
+        let point = Vector3::new(1., 2., 3.);
+
+        let projected = plane.project(&point);
+
+        assert!((projected - Vector3::new(1., 2., 1.)).mag() < EPSILON);
+        assert!(plane.distance(&projected).abs() < EPSILON);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::geometry::collision::{Clip, Distance, Intersection, Intersects};
-use crate::geometry::{Aabb, Line, Plane, Triangle, Vector3};
+use crate::geometry::{Aabb, Line, Plane, Triangle, Vector3, EPSILON};
 
 #[derive(Debug, Clone)]
 pub struct Polygon {
@@ -17,6 +17,15 @@ impl Polygon {
         &self.vertices
     }
 
+    /// Compute the centroid of the vertices
+    pub fn centroid(&self) -> Vector3 {
+        let n = self.vertices.len() as f64;
+        self.vertices
+            .iter()
+            .fold(Vector3::zeros(), |acc, &v| acc + v)
+            / n
+    }
+
     /// Get the lines defining the boundary.
     pub fn lines(&self) -> Vec<Line> {
         let mut lines = vec![];
@@ -31,72 +40,186 @@ impl Polygon {
         lines
     }
 
-    /// Compute the triangulation of the polygon.
+    /// Compute the triangulation of the polygon by ear clipping. The
+    /// triangles keep the winding of the vertices.
     pub fn triangulate(&self) -> Vec<Triangle> {
+        self.triangulate_indices()
+            .iter()
+            .map(|&[i, j, k]| Triangle::new(self.vertices[i], self.vertices[j], self.vertices[k]))
+            .collect()
+    }
+
+    /// Compute the triangulation of the polygon by ear clipping as indices
+    /// into the vertices. Unlike the triangles from triangulate, these are
+    /// unambiguous when the polygon has coincident vertices.
+    pub fn triangulate_indices(&self) -> Vec<[usize; 3]> {
         if self.vertices.len() < 3 {
             return vec![];
         }
 
+        let normal = self.normal();
         let mut triangles = vec![];
         let mut remaining: Vec<usize> = (0..self.vertices.len()).collect();
 
         while remaining.len() > 3 {
             let n = remaining.len();
 
-            for i in 0..n {
-                if self.is_ear(remaining[i]) {
-                    let j = if i == 0 { n - 1 } else { (i - 1) % n };
-                    let k = (i + 1) % n;
+            // Clip the first vertex if there is no ear (a degenerate or
+            // self-intersecting polygon) so the triangulation terminates
+            let i = (0..n)
+                .find(|&i| self.is_ear(&remaining, i, &normal))
+                .unwrap_or(0);
+
+            triangles.push([
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            ]);
+            remaining.remove(i);
+        }
+
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+        triangles
+    }
+
+    /// Compute the unit normal of the best-fit plane using Newell's method.
+    /// The normal is oriented by the winding of the vertices.
+    pub fn normal(&self) -> Vector3 {
+        let n = self.vertices.len();
+        let mut normal = Vector3::zeros();
+
+        for i in 0..n {
+            let p = self.vertices[i];
+            let q = self.vertices[(i + 1) % n];
+            normal += Vector3::cross(&p, &q);
+        }
+
+        normal.unit()
+    }
+
+    /// Inset the polygon by moving each edge inward by a distance within the
+    /// best-fit plane and intersecting the adjacent offset edges. This
+    /// returns None if the polygon collapses (any edge vanishes or reverses).
+    pub fn inset(&self, distance: f64) -> Option<Polygon> {
+        let n = self.vertices.len();
+
+        if n < 3 {
+            return None;
+        }
+
+        let normal = self.normal();
+        let directions = (0..n)
+            .map(|i| (self.vertices[(i + 1) % n] - self.vertices[i]).unit())
+            .collect::<Vec<Vector3>>();
+        let inward = directions
+            .iter()
+            .map(|d| Vector3::cross(&normal, d))
+            .collect::<Vec<Vector3>>();
+
+        let mut vertices = vec![];
+
+        for i in 0..n {
+            let u = inward[(i + n - 1) % n];
+            let v = inward[i];
+            let scale = 1. + Vector3::dot(&u, &v);
+
+            if scale < EPSILON {
+                return None;
+            }
+
+            vertices.push(self.vertices[i] + (u + v) * (distance / scale));
+        }
+
+        for i in 0..n {
+            let edge = vertices[(i + 1) % n] - vertices[i];
+
+            if Vector3::dot(&edge, &directions[i]) < EPSILON {
+                return None;
+            }
+        }
 
-                    let p = self.vertices[remaining[j]];
-                    let q = self.vertices[remaining[i]];
-                    let r = self.vertices[remaining[k]];
-                    let triangle = Triangle::new(p, q, r);
+        Some(Polygon::new(vertices))
+    }
 
-                    triangles.push(triangle);
-                    remaining.remove(i);
-                    break;
+    /// Split the polygon by a plane into the parts in front of and behind the
+    /// plane. Vertices within the geometric tolerance of the plane are shared
+    /// by both parts and a part is None if it would be degenerate.
+    pub fn split(&self, plane: &Plane) -> (Option<Polygon>, Option<Polygon>) {
+        let scale = plane.normal().mag();
+        let n = self.vertices.len();
+        let mut front = vec![];
+        let mut back = vec![];
+
+        let sides = self
+            .vertices
+            .iter()
+            .map(|v| {
+                let d = plane.distance(v) / scale;
+
+                if d > EPSILON {
+                    1
+                } else if d < -EPSILON {
+                    -1
+                } else {
+                    0
+                }
+            })
+            .collect::<Vec<i32>>();
+
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let p = self.vertices[i];
+
+            if sides[i] >= 0 {
+                front.push(p);
+            }
+
+            if sides[i] <= 0 {
+                back.push(p);
+            }
+
+            if sides[i] * sides[j] < 0 {
+                let line = Line::new(p, self.vertices[j]);
+
+                if let Some(t) = plane.intersection(&line) {
+                    front.push(t);
+                    back.push(t);
                 }
             }
         }
 
-        let p = self.vertices[remaining[0]];
-        let q = self.vertices[remaining[1]];
-        let r = self.vertices[remaining[2]];
-        let triangle = Triangle::new(p, q, r);
-        triangles.push(triangle);
+        let front = (front.len() >= 3 && sides.contains(&1)).then(|| Polygon::new(front));
+        let back = (back.len() >= 3 && sides.contains(&-1)).then(|| Polygon::new(back));
 
-        triangles
+        (front, back)
     }
 
-    /// Check if the vertex is an ear for triangulation.
-    fn is_ear(&self, index: usize) -> bool {
+    /// Check if the remaining vertex is an ear for triangulation.
+    fn is_ear(&self, remaining: &[usize], index: usize, normal: &Vector3) -> bool {
         // Compute the indices of the vertices defining the triangle
-        let n = self.vertices.len();
-        let pi = if index == 0 { n - 1 } else { (index - 1) % n };
-        let qi = index;
-        let ri = (index + 1) % n;
+        let n = remaining.len();
+        let pi = remaining[(index + n - 1) % n];
+        let qi = remaining[index];
+        let ri = remaining[(index + 1) % n];
 
         let p = self.vertices[pi];
         let q = self.vertices[qi];
         let r = self.vertices[ri];
 
         // Check if the angle is convex at q
-        let u = p - q;
+        let u = q - p;
         let v = r - q;
 
-        if Vector3::angle(&u, &v) >= std::f64::consts::PI {
+        if Vector3::dot(&Vector3::cross(&u, &v), normal) < EPSILON {
             return false;
         }
 
-        // Check if any other point in the polygon lies inside the triangle
+        // Check if any other remaining point lies inside the triangle
         let triangle = Triangle::new(p, q, r);
 
-        for (j, point) in self.vertices.iter().enumerate() {
-            if j != pi && j != qi && j != ri {
-                if triangle.intersects(point) {
-                    return false;
-                }
+        for &j in remaining.iter() {
+            if j != pi && j != qi && j != ri && triangle.intersects(&self.vertices[j]) {
+                return false;
             }
         }
 
@@ -271,6 +394,78 @@ mod test {
         assert_eq!(result.vertices[3], Vector3::new(0., 0.5, 0.5));
     }
 
+    #[test]
+    fn test_split_polygon_plane() {
+        let p = Vector3::new(0., 0., 0.);
+        let q = Vector3::new(1., 0., 0.);
+        let r = Vector3::new(1., 1., 0.);
+        let polygon = Polygon::new(vec![p, q, r]);
+
+        let normal = Vector3::new(1., 0., 0.);
+        let plane = Plane::new(normal, -0.5);
+
+        let (front, back) = polygon.split(&plane);
+        let front = front.unwrap();
+        let back = back.unwrap();
+
+        assert_eq!(front.vertices.len(), 4);
+        assert_eq!(back.vertices.len(), 3);
+        assert_eq!(back.vertices[0], Vector3::new(0., 0., 0.));
+        assert_eq!(back.vertices[1], Vector3::new(0.5, 0., 0.));
+        assert_eq!(back.vertices[2], Vector3::new(0.5, 0.5, 0.));
+    }
+
+    #[test]
+    fn test_split_polygon_plane_vertex() {
+        let p = Vector3::new(0., 0., 0.);
+        let q = Vector3::new(1., 0., 0.);
+        let r = Vector3::new(1., 1., 0.);
+        let polygon = Polygon::new(vec![p, q, r]);
+
+        let normal = Vector3::new(1., 0., 0.);
+        let plane = Plane::new(normal, 0.);
+
+        let (front, back) = polygon.split(&plane);
+
+        assert_eq!(front.unwrap().vertices.len(), 3);
+        assert!(back.is_none());
+    }
+
+    #[test]
+    fn test_inset_polygon() {
+        let v0 = Vector3::new(0., 0., 0.);
+        let v1 = Vector3::new(1., 0., 0.);
+        let v2 = Vector3::new(1., 1., 0.);
+        let v3 = Vector3::new(0., 1., 0.);
+
+        let polygon = Polygon::new(vec![v0, v1, v2, v3]);
+        let inset = polygon.inset(0.25).unwrap();
+
+        let expected = [
+            Vector3::new(0.25, 0.25, 0.),
+            Vector3::new(0.75, 0.25, 0.),
+            Vector3::new(0.75, 0.75, 0.),
+            Vector3::new(0.25, 0.75, 0.),
+        ];
+
+        for (v, e) in inset.vertices().iter().zip(expected.iter()) {
+            assert!((*v - *e).mag() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_inset_polygon_collapse() {
+        let v0 = Vector3::new(0., 0., 0.);
+        let v1 = Vector3::new(1., 0., 0.);
+        let v2 = Vector3::new(1., 1., 0.);
+        let v3 = Vector3::new(0., 1., 0.);
+
+        let polygon = Polygon::new(vec![v0, v1, v2, v3]);
+
+        assert!(polygon.inset(0.5).is_none());
+        assert!(polygon.inset(0.75).is_none());
+    }
+
     #[test]
     fn test_triangulate_polygon_convex() {
         let v0 = Vector3::new(0., 0., 0.);
@@ -302,8 +497,8 @@ mod test {
 
         let polygon = Polygon::new(vec![v0, v1, v2, v3, v4]);
         let t0 = Triangle::new(v4, v0, v1);
-        let t1 = Triangle::new(v2, v3, v4);
-        let t2 = Triangle::new(v1, v2, v4);
+        let t1 = Triangle::new(v4, v1, v2);
+        let t2 = Triangle::new(v2, v3, v4);
 
         let triangles = polygon.triangulate();
 
@@ -313,6 +508,26 @@ mod test {
         assert_eq!(triangles[2], t2);
     }
 
+    #[test]
+    fn test_triangulate_indices_coincident() {
+        let v0 = Vector3::new(0., 0., 0.);
+        let v1 = Vector3::new(2., 0., 0.);
+        let v2 = Vector3::new(2., 2., 0.);
+        let v3 = Vector3::new(1., 1., 0.);
+        let v4 = Vector3::new(0., 2., 0.);
+
+        let polygon = Polygon::new(vec![v0, v1, v3, v2, v3, v4]);
+        let triangles = polygon.triangulate_indices();
+
+        assert_eq!(triangles.len(), 4);
+
+        for triangle in triangles.iter() {
+            assert!(triangle[0] != triangle[1]);
+            assert!(triangle[1] != triangle[2]);
+            assert!(triangle[2] != triangle[0]);
+        }
+    }
+
     #[test]
     fn test_triangulate_polygon_nonplanar() {
         let v0 = Vector3::new(0., 0., 0.);
@@ -1,5 +1,5 @@
 use crate::geometry::collision;
-use crate::geometry::{Aabb, Intersects, Sphere, Triangle, Vector3};
+use crate::geometry::{Aabb, Intersection, Intersects, Sphere, Triangle, Vector3};
 
 /// One-sided infinite ray in three-dimensional Cartesian space.
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -23,6 +23,52 @@ impl Ray {
     pub fn direction(&self) -> Vector3 {
         self.direction
     }
+
+    /// Compute the point at the parameter t along the ray
+    pub fn point_at(&self, t: f64) -> Vector3 {
+        self.origin + self.direction * t
+    }
+
+    /// Compute the entry and exit points of the ray through a sphere. If the
+    /// ray originates inside the sphere, the entry point is the ray origin.
+    pub fn sphere_points(&self, sphere: &Sphere) -> Option<(Vector3, Vector3)> {
+        let (t0, t1) = self.intersection(sphere)?;
+        Some((self.point_at(t0.max(0.)), self.point_at(t1)))
+    }
+}
+
+/// Hit of a Ray against a Triangle.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RayHit {
+    t: f64,
+    point: Vector3,
+    barycentric: Vector3,
+}
+
+impl RayHit {
+    /// Construct a RayHit from its distance, point, and barycentric coordinates
+    pub fn new(t: f64, point: Vector3, barycentric: Vector3) -> RayHit {
+        RayHit {
+            t,
+            point,
+            barycentric,
+        }
+    }
+
+    /// Get the distance along the ray (in units of the ray direction)
+    pub fn t(&self) -> f64 {
+        self.t
+    }
+
+    /// Get the hit point
+    pub fn point(&self) -> Vector3 {
+        self.point
+    }
+
+    /// Get the barycentric coordinates of the hit point
+    pub fn barycentric(&self) -> Vector3 {
+        self.barycentric
+    }
 }
 
 impl Intersects<Aabb> for Ray {
@@ -31,14 +77,38 @@ impl Intersects<Aabb> for Ray {
     }
 }
 
+impl Intersection<Aabb> for Ray {
+    type Output = f64;
+
+    fn intersection(&self, aabb: &Aabb) -> Option<Self::Output> {
+        collision::intersection_aabb_ray(aabb, self)
+    }
+}
+
 impl Intersects<Sphere> for Ray {
     fn intersects(&self, sphere: &Sphere) -> bool {
         collision::intersects_ray_sphere(self, sphere)
     }
 }
 
+impl Intersection<Sphere> for Ray {
+    type Output = (f64, f64);
+
+    fn intersection(&self, sphere: &Sphere) -> Option<Self::Output> {
+        collision::intersection_ray_sphere(self, sphere)
+    }
+}
+
 impl Intersects<Triangle> for Ray {
     fn intersects(&self, triangle: &Triangle) -> bool {
         collision::intersects_ray_triangle(self, triangle)
     }
 }
+
+impl Intersection<Triangle> for Ray {
+    type Output = RayHit;
+
+    fn intersection(&self, triangle: &Triangle) -> Option<Self::Output> {
+        collision::hit_ray_triangle(self, triangle)
+    }
+}
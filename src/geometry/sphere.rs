@@ -1,5 +1,5 @@
 use crate::geometry::collision;
-use crate::geometry::{Aabb, Intersects, Ray, Vector3};
+use crate::geometry::{Aabb, Bounded, Intersects, Matrix3, Ray, Transform, Vector3};
 
 /// Sphere in three-dimensional Cartesian space.
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -31,6 +31,22 @@ impl Sphere {
     }
 }
 
+impl Bounded for Sphere {
+    fn aabb(&self) -> Aabb {
+        Sphere::aabb(self)
+    }
+}
+
+impl Transform for Sphere {
+    /// Transform the center and scale the radius by the largest singular
+    /// value so the result encloses the transformed sphere
+    fn transformed(&self, m: &Matrix3, t: &Vector3) -> Sphere {
+        let center = self.center.transformed(m, t);
+        let radius = self.radius * m.max_singular_value();
+        Sphere::new(center, radius)
+    }
+}
+
 impl Intersects<Aabb> for Sphere {
     fn intersects(&self, aabb: &Aabb) -> bool {
         collision::intersects_aabb_sphere(aabb, self)
@@ -0,0 +1,107 @@
+use crate::geometry::{Matrix3, Vector3};
+
+/// Apply the affine transform x -> m x + t.
+pub trait Transform {
+    fn transformed(&self, m: &Matrix3, t: &Vector3) -> Self;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::{Aabb, Distance, Line, Plane, Sphere, Triangle, EPSILON};
+
+    fn rigid() -> (Matrix3, Vector3) {
+        let m = Matrix3::rotation(&Vector3::new(1., 2., 3.), 0.7);
+        let t = Vector3::new(-1., 0.5, 2.);
+        (m, t)
+    }
+
+    #[test]
+    fn test_transform_vector3() {
+        let (m, t) = rigid();
+        let u = Vector3::new(1., 2., 3.);
+        let v = Vector3::new(-2., 0., 1.);
+
+        let distance = (u.transformed(&m, &t) - v.transformed(&m, &t)).mag();
+
+        assert!((distance - (u - v).mag()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_transform_triangle() {
+        let (m, t) = rigid();
+        let triangle = Triangle::new(
+            Vector3::new(0., 0., 0.),
+            Vector3::new(2., 0., 0.),
+            Vector3::new(0., 1., 1.),
+        );
+
+        let transformed = triangle.transformed(&m, &t);
+        let normal = m * triangle.unit_normal();
+
+        assert!((transformed.area() - triangle.area()).abs() < EPSILON);
+        assert!((transformed.unit_normal() - normal).mag() < EPSILON);
+        assert!((transformed.centroid() - triangle.centroid().transformed(&m, &t)).mag() < EPSILON);
+    }
+
+    #[test]
+    fn test_transform_aabb() {
+        let (m, t) = rigid();
+        let aabb = Aabb::new(Vector3::new(1., 1., 1.), Vector3::new(1., 2., 0.5));
+        let transformed = aabb.transformed(&m, &t);
+
+        for octant in 0..8 {
+            let corner = aabb.octant(octant).center() * 2. - aabb.center();
+            let corner = corner.transformed(&m, &t);
+
+            for i in 0..3 {
+                assert!(corner[i] >= transformed.min()[i] - EPSILON);
+                assert!(corner[i] <= transformed.max()[i] + EPSILON);
+            }
+        }
+
+        let m = Matrix3::rotation(&Vector3::new(0., 0., 1.), std::f64::consts::FRAC_PI_2);
+        let transformed = aabb.transformed(&m, &t);
+
+        assert!((transformed.center() - Vector3::new(-2., 1.5, 3.)).mag() < EPSILON);
+        assert!((transformed.halfsize() - Vector3::new(2., 1., 0.5)).mag() < EPSILON);
+    }
+
+    #[test]
+    fn test_transform_sphere() {
+        let (m, t) = rigid();
+        let sphere = Sphere::new(Vector3::new(1., 0., 0.), 2.);
+        let transformed = sphere.transformed(&m, &t);
+
+        assert!((transformed.center() - sphere.center().transformed(&m, &t)).mag() < EPSILON);
+        assert!((transformed.radius() - 2.).abs() < EPSILON);
+
+        let scaled = sphere.transformed(&(m * 3.), &t);
+
+        assert!((scaled.radius() - 6.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_transform_plane() {
+        let (m, t) = rigid();
+        let plane = Plane::new(Vector3::new(0., 0., 2.), -2.);
+        let transformed = plane.transformed(&m, &t);
+        let point = Vector3::new(3., -1., 4.);
+
+        assert!((transformed.normal() - m * plane.normal()).mag() < EPSILON);
+        assert!(
+            (transformed.distance(&point.transformed(&m, &t)) - plane.distance(&point)).abs()
+                < EPSILON
+        );
+    }
+
+    #[test]
+    fn test_transform_line() {
+        let (m, t) = rigid();
+        let line = Line::new(Vector3::new(0., 1., 0.), Vector3::new(3., 1., 4.));
+        let transformed = line.transformed(&m, &t);
+
+        assert!(((transformed.q() - transformed.p()).mag() - 5.).abs() < EPSILON);
+        assert!((transformed.p() - line.p().transformed(&m, &t)).mag() < EPSILON);
+    }
+}
@@ -1,8 +1,11 @@
 use crate::geometry::collision;
-use crate::geometry::{Aabb, Intersects, Ray, Sphere, Vector3};
+use crate::geometry::{
+    Aabb, Bounded, Distance, Intersects, Matrix3, Ray, Sphere, Transform, Vector3,
+};
 
 /// Triangle in three-dimensional Cartesian space
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Triangle {
     p: Vector3,
     q: Vector3,
@@ -65,11 +68,12 @@ impl Triangle {
         (self.p + self.q + self.r) / 3.
     }
 
-    /// Compute the Barycentric coordinate (u, v, w).
-    pub fn barycenter(&self) -> Vector3 {
+    /// Compute the Barycentric coordinate (u, v, w) of a point such that the
+    /// point is u * p + v * q + w * r.
+    pub fn barycenter(&self, point: Vector3) -> Vector3 {
         let v0 = self.q - self.p;
-        let v1 = self.r - self.q;
-        let v2 = self.p - self.r;
+        let v1 = self.r - self.p;
+        let v2 = point - self.p;
 
         let d00 = Vector3::dot(&v0, &v0);
         let d01 = Vector3::dot(&v0, &v1);
@@ -79,11 +83,76 @@ impl Triangle {
 
         let d = d00 * d11 - d01 * d01;
         let v = (d11 * d20 - d01 * d21) / d;
-        let w = (d00 * d21 - d00 * d20) / d;
+        let w = (d00 * d21 - d01 * d20) / d;
         let u = 1. - v - w;
 
         Vector3::new(u, v, w)
     }
+
+    /// Compute the point from its Barycentric coordinate (u, v, w) as
+    /// u * p + v * q + w * r.
+    pub fn point_from_barycentric(&self, bary: Vector3) -> Vector3 {
+        self.p * bary[0] + self.q * bary[1] + self.r * bary[2]
+    }
+
+    /// Compute the closest point on the triangle to a point
+    pub fn closest_point(&self, v: Vector3) -> Vector3 {
+        let ab = self.q - self.p;
+        let ac = self.r - self.p;
+
+        // Vertex region p
+        let ap = v - self.p;
+        let d1 = Vector3::dot(&ab, &ap);
+        let d2 = Vector3::dot(&ac, &ap);
+
+        if d1 <= 0. && d2 <= 0. {
+            return self.p;
+        }
+
+        // Vertex region q
+        let bp = v - self.q;
+        let d3 = Vector3::dot(&ab, &bp);
+        let d4 = Vector3::dot(&ac, &bp);
+
+        if d3 >= 0. && d4 <= d3 {
+            return self.q;
+        }
+
+        // Edge region pq
+        let vc = d1 * d4 - d3 * d2;
+
+        if vc <= 0. && d1 >= 0. && d3 <= 0. {
+            return self.p + ab * (d1 / (d1 - d3));
+        }
+
+        // Vertex region r
+        let cp = v - self.r;
+        let d5 = Vector3::dot(&ab, &cp);
+        let d6 = Vector3::dot(&ac, &cp);
+
+        if d6 >= 0. && d5 <= d6 {
+            return self.r;
+        }
+
+        // Edge region pr
+        let vb = d5 * d2 - d1 * d6;
+
+        if vb <= 0. && d2 >= 0. && d6 <= 0. {
+            return self.p + ac * (d2 / (d2 - d6));
+        }
+
+        // Edge region qr
+        let va = d3 * d6 - d5 * d4;
+
+        if va <= 0. && (d4 - d3) >= 0. && (d5 - d6) >= 0. {
+            let t = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+            return self.q + (self.r - self.q) * t;
+        }
+
+        // Face region
+        let d = 1. / (va + vb + vc);
+        self.p + ab * (vb * d) + ac * (vc * d)
+    }
 }
 
 impl std::ops::Index<usize> for Triangle {
@@ -110,6 +179,22 @@ impl std::ops::IndexMut<usize> for Triangle {
     }
 }
 
+impl Bounded for Triangle {
+    fn aabb(&self) -> Aabb {
+        Triangle::aabb(self)
+    }
+}
+
+impl Transform for Triangle {
+    fn transformed(&self, m: &Matrix3, t: &Vector3) -> Triangle {
+        Triangle::new(
+            self.p.transformed(m, t),
+            self.q.transformed(m, t),
+            self.r.transformed(m, t),
+        )
+    }
+}
+
 impl Intersects<Aabb> for Triangle {
     fn intersects(&self, aabb: &Aabb) -> bool {
         collision::intersects_aabb_triangle(aabb, self)
@@ -139,3 +224,108 @@ impl Intersects<Vector3> for Triangle {
         collision::intersects_triangle_vector3(self, v)
     }
 }
+
+impl Distance<Vector3> for Triangle {
+    fn distance(&self, v: &Vector3) -> f64 {
+        collision::distance_triangle_vector3(self, v)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::EPSILON;
+
+    fn get_triangle() -> Triangle {
+        let p = Vector3::new(0., 0., 0.);
+        let q = Vector3::new(2., 0., 0.);
+        let r = Vector3::new(0., 2., 0.);
+        Triangle::new(p, q, r)
+    }
+
+    #[test]
+    fn test_barycenter() {
+        let triangle = Triangle::new(
+            Vector3::new(1., -2., 0.5),
+            Vector3::new(3., 1., 0.),
+            Vector3::new(-1., 2., 2.),
+        );
+
+        let uvw = triangle.barycenter(triangle.centroid());
+        assert!((uvw - Vector3::ones() / 3.).mag() < EPSILON);
+
+        let basis = [
+            Vector3::new(1., 0., 0.),
+            Vector3::new(0., 1., 0.),
+            Vector3::new(0., 0., 1.),
+        ];
+
+        for (i, expected) in basis.iter().enumerate() {
+            assert!((triangle.barycenter(triangle[i]) - *expected).mag() < EPSILON);
+        }
+
+        let point = triangle.p() * 0.2 + triangle.q() * 0.3 + triangle.r() * 0.5;
+        let uvw = triangle.barycenter(point);
+        assert!((uvw - Vector3::new(0.2, 0.3, 0.5)).mag() < EPSILON);
+    }
+
+    #[test]
+    fn test_point_from_barycentric() {
+        let triangle = Triangle::new(
+            Vector3::new(1., -2., 0.5),
+            Vector3::new(3., 1., 0.),
+            Vector3::new(-1., 2., 2.),
+        );
+
+        let points = [
+            triangle.centroid(),
+            triangle.p(),
+            (triangle.q() + triangle.r()) * 0.5,
+            triangle.p() * 0.1 + triangle.q() * 0.6 + triangle.r() * 0.3,
+            triangle.p() * 1.5 - triangle.q() + triangle.r() * 0.5,
+        ];
+
+        for point in points {
+            let bary = triangle.barycenter(point);
+            let other = triangle.point_from_barycentric(bary);
+
+            assert!((other - point).mag() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_closest_point_face() {
+        let triangle = get_triangle();
+        let point = triangle.closest_point(Vector3::new(0.5, 0.5, 1.));
+
+        assert_eq!(point, Vector3::new(0.5, 0.5, 0.));
+        assert_eq!(triangle.distance(&Vector3::new(0.5, 0.5, 1.)), 1.);
+    }
+
+    #[test]
+    fn test_closest_point_edge() {
+        let triangle = get_triangle();
+        let point = triangle.closest_point(Vector3::new(2., 2., 0.));
+
+        assert_eq!(point, Vector3::new(1., 1., 0.));
+        assert_eq!(Vector3::new(1., -1., 0.).distance(&triangle), 1.);
+    }
+
+    #[test]
+    fn test_closest_point_vertex() {
+        let triangle = get_triangle();
+
+        assert_eq!(
+            triangle.closest_point(Vector3::new(-1., -1., 0.)),
+            triangle.p()
+        );
+        assert_eq!(
+            triangle.closest_point(Vector3::new(3., -1., 1.)),
+            triangle.q()
+        );
+        assert_eq!(
+            triangle.closest_point(Vector3::new(-1., 3., 0.)),
+            triangle.r()
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use crate::geometry::collision;
-use crate::geometry::{Aabb, Distance, Intersects, Plane, Sphere, Triangle};
+use crate::geometry::{
+    Aabb, Bounded, Distance, Intersects, Matrix3, Plane, Sphere, Transform, Triangle,
+};
 
 /// Vector3 in three-dimensional Cartesian space.
 #[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector3 {
     x: f64,
     y: f64,
@@ -89,6 +92,53 @@ impl Vector3 {
         }
     }
 
+    /// Rotate by an angle (radians) about an axis using Rodrigues' rotation
+    /// formula. The axis is normalized internally.
+    pub fn rotate(&self, axis: Vector3, angle: f64) -> Vector3 {
+        let k = axis.unit();
+        let (sin, cos) = angle.sin_cos();
+
+        *self * cos + Vector3::cross(&k, self) * sin + k * (Vector3::dot(&k, self) * (1. - cos))
+    }
+
+    /// Compute the vector projection onto another vector
+    pub fn project_onto(&self, other: &Vector3) -> Vector3 {
+        *other * (Vector3::dot(self, other) / Vector3::dot(other, other))
+    }
+
+    /// Compute the reflection about the plane with a unit normal
+    pub fn reflect(&self, normal: &Vector3) -> Vector3 {
+        *self - *normal * (2. * Vector3::dot(self, normal))
+    }
+
+    /// Compute the linear interpolation towards another vector where t = 0
+    /// is this vector and t = 1 is the other
+    pub fn lerp(&self, other: &Vector3, t: f64) -> Vector3 {
+        *self + (*other - *self) * t
+    }
+
+    /// Get the components as an array
+    pub fn to_array(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Check if each component is equal to that of another vector within an
+    /// absolute tolerance
+    pub fn approx_eq(&self, other: &Vector3, tol: f64) -> bool {
+        (0..3).all(|i| (self[i] - other[i]).abs() <= tol)
+    }
+
+    /// Check if each component is equal to that of another vector within a
+    /// tolerance relative to the larger magnitude of the two components.
+    /// Components smaller than one in magnitude are compared within the
+    /// absolute tolerance.
+    pub fn approx_eq_relative(&self, other: &Vector3, tol: f64) -> bool {
+        (0..3).all(|i| {
+            let scale = self[i].abs().max(other[i].abs()).max(1.);
+            (self[i] - other[i]).abs() <= tol * scale
+        })
+    }
+
     /// Compute the index of the minimal component
     pub fn argmin(&self) -> usize {
         let mut index = 0;
@@ -124,6 +174,12 @@ impl Vector3 {
     }
 }
 
+impl Transform for Vector3 {
+    fn transformed(&self, m: &Matrix3, t: &Vector3) -> Vector3 {
+        *m * *self + *t
+    }
+}
+
 impl std::ops::Index<usize> for Vector3 {
     type Output = f64;
 
@@ -148,6 +204,27 @@ impl std::ops::IndexMut<usize> for Vector3 {
     }
 }
 
+impl From<[f64; 3]> for Vector3 {
+    fn from(data: [f64; 3]) -> Vector3 {
+        Vector3::new(data[0], data[1], data[2])
+    }
+}
+
+impl From<Vector3> for [f64; 3] {
+    fn from(vector: Vector3) -> [f64; 3] {
+        vector.to_array()
+    }
+}
+
+impl IntoIterator for Vector3 {
+    type Item = f64;
+    type IntoIter = std::array::IntoIter<f64, 3>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.to_array().into_iter()
+    }
+}
+
 impl std::ops::Add<Vector3> for Vector3 {
     type Output = Vector3;
 
@@ -368,6 +445,12 @@ impl std::ops::Neg for Vector3 {
     }
 }
 
+impl Bounded for Vector3 {
+    fn aabb(&self) -> Aabb {
+        Aabb::new(*self, Vector3::zeros())
+    }
+}
+
 impl Intersects<Aabb> for Vector3 {
     fn intersects(&self, aabb: &Aabb) -> bool {
         collision::intersects_aabb_vector3(aabb, self)
@@ -386,8 +469,110 @@ impl Intersects<Triangle> for Vector3 {
     }
 }
 
+impl Distance<Aabb> for Vector3 {
+    fn distance(&self, aabb: &Aabb) -> f64 {
+        collision::distance_aabb_vector3(aabb, self)
+    }
+}
+
 impl Distance<Plane> for Vector3 {
     fn distance(&self, plane: &Plane) -> f64 {
         collision::distance_plane_vector3(plane, self)
     }
 }
+
+impl Distance<Triangle> for Vector3 {
+    fn distance(&self, triangle: &Triangle) -> f64 {
+        collision::distance_triangle_vector3(triangle, self)
+    }
+}
+
+impl Distance<Vector3> for Vector3 {
+    fn distance(&self, other: &Vector3) -> f64 {
+        (*self - *other).mag()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::EPSILON;
+
+    #[test]
+    fn test_project_onto() {
+        let u = Vector3::new(2., 3., -1.);
+        let v = Vector3::new(0., 2., 0.);
+
+        assert_eq!(u.project_onto(&v), Vector3::new(0., 3., 0.));
+
+        let w = u.project_onto(&Vector3::new(1., 1., 1.));
+
+        assert!(Vector3::cross(&w, &Vector3::ones()).mag() < EPSILON);
+        assert!(Vector3::dot(&(u - w), &Vector3::ones()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_reflect() {
+        let u = Vector3::new(1., -1., 2.);
+        let normal = Vector3::new(0., 1., 0.);
+
+        assert_eq!(u.reflect(&normal), Vector3::new(1., 1., 2.));
+        assert_eq!(u.reflect(&normal).reflect(&normal), u);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let u = Vector3::new(1., 2., 3.);
+        let v = Vector3::new(3., 2., -1.);
+
+        assert_eq!(u.lerp(&v, 0.), u);
+        assert_eq!(u.lerp(&v, 1.), v);
+        assert_eq!(u.lerp(&v, 0.5), Vector3::new(2., 2., 1.));
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let u = Vector3::new(1., 2., 3.);
+        let v = u + Vector3::new(1e-10, -1e-10, 0.);
+
+        assert!(u.approx_eq(&v, EPSILON));
+        assert!(!u.approx_eq(&(u + Vector3::new(0., 0., 1e-6)), EPSILON));
+
+        let u = Vector3::new(1e9, -2e9, 0.5);
+        let v = u + Vector3::new(1., 1., 0.);
+
+        assert!(!u.approx_eq(&v, EPSILON));
+        assert!(u.approx_eq_relative(&v, EPSILON));
+        assert!(!u.approx_eq_relative(&(u + Vector3::new(0., 0., 1e-6)), EPSILON));
+    }
+
+    #[test]
+    fn test_array_conversions() {
+        let u = Vector3::from([1., 2., 3.]);
+
+        assert_eq!(u, Vector3::new(1., 2., 3.));
+        assert_eq!(u.to_array(), [1., 2., 3.]);
+
+        let data: [f64; 3] = u.into();
+
+        assert_eq!(data, [1., 2., 3.]);
+        assert_eq!(u.into_iter().collect::<Vec<f64>>(), vec![1., 2., 3.]);
+        assert_eq!(u.into_iter().sum::<f64>(), 6.);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let u = Vector3::new(1., 0., 0.);
+        let rotated = u.rotate(Vector3::new(0., 0., 3.), std::f64::consts::FRAC_PI_2);
+
+        assert!((rotated - Vector3::new(0., 1., 0.)).mag() < EPSILON);
+
+        let u = Vector3::new(1., -2., 0.5);
+        let axis = Vector3::new(0.3, 1., -2.);
+        let rotated = u.rotate(axis, 1.3);
+
+        assert!((rotated.mag() - u.mag()).abs() < EPSILON);
+        assert!((Vector3::dot(&rotated, &axis) - Vector3::dot(&u, &axis)).abs() < EPSILON);
+        assert!((rotated - Matrix3::rotation(&axis, 1.3) * u).mag() < EPSILON);
+    }
+}
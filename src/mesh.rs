@@ -1,8 +1,14 @@
 pub mod common;
 pub mod half_edge;
 pub mod helpers;
+pub mod off;
+pub mod ply;
+pub mod sparse;
+pub mod stl;
 pub mod utils;
 pub mod wavefront;
 
 // Re-exports
 pub use common::{Edge, Face, Patch, Vertex};
+pub use helpers::check_manifold;
+pub use sparse::SparseMatrix;
@@ -1,6 +1,7 @@
 use crate::geometry::Vector3;
 
 #[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vertex {
     x: f64,
     y: f64,
@@ -66,6 +67,7 @@ impl Into<Vector3> for Vertex {
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Face {
     vertices: Vec<usize>,
     patch: Option<usize>,
@@ -117,6 +119,7 @@ impl std::ops::IndexMut<usize> for Face {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Edge {
     p: usize,
     q: usize,
@@ -176,6 +179,7 @@ impl std::ops::IndexMut<usize> for Edge {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Patch {
     name: String,
 }
@@ -1,9 +1,22 @@
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
-
-use crate::geometry::{Aabb, Polygon, Sphere, Vector3, EPSILON};
-use crate::mesh::wavefront::{ObjReader, ObjWriter};
-use crate::mesh::{Face, Patch, Vertex};
-use crate::spatial::{Octree, SearchMany};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::sync::OnceLock;
+
+use rand::prelude::*;
+use rayon::prelude::*;
+
+use crate::geometry::collision;
+use crate::geometry::{
+    Aabb, Distance, Intersects, Line, Matrix3, Plane, Polygon, Ray, Sphere, Transform, Triangle,
+    Vector3, EPSILON,
+};
+use crate::mesh::helpers::{convex_hull, douglas_peucker, UnionFind};
+use crate::mesh::off::{OffReader, OffWriter};
+use crate::mesh::ply::{PlyReader, PlyWriter};
+use crate::mesh::stl::{StlReader, StlWriter};
+use crate::mesh::wavefront::{MtlWriter, ObjOptions, ObjReader, ObjWriter};
+use crate::mesh::{Face, Patch, SparseMatrix, Vertex};
+use crate::spatial::{Octree, Search, SearchMany};
 
 #[derive(Debug, Clone, Default)]
 pub struct HeMesh {
@@ -11,6 +24,8 @@ pub struct HeMesh {
     faces: Vec<HeFace>,
     half_edges: Vec<HeHalfEdge>,
     patches: Vec<HePatch>,
+    colors: Option<Vec<[f64; 3]>>,
+    cache: TopologyCache,
 }
 
 impl HeMesh {
@@ -112,25 +127,148 @@ impl HeMesh {
 
     /// Import a HeMesh from an OBJ file
     pub fn from_obj(filename: &str) -> std::io::Result<HeMesh> {
+        HeMesh::from_obj_with_options(filename, &ObjOptions::default())
+    }
+
+    /// Import a HeMesh from an OBJ file using the import options
+    pub fn from_obj_with_options(filename: &str, options: &ObjOptions) -> std::io::Result<HeMesh> {
         let mut reader = ObjReader::new(filename);
         reader.read()?;
 
-        let vertices = reader.vertices();
+        let system = options.coordinate_system();
+        let vertices = reader
+            .vertices()
+            .iter()
+            .map(|&v| system.to_y_up(v))
+            .collect();
+
         let faces = reader.faces();
         let patches = reader.patches();
-        let mesh = HeMesh::new(vertices, faces, patches);
+        let mut mesh = HeMesh::new(&vertices, faces, patches);
+
+        if !reader.colors().is_empty() && reader.colors().len() == mesh.n_vertices() {
+            mesh.colors = Some(reader.colors().clone());
+        }
+
+        Ok(mesh)
+    }
+
+    /// Import a HeMesh from an OFF file
+    pub fn from_off(filename: &str) -> std::io::Result<HeMesh> {
+        let mut reader = OffReader::new(filename);
+        reader.read()?;
+
+        Ok(HeMesh::new(reader.vertices(), reader.faces(), &vec![]))
+    }
+
+    /// Export a HeMesh to an OFF file
+    pub fn export_off(&self, filename: &str) -> std::io::Result<()> {
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|v| Vertex::from(v.point))
+            .collect();
+
+        let faces = (0..self.n_faces())
+            .map(|i| Face::new(self.face_vertices(i), None))
+            .collect();
+
+        let mut writer = OffWriter::new();
+        writer.set_vertices(vertices);
+        writer.set_faces(faces);
+        writer.write(filename)
+    }
+
+    /// Import a HeMesh from an ASCII or binary PLY file
+    pub fn from_ply(filename: &str) -> std::io::Result<HeMesh> {
+        let mut reader = PlyReader::new(filename);
+        reader.read()?;
+
+        Ok(HeMesh::new(reader.vertices(), reader.faces(), &vec![]))
+    }
+
+    /// Export a HeMesh to an ASCII PLY file
+    pub fn export_ply(&self, filename: &str) -> std::io::Result<()> {
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|v| Vertex::from(v.point))
+            .collect();
+
+        let faces = (0..self.n_faces())
+            .map(|i| Face::new(self.face_vertices(i), None))
+            .collect();
+
+        let mut writer = PlyWriter::new();
+        writer.set_vertices(vertices);
+        writer.set_faces(faces);
+        writer.write(filename)
+    }
+
+    /// Import a HeMesh from an ASCII or binary STL file. The duplicated
+    /// triangle corners are merged so the connectivity is shared. This
+    /// returns an InvalidData error if more than two facets share an edge.
+    pub fn from_stl(filename: &str) -> std::io::Result<HeMesh> {
+        let mut reader = StlReader::new(filename);
+        reader.read()?;
+
+        let mut mesh = HeMesh::new(reader.vertices(), reader.faces(), &vec![]);
+        mesh.try_merge_vertices()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
 
         Ok(mesh)
     }
 
     /// Export a HeMesh to an OBJ file
     pub fn export_obj(&self, filename: &str) -> std::io::Result<()> {
+        self.export_obj_with_options(filename, &ObjOptions::default())
+    }
+
+    /// Export a HeMesh to an OBJ file using the export options
+    pub fn export_obj_with_options(
+        &self,
+        filename: &str,
+        options: &ObjOptions,
+    ) -> std::io::Result<()> {
+        self.obj_writer(options).write(filename)
+    }
+
+    /// Format a HeMesh as the contents of an OBJ file. This is the same
+    /// content written by export_obj.
+    pub fn to_obj_string(&self) -> String {
+        self.obj_writer(&ObjOptions::default()).format()
+    }
+
+    /// Export a HeMesh to an OBJ file with a sibling MTL file of the same
+    /// name. Each patch uses a material of the same name with a distinct
+    /// diffuse color.
+    pub fn export_obj_mtl(&self, obj_path: &str) -> std::io::Result<()> {
+        let mtl_path = std::path::Path::new(obj_path).with_extension("mtl");
+        let mtl_name = mtl_path.file_name().unwrap_or_default().to_string_lossy();
+
+        let mut writer = self.obj_writer(&ObjOptions::default());
+        writer.set_material_library(&mtl_name);
+        writer.write(obj_path)?;
+
+        let mut materials = MtlWriter::new();
+
+        for (i, patch) in self.patches.iter().enumerate() {
+            let color = distinct_color(i, self.n_patches());
+            materials.add_material(patch.name(), color);
+        }
+
+        materials.write(&mtl_path.to_string_lossy())
+    }
+
+    /// Construct the ObjWriter for a HeMesh using the export options
+    fn obj_writer(&self, options: &ObjOptions) -> ObjWriter {
+        let system = options.coordinate_system();
         let mut vertices = vec![];
         let mut faces = vec![];
         let mut patches = vec![];
 
         for vertex in self.vertices.iter() {
-            let vertex = Vertex::from(vertex.point);
+            let vertex = system.from_y_up(Vertex::from(vertex.point));
             vertices.push(vertex);
         }
 
@@ -148,11 +286,63 @@ impl HeMesh {
 
         let mut writer = ObjWriter::new();
         writer.set_vertices(vertices);
+        writer.set_colors(self.colors.clone().unwrap_or_default());
         writer.set_faces(faces);
         writer.set_patches(patches);
+        writer
+    }
+
+    /// Export a HeMesh to an STL file. Faces are triangulated and any
+    /// degenerate (zero area) triangles are skipped. Files with the "stla"
+    /// extension are written as ASCII and all others as binary.
+    pub fn export_stl(&self, filename: &str) -> std::io::Result<()> {
+        let mut triangles = vec![];
+        let mut normals = vec![];
+
+        for i in 0..self.n_faces() {
+            let vertices = self
+                .face_vertices(i)
+                .iter()
+                .map(|&j| self.vertices[j].point)
+                .collect();
+
+            let normal = self.face_normal(i);
+
+            for triangle in Polygon::new(vertices).triangulate() {
+                if triangle.area() > EPSILON * EPSILON {
+                    triangles.push(triangle);
+                    normals.push(normal);
+                }
+            }
+        }
+
+        let mut writer = StlWriter::new();
+        writer.set_triangles(triangles);
+        writer.set_normals(normals);
         writer.write(filename)
     }
 
+    /// Export each contiguous component to its own OBJ file in a directory,
+    /// named by the basename and component index. This returns the paths of
+    /// the written files.
+    pub fn export_components(
+        &self,
+        directory: &str,
+        basename: &str,
+    ) -> std::io::Result<Vec<String>> {
+        let mut paths = vec![];
+
+        for (i, component) in self.components().iter().enumerate() {
+            let path = std::path::Path::new(directory).join(format!("{}_{}.obj", basename, i));
+            let path = path.to_string_lossy().to_string();
+
+            self.extract_faces(component).export_obj(&path)?;
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+
     /// Get a borrowed reference to the vertices
     pub fn vertices(&self) -> &Vec<HeVertex> {
         &self.vertices
@@ -168,6 +358,22 @@ impl HeMesh {
         self.vertices.len()
     }
 
+    /// Get a borrowed reference to the vertex colors (if defined)
+    pub fn colors(&self) -> Option<&Vec<[f64; 3]>> {
+        self.colors.as_ref()
+    }
+
+    /// Set the vertex colors. There must be a color for every vertex.
+    pub fn set_colors(&mut self, colors: Option<Vec<[f64; 3]>>) {
+        if let Some(colors) = &colors {
+            if colors.len() != self.n_vertices() {
+                panic!("colors must match the vertices");
+            }
+        }
+
+        self.colors = colors;
+    }
+
     /// Get a borrowed reference to the faces
     pub fn faces(&self) -> &Vec<HeFace> {
         &self.faces
@@ -198,6 +404,39 @@ impl HeMesh {
         self.half_edges.len()
     }
 
+    /// Get the number of unique edges. This is cached until the connectivity
+    /// is next modified.
+    pub fn n_edges(&self) -> usize {
+        *self.cache.n_edges.get_or_init(|| {
+            let n_boundary = self.n_boundary_edges();
+            n_boundary + (self.n_half_edges() - n_boundary) / 2
+        })
+    }
+
+    /// Get the number of boundary edges. This is cached until the
+    /// connectivity is next modified.
+    pub fn n_boundary_edges(&self) -> usize {
+        *self
+            .cache
+            .n_boundary_edges
+            .get_or_init(|| self.half_edges.iter().filter(|h| h.is_boundary()).count())
+    }
+
+    /// Get the number of contiguous components. This is cached until the
+    /// connectivity is next modified.
+    pub fn n_components(&self) -> usize {
+        *self
+            .cache
+            .n_components
+            .get_or_init(|| self.components().len())
+    }
+
+    /// Reset the cached topology counts. This must be called by every method
+    /// which modifies the connectivity (rather than only the positions).
+    fn invalidate(&mut self) {
+        self.cache = TopologyCache::default();
+    }
+
     /// Get a borrowed reference to the patches
     pub fn patches(&self) -> &Vec<HePatch> {
         &self.patches
@@ -213,6 +452,95 @@ impl HeMesh {
         self.patches.len()
     }
 
+    /// Get the name of the patch of a face by index
+    pub fn face_patch_name(&self, face: usize) -> Option<&str> {
+        self.faces[face].patch.map(|i| self.patches[i].name())
+    }
+
+    /// Get the index of the first patch with the name
+    pub fn patch_index(&self, name: &str) -> Option<usize> {
+        self.patches.iter().position(|p| p.name() == name)
+    }
+
+    /// Scale the vertices along each axis. If the product of the scale
+    /// factors is negative (a reflection) all faces are flipped so their
+    /// orientation relative to the surface is preserved.
+    pub fn scale_axes(&mut self, sx: f64, sy: f64, sz: f64) {
+        for vertex in self.vertices.iter_mut() {
+            let p = vertex.point;
+            vertex.point = Vector3::new(p[0] * sx, p[1] * sy, p[2] * sz);
+        }
+
+        if sx * sy * sz < 0. {
+            (0..self.n_faces()).for_each(|i| self.flip_face(i));
+        }
+    }
+
+    /// Apply the affine transform given by the rows of a 4x4 matrix to every
+    /// vertex. The last row is ignored. If the transform is a reflection
+    /// (negative determinant) and flip is true all faces are flipped so their
+    /// orientation relative to the surface is preserved.
+    pub fn transform(&mut self, rows: [[f64; 4]; 4], flip: bool) {
+        let m = Matrix3::new(std::array::from_fn(|i| {
+            [rows[i][0], rows[i][1], rows[i][2]]
+        }));
+        let t = Vector3::new(rows[0][3], rows[1][3], rows[2][3]);
+
+        for vertex in self.vertices.iter_mut() {
+            vertex.point = vertex.point.transformed(&m, &t);
+        }
+
+        if flip && m.determinant() < 0. {
+            (0..self.n_faces()).for_each(|i| self.flip_face(i));
+        }
+    }
+
+    /// Translate the vertices by an offset
+    pub fn translate(&mut self, offset: Vector3) {
+        let rows = [
+            [1., 0., 0., offset[0]],
+            [0., 1., 0., offset[1]],
+            [0., 0., 1., offset[2]],
+            [0., 0., 0., 1.],
+        ];
+
+        self.transform(rows, false);
+    }
+
+    /// Scale the vertices along each axis about the origin. Faces are flipped
+    /// for a reflection as in scale_axes.
+    pub fn scale(&mut self, factors: Vector3) {
+        let rows = [
+            [factors[0], 0., 0., 0.],
+            [0., factors[1], 0., 0.],
+            [0., 0., factors[2], 0.],
+            [0., 0., 0., 1.],
+        ];
+
+        self.transform(rows, true);
+    }
+
+    /// Rotate the vertices by an angle (radians) about an axis through the
+    /// origin
+    pub fn rotate(&mut self, axis: Vector3, angle: f64) {
+        let m = Matrix3::rotation(&axis, angle);
+        let rows = std::array::from_fn(|i| match i {
+            3 => [0., 0., 0., 1.],
+            _ => [m[(i, 0)], m[(i, 1)], m[(i, 2)], 0.],
+        });
+
+        self.transform(rows, false);
+    }
+
+    /// Project every vertex onto a plane. This flattens the mesh onto the
+    /// plane, so faces that are not parallel to the plane become degenerate
+    /// (zero area) or overlapping.
+    pub fn project_onto_plane(&mut self, plane: &Plane) {
+        for vertex in self.vertices.iter_mut() {
+            vertex.point = plane.project(&vertex.point);
+        }
+    }
+
     /// Compute the axis-aligned bounding box
     pub fn aabb(&self) -> Aabb {
         let mut min = Vector3::ones() * std::f64::INFINITY;
@@ -233,6 +561,64 @@ impl HeMesh {
         Aabb::from_bounds(min, max)
     }
 
+    /// Compute an approximate minimum-volume oriented bounding box. The axes
+    /// are the principal components of the convex hull vertices, which
+    /// gives a tight (but not necessarily minimal) fit. This returns the
+    /// rotation (with the box axes as its columns), the center and the
+    /// halfsizes along each axis.
+    pub fn obb(&self) -> (Matrix3, Vector3, Vector3) {
+        let points = self
+            .vertices
+            .iter()
+            .map(|v| v.point)
+            .collect::<Vec<Vector3>>();
+        let hull = convex_hull(&points);
+
+        let mut indices = hull.iter().flatten().copied().collect::<Vec<usize>>();
+        indices.sort();
+        indices.dedup();
+
+        if indices.is_empty() {
+            indices = (0..points.len()).collect();
+        }
+
+        let n = indices.len() as f64;
+        let mean = indices
+            .iter()
+            .fold(Vector3::zeros(), |acc, &i| acc + points[i])
+            / n;
+        let mut covariance = Matrix3::zeros();
+
+        for &i in indices.iter() {
+            let u = points[i] - mean;
+            covariance += Matrix3::outer(&u, &u);
+        }
+
+        let (_, vectors) = covariance.symmetric_eigen();
+        let axes = [
+            vectors[0],
+            vectors[1],
+            Vector3::cross(&vectors[0], &vectors[1]),
+        ];
+
+        let mut min = Vector3::ones() * f64::INFINITY;
+        let mut max = Vector3::ones() * f64::NEG_INFINITY;
+
+        for &i in indices.iter() {
+            for (k, axis) in axes.iter().enumerate() {
+                let t = Vector3::dot(axis, &(points[i] - mean));
+                min[k] = min[k].min(t);
+                max[k] = max[k].max(t);
+            }
+        }
+
+        let local = (min + max) * 0.5;
+        let center = mean + axes[0] * local[0] + axes[1] * local[1] + axes[2] * local[2];
+        let rotation = Matrix3::new(std::array::from_fn(|i| axes.map(|axis| axis[i])));
+
+        (rotation, center, (max - min) * 0.5)
+    }
+
     /// Compute if the mesh is closed
     pub fn is_closed(&self) -> bool {
         for half_edge in self.half_edges.iter() {
@@ -244,837 +630,5959 @@ impl HeMesh {
         true
     }
 
-    /// Compute if the mesh faces are consistently oriented
-    pub fn is_consistent(&self) -> bool {
-        for half_edge in self.half_edges.iter() {
-            if let Some(twin) = half_edge.twin {
-                if self.half_edges[twin].origin == half_edge.origin {
-                    return false;
-                }
-            }
-        }
+    /// Compute the total length of the boundary edges
+    pub fn total_boundary_length(&self) -> f64 {
+        self.boundary_loops()
+            .iter()
+            .map(|boundary| {
+                (0..boundary.len())
+                    .map(|i| {
+                        let p = self.vertices[boundary[i]].point;
+                        let q = self.vertices[boundary[(i + 1) % boundary.len()]].point;
+                        (q - p).mag()
+                    })
+                    .sum::<f64>()
+            })
+            .sum()
+    }
 
-        true
+    /// Compute the number of holes (boundary loops)
+    pub fn n_holes(&self) -> usize {
+        self.boundary_loops().len()
     }
 
-    /// Compute if the neighboring pair of mesh faces are consistently
-    /// oriented. If the faces do not share an edge, return false.
-    pub fn is_consistent_faces(&self, i: usize, j: usize) -> bool {
-        let mut index = HashSet::new();
+    /// Simplify each boundary loop using the Douglas-Peucker algorithm with a
+    /// distance tolerance. Each removed boundary vertex is collapsed into the
+    /// preceding kept vertex of its loop and any degenerate faces are
+    /// removed. This returns the number of removed boundary vertices.
+    pub fn simplify_boundaries(&mut self, tol: f64) -> usize {
+        self.invalidate();
 
-        for k in self.face_half_edges(i) {
-            index.insert(k);
-        }
+        let mut lookup = (0..self.n_vertices()).collect::<Vec<usize>>();
+        let mut count = 0;
 
-        for k in self.face_half_edges(j) {
-            let half_edge = &self.half_edges[k];
+        for boundary in self.boundary_loops() {
+            let points = boundary
+                .iter()
+                .map(|&i| self.vertices[i].point)
+                .collect::<Vec<Vector3>>();
+
+            // Split the closed loop at the first vertex and the vertex
+            // farthest from it into two open polylines.
+            let k = (0..points.len())
+                .max_by(|&i, &j| {
+                    let u = (points[i] - points[0]).mag();
+                    let v = (points[j] - points[0]).mag();
+                    u.total_cmp(&v)
+                })
+                .unwrap_or(0);
+
+            let mut tail = points[k..].to_vec();
+            tail.push(points[0]);
+
+            let mut keep = douglas_peucker(&points[..k + 1], tol);
+            keep.pop();
+            keep.extend(douglas_peucker(&tail, tol));
+            keep.pop();
+
+            let unique = boundary.iter().collect::<HashSet<&usize>>().len() == boundary.len();
+
+            if !unique || keep.iter().filter(|&&k| k).count() < 3 {
+                continue;
+            }
 
-            if let Some(twin) = half_edge.twin {
-                if index.contains(&twin) {
-                    let twin = &self.half_edges[twin];
-                    return half_edge.origin != twin.origin;
+            let mut target = boundary[0];
+
+            for (&i, &kept) in boundary.iter().zip(keep.iter()) {
+                if kept {
+                    target = i;
+                } else {
+                    lookup[i] = target;
+                    count += 1;
                 }
             }
         }
 
-        false
-    }
+        if count == 0 {
+            return 0;
+        }
 
-    /// Compute the neighboring vertices for a vertex by index. This is only
-    /// valid for closed oriented meshes.
-    pub fn vertex_neighbors(&self, index: usize) -> Vec<usize> {
-        let vertex = self.vertices[index];
-        let mut current = vertex.half_edge;
-        let mut neighbors = vec![];
+        let mut faces = vec![];
 
-        loop {
-            let half_edge = self.half_edges[current];
-            let prev = self.half_edges[half_edge.prev];
-            neighbors.push(prev.origin);
+        for i in 0..self.n_faces() {
+            let mut vertices = self
+                .face_vertices(i)
+                .iter()
+                .map(|&j| lookup[j])
+                .collect::<Vec<usize>>();
 
-            current = prev.twin.expect("mesh must be closed");
+            vertices.dedup();
 
-            if current == vertex.half_edge {
-                break;
+            while vertices.len() > 1 && vertices.first() == vertices.last() {
+                vertices.pop();
+            }
+
+            if vertices.len() >= 3 {
+                faces.push(Face::new(vertices, self.faces[i].patch));
             }
         }
 
-        neighbors
-    }
+        let mut indices = vec![None; self.n_vertices()];
+        let mut vertices = vec![];
+        let mut colors = vec![];
+
+        for face in faces.iter_mut() {
+            let face_vertices = face
+                .vertices()
+                .iter()
+                .map(|&j| {
+                    *indices[j].get_or_insert_with(|| {
+                        vertices.push(Vertex::from(self.vertices[j].point));
+
+                        if let Some(c) = self.colors.as_ref() {
+                            colors.push(c[j]);
+                        }
 
-    /// Compute the faces containing a vertex by index. This is only valid
-    /// for closed oriented meshes.
-    pub fn vertex_faces(&self, index: usize) -> Vec<usize> {
-        let vertex = self.vertices[index];
-        let mut current = vertex.half_edge;
-        let mut faces = vec![];
+                        vertices.len() - 1
+                    })
+                })
+                .collect();
 
-        loop {
-            let half_edge = self.half_edges[current];
-            faces.push(half_edge.face);
+            *face = Face::new(face_vertices, face.patch());
+        }
 
-            let prev = self.half_edges[half_edge.prev];
-            current = prev.twin.expect("mesh must be closed");
+        let patches = self
+            .patches
+            .iter()
+            .map(|p| Patch::new(p.name().to_string()))
+            .collect();
 
-            if current == vertex.half_edge {
-                break;
-            }
+        let has_colors = self.colors.is_some();
+        *self = HeMesh::new(&vertices, &faces, &patches);
+
+        if has_colors {
+            self.colors = Some(colors);
         }
 
-        faces
+        count
     }
 
-    /// Compute the vertices defining a face by index
-    pub fn face_vertices(&self, index: usize) -> Vec<usize> {
-        self.face_half_edges(index)
-            .iter()
-            .map(|&i| self.half_edges[i].origin)
-            .collect()
-    }
+    /// Resample the boundary loops so each boundary edge is close to a target
+    /// length. Boundary edges shorter than 4/5 of the target are collapsed
+    /// into one of their end points, provided the removed vertex lies within
+    /// a tenth of the target of the merged edge, and then boundary edges
+    /// longer than 4/3 of the target are split evenly.
+    pub fn resample_boundary(&mut self, target_length: f64) {
+        self.invalidate();
+
+        let min = target_length * 0.8;
+        let max = target_length * 4. / 3.;
+        let tol = target_length * 0.1;
+
+        let mut removed_vertices = vec![];
+        let mut removed_faces = vec![];
+        let mut removed_half_edges = vec![false; self.n_half_edges()];
+
+        for h in 0..self.n_half_edges() {
+            if removed_half_edges[h] || !self.half_edges[h].is_boundary() {
+                continue;
+            }
 
-    /// Compute the neighboring faces for a face by index
-    pub fn face_neighbors(&self, index: usize) -> Vec<usize> {
-        self.face_half_edges(index)
-            .iter()
-            .map(|&i| self.half_edges[i])
-            .filter(|h| !h.is_boundary())
-            .map(|h| self.half_edges[h.twin.unwrap()].face)
-            .collect()
-    }
+            let [i, j, k, l] = self.boundary_neighbors(h);
+            let [pi, pj, pk, pl] = [i, j, k, l].map(|v| self.vertices[v].point);
 
-    /// Compute the ordered half edges defining the boundary of a face by index
-    pub fn face_half_edges(&self, index: usize) -> Vec<usize> {
-        let face = self.faces[index];
-        let mut current = face.half_edge;
-        let mut half_edges = vec![];
+            if (pk - pj).mag() >= min {
+                continue;
+            }
 
-        loop {
-            half_edges.push(current);
-            current = self.half_edges[current].next;
+            // Either keep the origin and merge with the next boundary edge or
+            // keep the head and merge with the previous boundary edge.
+            let options = [(pj, Line::new(pj, pl), pk), (pk, Line::new(pi, pk), pj)];
 
-            if current == face.half_edge {
-                break;
+            let best = options
+                .iter()
+                .filter(|(_, line, _)| (line.q() - line.p()).mag() <= max)
+                .map(|(point, line, other)| (*point, (line.closest_point(*other) - *other).mag()))
+                .filter(|&(_, deviation)| deviation <= tol)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            if let Some((point, _)) = best {
+                if let Ok((faces, half_edges)) = self.collapse_edge_to(h, point) {
+                    removed_vertices.push(k);
+                    removed_faces.extend(faces);
+
+                    for i in half_edges {
+                        removed_half_edges[i] = true;
+                    }
+                }
             }
         }
 
-        half_edges
+        let removed_half_edges = removed_half_edges
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &removed)| removed.then_some(i))
+            .collect::<Vec<usize>>();
+
+        self.remove_elements(&removed_vertices, &removed_faces, &removed_half_edges);
+
+        for h in 0..self.n_half_edges() {
+            if !self.half_edges[h].is_boundary() {
+                continue;
+            }
+
+            let p = self.vertices[self.half_edges[h].origin].point;
+            let q = self.vertices[self.half_edges[self.half_edges[h].next].origin].point;
+            let length = (q - p).mag();
+
+            if length <= max {
+                continue;
+            }
+
+            // Split from the far end so the half edge always ends at the last
+            // inserted vertex.
+            let n = (length / target_length).round() as usize;
+
+            for m in (1..n).rev() {
+                self.split_edge(h, p + (q - p) * (m as f64 / n as f64));
+            }
+        }
     }
 
-    /// Compute the unit normal vector of a face.
-    pub fn face_normal(&self, index: usize) -> Vector3 {
-        let mut normal = Vector3::zeros();
-        let index = self.face_vertices(index);
-        let n = index.len();
+    /// Compute the boundary vertices surrounding a boundary half edge: the
+    /// previous boundary vertex, the origin, the head and the next boundary
+    /// vertex.
+    fn boundary_neighbors(&self, half_edge: usize) -> [usize; 4] {
+        let mut next = self.half_edges[half_edge].next;
 
-        for i in 0..n {
-            let p = self.vertices[index[i]].point;
-            let q = self.vertices[index[(i + 1) % n]].point;
-            normal += Vector3::cross(&p, &q);
+        while let Some(twin) = self.half_edges[next].twin {
+            next = self.half_edges[twin].next;
         }
 
-        normal.unit()
-    }
+        let mut prev = self.half_edges[half_edge].prev;
 
-    /// Compute the unit normals for all faces.
-    pub fn face_normals(&self) -> Vec<Vector3> {
-        (0..self.n_faces()).map(|i| self.face_normal(i)).collect()
+        while let Some(twin) = self.half_edges[prev].twin {
+            prev = self.half_edges[twin].prev;
+        }
+
+        [
+            self.half_edges[prev].origin,
+            self.half_edges[half_edge].origin,
+            self.half_edges[next].origin,
+            self.half_edges[self.half_edges[next].next].origin,
+        ]
     }
 
-    /// Compute the feature edges using a threshold angle in radians. This will
-    /// return the pair of half edges defining the edge.
-    pub fn feature_edges(&self, angle: f64) -> Vec<(usize, usize)> {
-        let mut visited = vec![false; self.n_half_edges()];
-        let mut features = vec![];
+    /// Bridge two boundary loops with a tube of quad faces joining matching
+    /// vertices. The loops must have the same number of vertices (resample
+    /// the boundaries first if they do not). Each loop may be given in either
+    /// direction; the second loop is matched to the first starting from its
+    /// vertex nearest the start of the first.
+    pub fn bridge(&mut self, loop_a: &[usize], loop_b: &[usize]) {
+        self.invalidate();
 
-        for (i, half_edge) in self.half_edges.iter().enumerate() {
-            if !visited[i] {
-                visited[i] = true;
+        if loop_a.len() != loop_b.len() || loop_a.len() < 3 {
+            panic!("boundary loops must have the same number of vertices");
+        }
 
-                if let Some(j) = half_edge.twin {
-                    visited[j] = true;
+        let edges = self
+            .half_edges
+            .iter()
+            .filter(|half_edge| half_edge.is_boundary())
+            .map(|half_edge| (half_edge.origin, self.half_edges[half_edge.next].origin))
+            .collect::<HashSet<(usize, usize)>>();
+
+        // Order each loop to follow the direction of its boundary half edges
+        let orient = |boundary: &[usize]| {
+            let n = boundary.len();
+            let forward = (0..n).all(|i| edges.contains(&(boundary[i], boundary[(i + 1) % n])));
+            let backward = (0..n).all(|i| edges.contains(&(boundary[(i + 1) % n], boundary[i])));
+
+            match (forward, backward) {
+                (true, _) => boundary.to_vec(),
+                (_, true) => boundary.iter().rev().copied().collect(),
+                _ => panic!("invalid boundary loop"),
+            }
+        };
+
+        let loop_a = orient(loop_a);
+        let loop_b = orient(loop_b);
+        let n = loop_a.len();
+
+        let start = self.vertices[loop_a[0]].point;
+        let offset = (0..n)
+            .min_by(|&i, &j| {
+                let u = (self.vertices[loop_b[i]].point - start).mag();
+                let v = (self.vertices[loop_b[j]].point - start).mag();
+                u.total_cmp(&v)
+            })
+            .unwrap();
 
-                    let twin = self.half_edges[j];
-                    let u = self.face_normal(half_edge.face);
-                    let v = self.face_normal(twin.face);
+        // The loops run in opposite directions along the tube so the second
+        // loop is traversed backwards to keep the faces consistently oriented.
+        let matched = |i: usize| loop_b[(offset + n - i % n) % n];
 
-                    if Vector3::angle(&u, &v) >= angle {
-                        features.push((i, j));
-                    }
-                }
-            }
+        let mut faces = (0..self.n_faces())
+            .map(|i| Face::new(self.face_vertices(i), self.faces[i].patch))
+            .collect::<Vec<Face>>();
+
+        for i in 0..n {
+            let vertices = vec![loop_a[(i + 1) % n], loop_a[i], matched(i), matched(i + 1)];
+            faces.push(Face::new(vertices, None));
         }
 
-        features
-    }
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|v| Vertex::from(v.point))
+            .collect::<Vec<Vertex>>();
 
-    /// Merge the mesh into the current mesh naively. This strictly copies
-    /// the mesh and does not merge vertices, edges, or faces.
-    pub fn merge(&mut self, other: &HeMesh) {
-        let nv = self.n_vertices();
-        let nf = self.n_faces();
-        let nh = self.n_half_edges();
-        let np = self.n_patches();
+        let patches = self
+            .patches
+            .iter()
+            .map(|p| Patch::new(p.name().to_string()))
+            .collect();
 
-        for patch in other.patches() {
-            let patch = patch.clone();
-            self.patches.push(patch);
-        }
+        let colors = self.colors.take();
+        *self = HeMesh::new(&vertices, &faces, &patches);
+        self.colors = colors;
+    }
 
-        for vertex in other.vertices().iter() {
-            let mut vertex = *vertex;
-            vertex.half_edge += nh;
-            self.vertices.push(vertex);
+    /// Fill each boundary loop with triangles by ear clipping the loop
+    /// projected onto its best-fit plane. The new faces traverse the loop
+    /// opposite to its boundary half edges so they are oriented consistently
+    /// with the surrounding faces. This returns the number of holes filled.
+    pub fn fill_holes(&mut self) -> usize {
+        self.invalidate();
+
+        let loops = self
+            .boundary_loops()
+            .into_iter()
+            .filter(|boundary| boundary.len() >= 3)
+            .collect::<Vec<Vec<usize>>>();
+
+        if loops.is_empty() {
+            return 0;
         }
 
-        for face in other.faces().iter() {
-            let mut face = *face;
-            face.half_edge += nh;
-
-            if let Some(patch) = face.patch {
-                face.patch = Some(patch + np);
+        let mut faces = (0..self.n_faces())
+            .map(|i| Face::new(self.face_vertices(i), self.faces[i].patch))
+            .collect::<Vec<Face>>();
+
+        for boundary in loops.iter() {
+            let indices = boundary.iter().rev().copied().collect::<Vec<usize>>();
+            let points = indices
+                .iter()
+                .map(|&i| self.vertices[i].point)
+                .collect::<Vec<Vector3>>();
+
+            // Project the loop onto an orthonormal basis of its plane
+            let normal = Polygon::new(points.clone()).normal();
+            let axis = if normal.x().abs() < 0.9 {
+                Vector3::new(1., 0., 0.)
+            } else {
+                Vector3::new(0., 1., 0.)
+            };
+
+            let u = Vector3::cross(&normal, &axis).unit();
+            let v = Vector3::cross(&normal, &u);
+            let projected = points
+                .iter()
+                .map(|p| Vector3::new(Vector3::dot(p, &u), Vector3::dot(p, &v), 0.))
+                .collect::<Vec<Vector3>>();
+
+            for [i, j, k] in Polygon::new(projected).triangulate_indices() {
+                let vertices = vec![indices[i], indices[j], indices[k]];
+                faces.push(Face::new(vertices, None));
             }
-
-            self.faces.push(face);
         }
 
-        for half_edge in other.half_edges().iter() {
-            let mut half_edge = *half_edge;
-            half_edge.origin += nv;
-            half_edge.face += nf;
-            half_edge.prev += nh;
-            half_edge.next += nh;
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|v| Vertex::from(v.point))
+            .collect::<Vec<Vertex>>();
 
-            if let Some(twin) = half_edge.twin {
-                half_edge.twin = Some(twin + nh);
-            }
+        let patches = self
+            .patches
+            .iter()
+            .map(|p| Patch::new(p.name().to_string()))
+            .collect();
 
-            self.half_edges.push(half_edge)
-        }
+        let colors = self.colors.take();
+        *self = HeMesh::new(&vertices, &faces, &patches);
+        self.colors = colors;
+
+        loops.len()
     }
 
-    /// Merge vertices within the geometric tolerance. This may result in a
-    /// non-manifold mesh.
-    pub fn merge_vertices(&mut self) {
-        let aabb = self.aabb();
-        let mut octree = Octree::<Vector3>::new(aabb);
-        let mut queries = vec![];
+    /// Compute the T-junction vertices. These are boundary vertices which lie
+    /// on the interior of another edge (within the tolerance) without being
+    /// one of its end points.
+    pub fn t_junctions(&self, tol: f64) -> Vec<usize> {
+        self.t_junction_edges(tol).iter().map(|&(i, _)| i).collect()
+    }
 
-        for vertex in self.vertices.iter() {
-            octree.insert(vertex.point);
+    /// Resolve the T-junctions by splitting each edge at the vertex lying on
+    /// it and joining the split edge to the matching boundary edges. This
+    /// returns the number of T-junctions resolved.
+    pub fn resolve_t_junctions(&mut self, tol: f64) -> usize {
+        self.invalidate();
 
-            let query = Sphere::new(vertex.point, EPSILON);
-            queries.push(query);
-        }
+        let mut resolved = HashSet::new();
 
-        let mut indices = BTreeMap::new();
-        let mut lookup = HashMap::new();
-        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        loop {
+            let next = self
+                .t_junction_edges(tol)
+                .into_iter()
+                .find(|(i, _)| !resolved.contains(i));
 
-        for (i, items) in octree.search_many(&queries).iter().enumerate() {
-            let index = items.iter().min().unwrap_or(&i);
-            indices.insert(*index, 0);
-            lookup.insert(i, *index);
-        }
+            let Some((vertex, half_edge)) = next else {
+                break;
+            };
 
-        for (i, (index, value)) in indices.iter_mut().enumerate() {
-            self.vertices[i] = self.vertices[*index];
-            *value = i;
+            self.split_edge_at(half_edge, vertex);
+            self.link_boundary_half_edges();
+            resolved.insert(vertex);
         }
 
-        for half_edge in self.half_edges.iter_mut() {
-            (*half_edge).origin = indices[&lookup[&half_edge.origin]];
-        }
+        resolved.len()
+    }
+
+    /// Compute the T-junction vertices and a half edge of the edge each one
+    /// lies on.
+    fn t_junction_edges(&self, tol: f64) -> Vec<(usize, usize)> {
+        let mut octree = Octree::<Line>::new(self.aabb());
+        let mut edges = vec![];
+        let mut candidates = BTreeSet::new();
 
         for (i, half_edge) in self.half_edges.iter().enumerate() {
-            if half_edge.is_boundary() {
-                let j = half_edge.origin;
-                let k = self.half_edges[half_edge.next].origin;
+            let j = half_edge.origin;
+            let k = self.half_edges[half_edge.next].origin;
 
-                edges
-                    .entry((j.min(k), j.max(k)))
-                    .and_modify(|p| p.push(i))
-                    .or_insert(vec![i]);
+            if half_edge.is_boundary() {
+                candidates.insert(j);
+                candidates.insert(k);
             }
-        }
 
-        for (_, shared) in edges.iter() {
-            if shared.len() > 2 {
-                panic!("non-manifold mesh");
+            if half_edge.twin.is_none_or(|twin| twin > i) {
+                let p = self.vertices[j].point;
+                let q = self.vertices[k].point;
+                octree.insert(Line::new(p, q));
+                edges.push((i, j, k));
             }
+        }
 
-            if shared.len() == 2 {
-                self.half_edges[shared[0]].twin = Some(shared[1]);
-                self.half_edges[shared[1]].twin = Some(shared[0]);
+        let tol = tol.max(EPSILON);
+        let mut junctions = vec![];
+
+        for &i in candidates.iter() {
+            let point = self.vertices[i].point;
+            let query = Aabb::new(point, Vector3::ones() * tol);
+            let mut found = octree.search(&query);
+            found.sort();
+
+            for index in found {
+                let (h, j, k) = edges[index];
+                let line = octree.item(index);
+                let closest = line.closest_point(point);
+
+                if i != j
+                    && i != k
+                    && (closest - point).mag() <= tol
+                    && (closest - line.p()).mag() > tol
+                    && (closest - line.q()).mag() > tol
+                {
+                    junctions.push((i, h));
+                    break;
+                }
             }
         }
 
-        self.vertices.truncate(indices.len());
+        junctions
     }
 
-    /// Combine patches with the same name explicitly.
-    pub fn remove_duplicate_patches(&mut self) {
-        let mut patches = vec![];
-        let mut index: HashMap<&str, usize> = HashMap::new();
-
-        for (i, patch) in self.patches.iter().enumerate() {
-            let name = patch.name();
+    /// Compute the ordered vertices of each boundary loop. The loops follow
+    /// the direction of their boundary half edges.
+    pub fn boundary_loops(&self) -> Vec<Vec<usize>> {
+        let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut visited = vec![false; self.n_half_edges()];
+        let mut loops = vec![];
 
-            if !index.contains_key(name) {
-                index.insert(name, i);
-                patches.push(patch.clone());
+        for (i, half_edge) in self.half_edges.iter().enumerate() {
+            if half_edge.is_boundary() {
+                outgoing.entry(half_edge.origin).or_default().push(i);
             }
         }
 
-        for face in self.faces.iter_mut() {
-            if let Some(patch) = face.patch {
-                let name = self.patches[patch].name();
-                face.patch = Some(index[name]);
+        for (i, half_edge) in self.half_edges.iter().enumerate() {
+            if !half_edge.is_boundary() || visited[i] {
+                continue;
             }
-        }
 
-        self.patches = patches;
-    }
+            let mut boundary = vec![];
+            let mut current = Some(i);
 
-    /// Extract a subset from the mesh by the index of the face. This
-    /// copies the target subset into a new mesh.
-    pub fn extract_faces(&self, face_ids: &Vec<usize>) -> HeMesh {
-        let mut faces = Vec::<Face>::with_capacity(face_ids.len());
-        let mut vertices = vec![];
-        let mut patches = vec![];
-        let mut index_vertices = HashMap::new();
-        let mut index_patches = HashMap::new();
+            while let Some(j) = current {
+                visited[j] = true;
+                boundary.push(self.half_edges[j].origin);
 
-        for &face_id in face_ids.iter() {
-            let mut vertices_ = self.face_vertices(face_id);
-            let mut patch_ = None;
+                let head = self.half_edges[self.half_edges[j].next].origin;
+                current = outgoing
+                    .get(&head)
+                    .and_then(|h| h.iter().find(|&&k| !visited[k]).copied());
+            }
 
-            for old_id in vertices_.iter_mut() {
-                if !index_vertices.contains_key(old_id) {
-                    let new_id = index_vertices.len();
-                    index_vertices.insert(*old_id, new_id);
+            loops.push(boundary);
+        }
 
-                    let point = self.vertices[*old_id].point;
-                    let vertex = Vertex::from(point);
-                    vertices.push(vertex);
-                }
+        loops
+    }
 
-                *old_id = index_vertices[old_id];
+    /// Compute the ordered vertices of each boundary loop grouped by the
+    /// component they bound in the order of components. Closed components
+    /// have no boundary loops.
+    pub fn boundary_loops_by_component(&self) -> Vec<Vec<Vec<usize>>> {
+        let components = self.components();
+        let mut lookup = vec![0; self.n_faces()];
+        let mut groups = vec![vec![]; components.len()];
+
+        for (i, component) in components.iter().enumerate() {
+            for &face in component.iter() {
+                lookup[face] = i;
             }
+        }
 
-            if let Some(old_id) = self.faces[face_id].patch {
-                if !index_patches.contains_key(&old_id) {
-                    let new_id = index_patches.len();
-                    index_patches.insert(old_id, new_id);
-
-                    let name = self.patches[old_id].name().to_string();
-                    let patch = Patch::new(name);
-                    patches.push(patch);
-                }
+        // Index the face of each boundary half edge by its end points
+        let mut faces = HashMap::new();
 
-                patch_ = Some(index_patches[&old_id]);
-            }
+        for half_edge in self.half_edges.iter().filter(|h| h.is_boundary()) {
+            let head = self.half_edges[half_edge.next].origin;
+            faces.insert((half_edge.origin, head), half_edge.face);
+        }
 
-            let face = Face::new(vertices_, patch_);
-            faces.push(face);
+        for boundary in self.boundary_loops() {
+            let edge = (boundary[0], boundary[1 % boundary.len()]);
+            groups[lookup[faces[&edge]]].push(boundary);
         }
 
-        HeMesh::new(&vertices, &faces, &patches)
+        groups
     }
 
-    /// Extract a subset from the mesh by the patch names. This copies the
-    /// target subset into a new mesh.
-    pub fn extract_patches(&self, patches: &Vec<String>) -> HeMesh {
-        let mut selected = HashSet::new();
-        let mut index = vec![false; self.n_patches()];
-        let mut faces = vec![];
+    /// Compute the pairs of faces which are coplanar (parallel normals and
+    /// vertices on the same plane within the tolerance) and overlap. Faces
+    /// which only touch along an edge or at a vertex do not overlap.
+    pub fn overlapping_coplanar_faces(&self, tol: f64) -> Vec<(usize, usize)> {
+        let mut octree = Octree::<Aabb>::new(self.aabb());
 
-        for patch in patches.iter() {
-            selected.insert(patch.clone());
+        for i in 0..self.n_faces() {
+            let aabb = self.face_aabb(i);
+            octree.insert(Aabb::new(aabb.center(), aabb.halfsize() + tol));
         }
 
-        for (i, patch) in self.patches.iter().enumerate() {
-            if selected.contains(patch.name()) {
-                index[i] = true;
+        let mut pairs = vec![];
+
+        for i in 0..self.n_faces() {
+            let normal = self.face_normal(i);
+            let origin = self.vertices[self.half_edges[self.faces[i].half_edge].origin].point;
+            let plane = Plane::new(normal, -Vector3::dot(&normal, &origin));
+            let triangles = self.face_triangles(i);
+            let mut found = octree.search(octree.item(i));
+            found.sort();
+
+            for j in found.into_iter().filter(|&j| j > i) {
+                let is_parallel = Vector3::cross(&normal, &self.face_normal(j)).mag() <= tol;
+                let is_coplanar = self
+                    .face_vertices(j)
+                    .iter()
+                    .all(|&k| plane.distance(&self.vertices[k].point).abs() <= tol);
+
+                if !is_parallel || !is_coplanar {
+                    continue;
+                }
+
+                let overlaps = self.face_triangles(j).iter().any(|u| {
+                    triangles
+                        .iter()
+                        .any(|t| overlaps_coplanar_triangles(t, u, &normal))
+                });
+
+                if overlaps {
+                    pairs.push((i, j));
+                }
             }
         }
 
-        for (i, face) in self.faces.iter().enumerate() {
-            if let Some(patch) = face.patch {
-                if index[patch] {
-                    faces.push(i);
+        pairs
+    }
+
+    /// Compute if the mesh faces are consistently oriented
+    pub fn is_consistent(&self) -> bool {
+        for half_edge in self.half_edges.iter() {
+            if let Some(twin) = half_edge.twin {
+                if self.half_edges[twin].origin == half_edge.origin {
+                    return false;
                 }
             }
         }
 
-        self.extract_faces(&faces)
+        true
     }
 
-    /// Orient the mesh such that the faces in each component have the same
-    /// directed normal relative to each other. This does not ensure that the
-    /// components' orientation are consistent.
-    pub fn orient(&mut self) -> usize {
-        let mut oriented = vec![false; self.n_faces()];
-        let mut count = 0;
-
-        for component in self.components() {
-            let next = component[0];
-            let mut queue = VecDeque::from([next]);
+    /// Compute if the neighboring pair of mesh faces are consistently
+    /// oriented. If the faces do not share an edge, return false.
+    pub fn is_consistent_faces(&self, i: usize, j: usize) -> bool {
+        let mut index = HashSet::new();
 
-            while let Some(current) = queue.pop_front() {
-                if !oriented[current] {
-                    oriented[current] = true;
+        for k in self.face_half_edges(i) {
+            index.insert(k);
+        }
 
-                    for neighbor in self.face_neighbors(current) {
-                        if !oriented[neighbor] {
-                            queue.push_back(neighbor);
+        for k in self.face_half_edges(j) {
+            let half_edge = &self.half_edges[k];
 
-                            if !self.is_consistent_faces(current, neighbor) {
-                                self.flip_face(neighbor);
-                                count += 1;
-                            }
-                        }
-                    }
+            if let Some(twin) = half_edge.twin {
+                if index.contains(&twin) {
+                    let twin = &self.half_edges[twin];
+                    return half_edge.origin != twin.origin;
                 }
             }
         }
 
-        count
+        false
     }
 
-    /// Compute the faces for each contiguous component in the mesh.
-    pub fn components(&self) -> Vec<Vec<usize>> {
-        let mut components = vec![];
-        let mut visited = vec![false; self.n_faces()];
-
-        for next in 0..visited.len() {
-            if !visited[next] {
-                let mut queue = VecDeque::from([next]);
-                let mut component = vec![];
+    /// Compute the neighboring vertices for a vertex by index. This is only
+    /// valid for closed oriented meshes.
+    pub fn vertex_neighbors(&self, index: usize) -> Vec<usize> {
+        let vertex = self.vertices[index];
+        let mut current = vertex.half_edge;
+        let mut neighbors = vec![];
 
-                while let Some(current) = queue.pop_front() {
-                    if !visited[current] {
-                        visited[current] = true;
-                        component.push(current);
+        loop {
+            let half_edge = self.half_edges[current];
+            let prev = self.half_edges[half_edge.prev];
+            neighbors.push(prev.origin);
 
-                        for neighbor in self.face_neighbors(current) {
-                            if !visited[neighbor] {
-                                queue.push_back(neighbor);
-                            }
-                        }
-                    }
-                }
+            current = prev.twin.expect("mesh must be closed");
 
-                components.push(component);
+            if current == vertex.half_edge {
+                break;
             }
         }
 
-        components
+        neighbors
     }
 
-    /// Split the mesh by feature angle (in radians).
-    pub fn split_by_features(&self, angle: f64) -> Vec<Vec<usize>> {
-        let mut components = vec![];
-        let mut visited = vec![false; self.n_faces()];
-        let normals = self.face_normals();
+    /// Compute the faces containing a vertex by index. This is only valid
+    /// for closed oriented meshes.
+    pub fn vertex_faces(&self, index: usize) -> Vec<usize> {
+        let vertex = self.vertices[index];
+        let mut current = vertex.half_edge;
+        let mut faces = vec![];
 
-        for next in 0..visited.len() {
-            if !visited[next] {
-                let mut queue = VecDeque::from([next]);
-                let mut component = vec![];
+        loop {
+            let half_edge = self.half_edges[current];
+            faces.push(half_edge.face);
 
-                while let Some(current) = queue.pop_front() {
-                    if !visited[current] {
-                        visited[current] = true;
-                        component.push(current);
+            let prev = self.half_edges[half_edge.prev];
+            current = prev.twin.expect("mesh must be closed");
 
-                        for neighbor in self.face_neighbors(current) {
-                            let u = &normals[current];
-                            let v = &normals[neighbor];
+            if current == vertex.half_edge {
+                break;
+            }
+        }
 
-                            if !visited[neighbor] && Vector3::dot(&u, &v).acos() < angle {
-                                queue.push_back(neighbor);
-                            }
-                        }
-                    }
-                }
+        faces
+    }
 
-                components.push(component);
+    /// Compute the outgoing half edges of a vertex by index. Unlike
+    /// vertex_faces, the circulation stops at boundary half edges and
+    /// continues in the opposite direction, so this is valid for open meshes.
+    fn vertex_half_edges(&self, index: usize) -> Vec<usize> {
+        let start = self.vertices[index].half_edge;
+        let mut half_edges = vec![start];
+        let mut current = self.half_edges[self.half_edges[start].prev].twin;
+
+        while let Some(i) = current {
+            if i == start {
+                return half_edges;
             }
+
+            half_edges.push(i);
+            current = self.half_edges[self.half_edges[i].prev].twin;
         }
 
-        components
+        // A boundary was reached so circulate the other way from the start.
+        let mut current = self.half_edges[start].twin;
+
+        while let Some(i) = current {
+            let next = self.half_edges[i].next;
+            half_edges.push(next);
+            current = self.half_edges[next].twin;
+        }
+
+        half_edges
     }
 
-    /// Flip the orientation of a face. This reverses the direction of all
-    /// half edges for the face.
-    pub fn flip_face(&mut self, index: usize) {
-        self.face_half_edges(index)
+    /// Compute the neighboring vertices of a vertex by index. Unlike
+    /// vertex_neighbors, this is valid for open meshes.
+    fn vertex_one_ring(&self, index: usize) -> Vec<usize> {
+        let mut neighbors = vec![];
+
+        for i in self.vertex_half_edges(index) {
+            let half_edge = self.half_edges[i];
+            let head = self.half_edges[half_edge.next].origin;
+            let tail = self.half_edges[half_edge.prev].origin;
+
+            for j in [head, tail] {
+                if !neighbors.contains(&j) {
+                    neighbors.push(j);
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    /// Compute if a vertex by index is on the boundary
+    fn is_boundary_vertex(&self, index: usize) -> bool {
+        self.vertex_half_edges(index).iter().any(|&i| {
+            let half_edge = self.half_edges[i];
+            half_edge.is_boundary() || self.half_edges[half_edge.prev].is_boundary()
+        })
+    }
+
+    /// Compute the unit normal of a vertex by index as the average of the
+    /// incident face normals weighted by the face angles at the vertex.
+    pub fn vertex_normal(&self, index: usize) -> Vector3 {
+        self.vertex_normal_weighted(index, NormalWeight::Angle)
+    }
+
+    /// Compute the unit normal of a vertex by index as the weighted average
+    /// of the incident face normals.
+    pub fn vertex_normal_weighted(&self, index: usize, weighting: NormalWeight) -> Vector3 {
+        let point = self.vertices[index].point;
+
+        self.vertex_half_edges(index)
             .iter()
-            .for_each(|&i| self.flip_half_edge(i));
+            .map(|&i| {
+                let half_edge = self.half_edges[i];
+                let weight = match weighting {
+                    NormalWeight::Equal => 1.,
+                    NormalWeight::Area => self.face_area(half_edge.face),
+                    NormalWeight::Angle => {
+                        let p = self.vertices[self.half_edges[half_edge.prev].origin].point;
+                        let q = self.vertices[self.half_edges[half_edge.next].origin].point;
+                        Vector3::angle(&(q - point), &(p - point))
+                    }
+                };
+
+                self.face_normal(half_edge.face) * weight
+            })
+            .fold(Vector3::zeros(), |acc, v| acc + v)
+            .unit()
     }
 
-    /// Flip the orientation of a half edge.
-    pub fn flip_half_edge(&mut self, index: usize) {
-        let half_edge = self.half_edges[index];
-        let prev = half_edge.next;
-        let origin = self.half_edges[prev].origin;
+    /// Compute the unit normals for all vertices weighted by the face angles.
+    pub fn vertex_normals(&self) -> Vec<Vector3> {
+        self.vertex_normals_weighted(NormalWeight::Angle)
+    }
 
-        self.half_edges[index].next = half_edge.prev;
-        self.half_edges[index].prev = prev;
-        self.half_edges[index].origin = origin;
+    /// Compute the unit normals for all vertices as the weighted average of
+    /// the incident face normals.
+    pub fn vertex_normals_weighted(&self, weighting: NormalWeight) -> Vec<Vector3> {
+        (0..self.n_vertices())
+            .map(|i| self.vertex_normal_weighted(i, weighting))
+            .collect()
     }
 
-    /// Calculate the Gaussian curvature at a vertex. This assumes the mesh
-    /// is composed of strictly trianglar faces and is oriented.
-    pub fn curvature(&self, index: usize) -> f64 {
-        let vertex = &self.vertices[index];
-        let mut current = vertex.half_edge;
-        let mut angle = 2. * std::f64::consts::PI;
-        let mut area = 0.;
+    /// Compute the unit normals for all vertices smoothed by averaging each
+    /// normal with those of its one-ring for a number of iterations. The
+    /// vertex positions are not modified.
+    pub fn smoothed_vertex_normals(&self, iterations: usize) -> Vec<Vector3> {
+        let rings = (0..self.n_vertices())
+            .map(|i| self.vertex_one_ring(i))
+            .collect::<Vec<Vec<usize>>>();
+
+        let mut normals = self.vertex_normals();
+
+        for _ in 0..iterations {
+            normals = rings
+                .iter()
+                .enumerate()
+                .map(|(i, ring)| {
+                    let sum = ring.iter().fold(normals[i], |sum, &j| sum + normals[j]);
+                    sum.unit()
+                })
+                .collect();
+        }
 
-        loop {
-            let half_edge = &self.half_edges[current];
-            let next = &self.half_edges[half_edge.next];
-            let prev = &self.half_edges[half_edge.prev];
+        normals
+    }
 
-            let p = self.vertices[prev.origin].point;
-            let q = vertex.point;
-            let r = self.vertices[next.origin].point;
+    /// Compute the vertices defining a face by index
+    pub fn face_vertices(&self, index: usize) -> Vec<usize> {
+        self.face_half_edges(index)
+            .iter()
+            .map(|&i| self.half_edges[i].origin)
+            .collect()
+    }
 
-            let u = p - q;
-            let v = r - q;
-            let theta = Vector3::angle(&u, &v);
+    /// Compute the neighboring faces for a face by index
+    pub fn face_neighbors(&self, index: usize) -> Vec<usize> {
+        self.face_half_edges(index)
+            .iter()
+            .map(|&i| self.half_edges[i])
+            .filter(|h| !h.is_boundary())
+            .map(|h| self.half_edges[h.twin.unwrap()].face)
+            .collect()
+    }
 
-            angle -= theta;
-            area += Vector3::cross(&u, &v).mag() * 0.5;
+    /// Compute the ordered half edges defining the boundary of a face by index
+    pub fn face_half_edges(&self, index: usize) -> Vec<usize> {
+        let face = self.faces[index];
+        let mut current = face.half_edge;
+        let mut half_edges = vec![];
 
-            let twin = half_edge.twin.expect("mesh must be closed");
-            current = self.half_edges[twin].next;
+        loop {
+            half_edges.push(current);
+            current = self.half_edges[current].next;
 
-            if current == vertex.half_edge {
+            if current == face.half_edge {
                 break;
             }
         }
 
-        3. * angle / area
+        half_edges
     }
-}
 
-#[derive(Debug, Copy, Clone, Default)]
-pub struct HeVertex {
-    point: Vector3,
-    half_edge: usize,
-}
+    /// Compute the unit normal vector of a face.
+    pub fn face_normal(&self, index: usize) -> Vector3 {
+        let mut normal = Vector3::zeros();
+        let index = self.face_vertices(index);
+        let n = index.len();
 
-impl HeVertex {
-    /// Get the point
-    pub fn point(&self) -> Vector3 {
-        self.point
-    }
+        for i in 0..n {
+            let p = self.vertices[index[i]].point;
+            let q = self.vertices[index[(i + 1) % n]].point;
+            normal += Vector3::cross(&p, &q);
+        }
 
-    /// Get the half edge handle
-    pub fn half_edge(&self) -> usize {
-        self.half_edge
+        normal.unit()
     }
-}
 
-impl From<&Vertex> for HeVertex {
-    fn from(vertex: &Vertex) -> HeVertex {
-        HeVertex {
-            point: (*vertex).into(),
-            half_edge: 0,
-        }
+    /// Compute the unit normals for all faces.
+    pub fn face_normals(&self) -> Vec<Vector3> {
+        (0..self.n_faces()).map(|i| self.face_normal(i)).collect()
     }
-}
-
-#[derive(Debug, Copy, Clone, Default)]
-pub struct HeFace {
-    half_edge: usize,
-    patch: Option<usize>,
-}
 
-impl HeFace {
-    /// Construct a HeFace from its half edge and patch
-    pub fn new(half_edge: usize, patch: Option<usize>) -> HeFace {
-        HeFace { half_edge, patch }
+    /// Compute the area of a face by index. Polygonal faces are triangulated
+    /// as a fan from their first vertex.
+    pub fn face_area(&self, index: usize) -> f64 {
+        self.face_triangles(index).iter().map(|t| t.area()).sum()
     }
 
-    /// Get the half edge handle
-    pub fn half_edge(&self) -> usize {
-        self.half_edge
+    /// Compute the total surface area of the faces.
+    pub fn area(&self) -> f64 {
+        (0..self.n_faces()).map(|i| self.face_area(i)).sum()
     }
 
-    /// Get the patch handle
-    pub fn patch(&self) -> Option<usize> {
-        self.patch
-    }
-}
+    /// Compute the least-squares best fit plane to the vertices of a subset of
+    /// faces. This returns the plane (with a unit normal) and the root mean
+    /// square deviation of the vertices from the plane.
+    pub fn fit_plane(&self, faces: &[usize]) -> (Plane, f64) {
+        let vertices = faces
+            .iter()
+            .flat_map(|&i| self.face_vertices(i))
+            .collect::<BTreeSet<usize>>();
 
-#[derive(Debug, Copy, Clone, Default)]
-pub struct HeHalfEdge {
-    origin: usize,
-    face: usize,
-    prev: usize,
-    next: usize,
-    twin: Option<usize>,
-}
+        if vertices.is_empty() {
+            panic!("no faces to fit");
+        }
 
-impl HeHalfEdge {
-    /// Construct a HeHalfEdge from its components
-    pub fn new(
-        origin: usize,
-        face: usize,
-        prev: usize,
-        next: usize,
-        twin: Option<usize>,
-    ) -> HeHalfEdge {
-        HeHalfEdge {
-            origin,
-            face,
-            prev,
-            next,
-            twin,
+        let n = vertices.len() as f64;
+        let points = vertices
+            .iter()
+            .map(|&i| self.vertices[i].point)
+            .collect::<Vec<Vector3>>();
+
+        let center = points.iter().fold(Vector3::zeros(), |acc, &p| acc + p) / n;
+        let mut covariance = Matrix3::zeros();
+
+        for &point in points.iter() {
+            let u = point - center;
+            covariance += Matrix3::outer(&u, &u);
         }
-    }
 
-    /// Get the origin handle
-    pub fn origin(&self) -> usize {
-        self.origin
-    }
+        let (_, vectors) = covariance.symmetric_eigen();
+        let normal = vectors[0];
+        let plane = Plane::new(normal, -Vector3::dot(&normal, &center));
 
-    /// Get the face handle
-    pub fn face(&self) -> usize {
-        self.face
+        let error = points
+            .iter()
+            .map(|point| plane.distance(point).powi(2))
+            .sum::<f64>();
+
+        (plane, (error / n).sqrt())
     }
 
-    /// Get the previous half edge handle
-    pub fn prev(&self) -> usize {
-        self.prev
+    /// Compute the triangles of a face by index. Polygonal faces are
+    /// triangulated as a fan about the first vertex.
+    fn face_triangles(&self, index: usize) -> Vec<Triangle> {
+        let points = self
+            .face_vertices(index)
+            .iter()
+            .map(|&i| self.vertices[i].point)
+            .collect::<Vec<Vector3>>();
+
+        (1..points.len() - 1)
+            .map(|i| Triangle::new(points[0], points[i], points[i + 1]))
+            .collect()
     }
 
-    /// Get the next half edge handle
-    pub fn next(&self) -> usize {
-        self.next
+    /// Iterate over the triangles of every face (fan triangulated from the
+    /// first vertex of the face) along with the index of the owning face.
+    pub fn triangles(&self) -> impl Iterator<Item = (usize, Triangle)> + '_ {
+        (0..self.n_faces()).flat_map(move |i| {
+            let start = self.faces[i].half_edge;
+            let p = self.vertices[self.half_edges[start].origin].point;
+
+            std::iter::successors(Some(self.half_edges[start].next), move |&j| {
+                Some(self.half_edges[j].next)
+            })
+            .take_while(move |&j| self.half_edges[j].next != start)
+            .map(move |j| {
+                let q = self.vertices[self.half_edges[j].origin].point;
+                let r = self.vertices[self.half_edges[self.half_edges[j].next].origin].point;
+                (i, Triangle::new(p, q, r))
+            })
+        })
     }
 
-    /// Get the twin half edge handle
-    pub fn twin(&self) -> Option<usize> {
-        self.twin
+    /// Compute the first intersection of a ray with the faces. This returns
+    /// the face index, the barycentric coordinates of the hit within the hit
+    /// triangle of the face (fan triangulated from its first vertex), and the
+    /// distance along the ray (in units of the ray direction).
+    pub fn raycast_bary(&self, ray: &Ray) -> Option<(usize, Vector3, f64)> {
+        let octree = self.face_octree();
+
+        let first_hit = |i: usize| {
+            self.face_triangles(i)
+                .iter()
+                .filter_map(|triangle| collision::hit_ray_triangle(ray, triangle))
+                .min_by(|a, b| a.t().total_cmp(&b.t()))
+        };
+
+        let (face, _) = octree.raycast_with(ray, |i| first_hit(i).map(|hit| hit.t()))?;
+        let hit = first_hit(face)?;
+
+        Some((face, hit.barycentric(), hit.t()))
     }
 
-    /// Get if the the half edge is a boundary (no twin)
-    pub fn is_boundary(&self) -> bool {
-        self.twin.is_none()
+    /// Compute every intersection of a ray with the faces. This returns the
+    /// face index and distance along the ray of each hit sorted by distance.
+    pub fn raycast_all(&self, ray: &Ray) -> Vec<(usize, f64)> {
+        self.raycast_all_with(&self.face_octree(), ray)
     }
-}
 
-#[derive(Debug, Clone, Default)]
-pub struct HePatch {
-    name: String,
-}
+    /// Compute every intersection of a ray with the faces using an octree of
+    /// the face bounding boxes.
+    fn raycast_all_with(&self, octree: &Octree<Aabb>, ray: &Ray) -> Vec<(usize, f64)> {
+        let aabb = octree.aabb();
 
-impl HePatch {
-    /// Get a borrowed reference to the name
-    pub fn name(&self) -> &str {
-        &self.name
-    }
-}
+        // Clip the ray to a segment which extends beyond the bounding box
+        let length = (ray.origin() - aabb.center()).mag() + aabb.halfsize().mag() * 2.;
+        let end = ray.origin() + ray.direction().unit() * length;
+        let segment = Line::new(ray.origin(), end);
 
-impl From<&Patch> for HePatch {
-    fn from(patch: &Patch) -> HePatch {
-        HePatch {
-            name: patch.name().to_string(),
+        let mut hits = vec![];
+
+        for i in octree.search(&segment) {
+            for triangle in self.face_triangles(i) {
+                if let Some(t) = collision::intersection_ray_triangle(ray, &triangle) {
+                    hits.push((i, t));
+                }
+            }
         }
+
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        hits
     }
-}
+
+    /// Compute the ambient occlusion of each vertex as the fraction of rays,
+    /// cast in random directions over the hemisphere about the vertex normal,
+    /// which hit a face within the maximum distance. The random directions
+    /// are seeded for reproducible results.
+    pub fn ambient_occlusion(&self, samples: usize, max_dist: f64, seed: u64) -> Vec<f64> {
+        let octree = self.face_octree();
+        let mut normals = vec![Vector3::zeros(); self.n_vertices()];
+
+        for (i, normal) in self.face_normals().iter().enumerate() {
+            for j in self.face_vertices(i) {
+                normals[j] += *normal;
+            }
+        }
+
+        normals
+            .par_iter()
+            .enumerate()
+            .map(|(i, normal)| {
+                let normal = normal.unit();
+                let origin = self.vertices[i].point + normal * (EPSILON * 100.);
+                let mut rng = StdRng::seed_from_u64(seed.wrapping_add(i as u64));
+                let mut blocked = 0;
+
+                for _ in 0..samples {
+                    // Sample a uniform direction on the unit sphere and
+                    // reflect it into the hemisphere about the normal.
+                    let z: f64 = rng.gen_range(-1.0..1.0);
+                    let theta: f64 = rng.gen_range(0.0..std::f64::consts::TAU);
+                    let r = (1. - z * z).sqrt();
+                    let mut direction = Vector3::new(r * theta.cos(), r * theta.sin(), z);
+
+                    if Vector3::dot(&direction, &normal) < 0. {
+                        direction *= -1.;
+                    }
+
+                    let ray = Ray::new(origin, direction);
+                    let hits = self.raycast_all_with(&octree, &ray);
+
+                    if hits.first().is_some_and(|&(_, t)| t <= max_dist) {
+                        blocked += 1;
+                    }
+                }
+
+                if samples == 0 {
+                    0.
+                } else {
+                    blocked as f64 / samples as f64
+                }
+            })
+            .collect()
+    }
+
+    /// Compute the axis-aligned bounding box of a face by index
+    fn face_aabb(&self, index: usize) -> Aabb {
+        let mut min = Vector3::ones() * f64::INFINITY;
+        let mut max = Vector3::ones() * f64::NEG_INFINITY;
+
+        for j in self.face_vertices(index) {
+            let point = self.vertices[j].point;
+
+            for k in 0..3 {
+                min[k] = min[k].min(point[k]);
+                max[k] = max[k].max(point[k]);
+            }
+        }
+
+        Aabb::from_bounds(min, max)
+    }
+
+    /// Construct an octree of the face bounding boxes. The item index is the
+    /// face index.
+    pub fn face_octree(&self) -> Octree<Aabb> {
+        let mut octree = Octree::<Aabb>::new(self.aabb());
+
+        for i in 0..self.n_faces() {
+            octree.insert(self.face_aabb(i));
+        }
+
+        octree
+    }
+
+    /// Compute if a point is inside a closed mesh by the parity of the ray
+    /// crossings. Faces are triangulated and only those in the octree cells
+    /// along the ray are tested.
+    pub fn contains(&self, point: Vector3) -> bool {
+        self.is_inside_with(&self.face_octree(), point)
+    }
+
+    /// Compute if a point is inside a closed mesh by the parity of the ray
+    /// crossings using an octree of the faces. If the ray grazes an edge or
+    /// vertex (two crossings at the same distance) the direction is jittered
+    /// and the ray cast again.
+    fn is_inside_with(&self, octree: &Octree<Aabb>, point: Vector3) -> bool {
+        if !octree.aabb().intersects(&point) {
+            return false;
+        }
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut direction = Vector3::new(0.5773, 0.5774, 0.5775);
+
+        for _ in 0..16 {
+            let ray = Ray::new(point, direction);
+            let hits = self.raycast_all_with(octree, &ray);
+            let grazes = hits.windows(2).any(|w| (w[1].1 - w[0].1).abs() < EPSILON);
+
+            if !grazes {
+                return hits.len() % 2 == 1;
+            }
+
+            let jitter = Vector3::new(rng.gen(), rng.gen(), rng.gen()) - 0.5;
+            direction = (direction + jitter * 0.1).unit();
+        }
+
+        false
+    }
+
+    /// Compute the number of times a ray crosses the surface. Hits at the
+    /// same distance (within tolerance), such as through an edge shared by
+    /// two faces or the diagonal of a fan triangulated face, are grouped. A
+    /// group counts as one crossing if the normals of its faces all point
+    /// the same way relative to the ray; otherwise the ray only touches the
+    /// surface and the group is not counted.
+    pub fn ray_crossings(&self, ray: &Ray) -> usize {
+        let hits = self.raycast_all(ray);
+        let direction = ray.direction().unit();
+        let mut crossings = 0;
+        let mut start = 0;
+
+        while start < hits.len() {
+            let mut end = start + 1;
+
+            while end < hits.len() && hits[end].1 - hits[end - 1].1 < EPSILON {
+                end += 1;
+            }
+
+            let signs = hits[start..end]
+                .iter()
+                .map(|&(i, _)| Vector3::dot(&self.face_normal(i), &direction))
+                .filter(|dot| dot.abs() > EPSILON)
+                .map(|dot| dot > 0.)
+                .collect::<Vec<bool>>();
+
+            if !signs.is_empty() && signs.iter().all(|&sign| sign == signs[0]) {
+                crossings += 1;
+            }
+
+            start = end;
+        }
+
+        crossings
+    }
+
+    /// Compute the local wall thickness at a face as the distance from its
+    /// centroid, along the inward normal, to the next face hit. This returns
+    /// None if the ray exits without hitting another face.
+    pub fn thickness_at(&self, face: usize) -> Option<f64> {
+        let vertices = self.face_vertices(face);
+        let centroid = vertices
+            .iter()
+            .fold(Vector3::zeros(), |acc, &j| acc + self.vertices[j].point)
+            / vertices.len() as f64;
+
+        let ray = Ray::new(centroid, -self.face_normal(face));
+
+        self.raycast_all(&ray)
+            .into_iter()
+            .find(|&(i, t)| i != face && t > EPSILON)
+            .map(|(_, t)| t)
+    }
+
+    /// Compute a boolean (constructive solid geometry) operation with another
+    /// mesh. Both meshes must be closed, manifold and outward oriented.
+    ///
+    /// Each face is split by the planes of the faces of the other mesh that
+    /// cross it, and the resulting pieces are kept or discarded by whether
+    /// they lie inside the other mesh. The pieces are then welded and the
+    /// T-junctions along the splits are resolved. This is not exact: faces
+    /// which are coplanar with faces of the other mesh, or intersections
+    /// smaller than the geometric tolerance, may produce an open result.
+    pub fn boolean(&self, other: &HeMesh, op: BooleanOp) -> HeMesh {
+        if !self.is_closed() || !other.is_closed() {
+            panic!("mesh must be closed");
+        }
+
+        let octree = self.face_octree();
+        let other_octree = other.face_octree();
+        let mut polygons = vec![];
+
+        for piece in self.boolean_pieces(other, &other_octree) {
+            let inside = other.is_inside_with(&other_octree, piece.centroid());
+
+            if inside == (op == BooleanOp::Intersection) {
+                polygons.push(piece);
+            }
+        }
+
+        for piece in other.boolean_pieces(self, &octree) {
+            let inside = self.is_inside_with(&octree, piece.centroid());
+
+            match op {
+                BooleanOp::Union if !inside => polygons.push(piece),
+                BooleanOp::Intersection if inside => polygons.push(piece),
+                BooleanOp::Difference if inside => {
+                    let mut vertices = piece.vertices().clone();
+                    vertices.reverse();
+                    polygons.push(Polygon::new(vertices));
+                }
+                _ => (),
+            }
+        }
+
+        let mut mesh = HeMesh::from_polygons(&polygons);
+        mesh.merge_vertices();
+        mesh.resolve_t_junctions(EPSILON);
+        mesh
+    }
+
+    /// Split the faces into convex pieces by the planes of the faces of the
+    /// other mesh which cross them.
+    fn boolean_pieces(&self, other: &HeMesh, octree: &Octree<Aabb>) -> Vec<Polygon> {
+        let mut pieces = vec![];
+
+        for i in 0..self.n_faces() {
+            for triangle in self.face_triangles(i) {
+                let plane = Plane::from_points(triangle.p(), triangle.q(), triangle.r());
+                let mut parts = vec![Polygon::new(vec![triangle.p(), triangle.q(), triangle.r()])];
+
+                for j in octree.search(&triangle.aabb()) {
+                    for cutter in other.face_triangles(j) {
+                        let cut = Plane::from_points(cutter.p(), cutter.q(), cutter.r());
+
+                        if !Self::straddles(&plane, &cutter) || !Self::straddles(&cut, &triangle) {
+                            continue;
+                        }
+
+                        parts = parts
+                            .into_iter()
+                            .flat_map(|part| match part.split(&cut) {
+                                (Some(front), Some(back)) => vec![front, back],
+                                _ => vec![part],
+                            })
+                            .collect();
+                    }
+                }
+
+                pieces.extend(parts);
+            }
+        }
+
+        pieces
+    }
+
+    /// Check if the vertices of a triangle lie strictly on both sides of a
+    /// plane.
+    fn straddles(plane: &Plane, triangle: &Triangle) -> bool {
+        let scale = plane.normal().mag();
+        let distances =
+            [triangle.p(), triangle.q(), triangle.r()].map(|v| plane.distance(&v) / scale);
+
+        distances.iter().any(|&d| d > EPSILON) && distances.iter().any(|&d| d < -EPSILON)
+    }
+
+    /// Extract the faces visible from outside of the mesh. A face is kept if
+    /// any of the rays cast from its centroid along a set of evenly spread
+    /// directions escapes the mesh without hitting another face. This is a
+    /// heuristic: more samples reduce the chance of discarding a face which
+    /// is only visible through a narrow opening.
+    pub fn keep_visible_shell(&self, samples: usize) -> HeMesh {
+        let octree = self.face_octree();
+        let directions = fibonacci_sphere(samples.max(1));
+        let mut faces = vec![];
+
+        for i in 0..self.n_faces() {
+            let vertices = self.face_vertices(i);
+            let centroid = vertices
+                .iter()
+                .fold(Vector3::zeros(), |acc, &j| acc + self.vertices[j].point)
+                / vertices.len() as f64;
+
+            let is_visible = directions.iter().any(|&direction| {
+                let ray = Ray::new(centroid, direction);
+                self.raycast_all_with(&octree, &ray)
+                    .iter()
+                    .all(|&(j, _)| j == i)
+            });
+
+            if is_visible {
+                faces.push(i);
+            }
+        }
+
+        self.extract_faces(&faces)
+    }
+
+    /// Compute the plane quadric of each vertex accumulated from its incident
+    /// face planes. Each symmetric 4x4 quadric is stored as its 10 unique
+    /// entries in row-major order of the upper triangle.
+    pub fn vertex_quadrics(&self) -> Vec<[f64; 10]> {
+        let mut quadrics = vec![[0.; 10]; self.n_vertices()];
+
+        for i in 0..self.n_faces() {
+            let normal = self.face_normal(i);
+            let origin = self.vertices[self.half_edges[self.faces[i].half_edge].origin].point;
+            let quadric = HeMesh::plane_quadric(normal, origin, 1.);
+
+            for j in self.face_vertices(i) {
+                for (value, q) in quadrics[j].iter_mut().zip(quadric.iter()) {
+                    *value += q;
+                }
+            }
+        }
+
+        quadrics
+    }
+
+    /// Compute the weighted quadric of the plane through a point with a unit
+    /// normal, stored as the 10 unique entries of the upper triangle.
+    fn plane_quadric(normal: Vector3, point: Vector3, weight: f64) -> [f64; 10] {
+        let plane = [
+            normal.x(),
+            normal.y(),
+            normal.z(),
+            -Vector3::dot(&normal, &point),
+        ];
+
+        let mut quadric = [0.; 10];
+        let mut k = 0;
+
+        for a in 0..4 {
+            for b in a..4 {
+                quadric[k] = plane[a] * plane[b] * weight;
+                k += 1;
+            }
+        }
+
+        quadric
+    }
+
+    /// Compute the error of a point with respect to a quadric
+    fn quadric_error(quadric: &[f64; 10], point: Vector3) -> f64 {
+        let [x, y, z] = [point.x(), point.y(), point.z()];
+        let q = quadric;
+
+        q[0] * x * x
+            + q[4] * y * y
+            + q[7] * z * z
+            + q[9]
+            + 2. * (q[1] * x * y + q[2] * x * z + q[5] * y * z)
+            + 2. * (q[3] * x + q[6] * y + q[8] * z)
+    }
+
+    /// Compute the point minimizing a quadric nearest to a fallback point.
+    /// Directions in which the quadric is (nearly) singular are left at the
+    /// fallback point.
+    fn quadric_point(quadric: &[f64; 10], fallback: Vector3) -> Vector3 {
+        let q = quadric;
+        let a = Matrix3::new([[q[0], q[1], q[2]], [q[1], q[4], q[5]], [q[2], q[5], q[7]]]);
+        let b = Vector3::new(q[3], q[6], q[8]);
+        let (values, vectors) = a.symmetric_eigen();
+        let residual = (a * fallback + b) * -1.;
+        let mut point = fallback;
+
+        for (k, vector) in vectors.iter().enumerate() {
+            if values[k] > values[2].abs() * 1e-6 && values[k] > EPSILON {
+                point += *vector * (Vector3::dot(vector, &residual) / values[k]);
+            }
+        }
+
+        point
+    }
+
+    /// Decimate a triangular mesh to a target number of faces by quadric
+    /// error edge collapses (Garland-Heckbert). Boundary and feature edges
+    /// are penalized to preserve the silhouette. Decimation stops early if no
+    /// valid collapses remain.
+    pub fn decimate(&mut self, target_faces: usize) {
+        self.decimate_with(target_faces, false);
+    }
+
+    /// Decimate a triangular mesh to a target number of faces by quadric
+    /// error edge collapses. If the boundary is preserved, collapses
+    /// involving boundary vertices are not allowed.
+    pub fn decimate_with(&mut self, target_faces: usize, preserve_boundary: bool) {
+        self.invalidate();
+
+        let angle = std::f64::consts::FRAC_PI_3;
+        let penalty = 1e3;
+        let mut quadrics = self.vertex_quadrics();
+
+        for i in 0..self.n_faces() {
+            if self.face_half_edges(i).len() != 3 {
+                panic!("mesh must be triangular");
+            }
+        }
+
+        // Constrain the boundary and feature edges by the planes through each
+        // edge perpendicular to its faces.
+        for half_edge in self.half_edges.iter() {
+            let is_feature = half_edge.twin.is_none_or(|j| {
+                let u = self.face_normal(half_edge.face);
+                let v = self.face_normal(self.half_edges[j].face);
+                Vector3::angle(&u, &v) >= angle
+            });
+
+            if is_feature {
+                let j = half_edge.origin;
+                let k = self.half_edges[half_edge.next].origin;
+                let p = self.vertices[j].point;
+                let edge = self.vertices[k].point - p;
+                let normal = Vector3::cross(&edge, &self.face_normal(half_edge.face)).unit();
+                let quadric = HeMesh::plane_quadric(normal, p, penalty);
+
+                for v in [j, k] {
+                    for (value, q) in quadrics[v].iter_mut().zip(quadric.iter()) {
+                        *value += q;
+                    }
+                }
+            }
+        }
+
+        let mut stamps = vec![0; self.n_vertices()];
+        let mut removed_vertices = vec![];
+        let mut removed_faces = vec![];
+        let mut removed_half_edges = vec![false; self.n_half_edges()];
+        let mut heap = BinaryHeap::new();
+        let mut n_faces = self.n_faces();
+
+        let candidate = |mesh: &HeMesh, quadrics: &[[f64; 10]], stamps: &[usize], i: usize| {
+            let j = mesh.half_edges[i].origin;
+            let k = mesh.half_edges[mesh.half_edges[i].next].origin;
+            let quadric = std::array::from_fn(|n| quadrics[j][n] + quadrics[k][n]);
+            let midpoint = (mesh.vertices[j].point + mesh.vertices[k].point) * 0.5;
+            let point = HeMesh::quadric_point(&quadric, midpoint);
+
+            Collapse {
+                error: HeMesh::quadric_error(&quadric, point).max(0.),
+                half_edge: i,
+                vertices: [j, k],
+                stamps: [stamps[j], stamps[k]],
+                point,
+            }
+        };
+
+        for (i, half_edge) in self.half_edges.iter().enumerate() {
+            if half_edge.twin.is_none_or(|j| i < j) {
+                heap.push(candidate(self, &quadrics, &stamps, i));
+            }
+        }
+
+        while n_faces > target_faces {
+            let Some(collapse) = heap.pop() else {
+                break;
+            };
+
+            let [j, k] = collapse.vertices;
+            let i = collapse.half_edge;
+
+            if removed_half_edges[i]
+                || collapse.stamps != [stamps[j], stamps[k]]
+                || self.half_edges[i].origin != j
+                || self.half_edges[self.half_edges[i].next].origin != k
+            {
+                continue;
+            }
+
+            if preserve_boundary && (self.is_boundary_vertex(j) || self.is_boundary_vertex(k)) {
+                continue;
+            }
+
+            let Ok((faces, half_edges)) = self.collapse_edge_to(i, collapse.point) else {
+                continue;
+            };
+
+            n_faces -= faces.len();
+            removed_vertices.push(k);
+            removed_faces.extend(faces);
+
+            for h in half_edges {
+                removed_half_edges[h] = true;
+            }
+
+            quadrics[j] = std::array::from_fn(|n| quadrics[j][n] + quadrics[k][n]);
+            stamps[j] += 1;
+
+            for h in self.vertex_half_edges(j) {
+                heap.push(candidate(self, &quadrics, &stamps, h));
+
+                let prev = self.half_edges[h].prev;
+                heap.push(candidate(self, &quadrics, &stamps, prev));
+            }
+        }
+
+        let removed_half_edges = removed_half_edges
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &removed)| removed.then_some(i))
+            .collect::<Vec<usize>>();
+
+        self.remove_elements(&removed_vertices, &removed_faces, &removed_half_edges);
+    }
+
+    /// Compute the feature edges using a threshold angle in radians. This will
+    /// return the pair of half edges defining the edge.
+    pub fn feature_edges(&self, angle: f64) -> Vec<(usize, usize)> {
+        let mut visited = vec![false; self.n_half_edges()];
+        let mut features = vec![];
+
+        for (i, half_edge) in self.half_edges.iter().enumerate() {
+            if !visited[i] {
+                visited[i] = true;
+
+                if let Some(j) = half_edge.twin {
+                    visited[j] = true;
+
+                    let twin = self.half_edges[j];
+                    let u = self.face_normal(half_edge.face);
+                    let v = self.face_normal(twin.face);
+
+                    if Vector3::angle(&u, &v) >= angle {
+                        features.push((i, j));
+                    }
+                }
+            }
+        }
+
+        features
+    }
+
+    /// Compute the nearest feature edge (using a threshold angle in radians)
+    /// to a point. This returns the pair of half edges defining the edge and
+    /// the closest point on the edge.
+    pub fn nearest_feature_edge(
+        &self,
+        point: &Vector3,
+        angle: f64,
+    ) -> Option<(usize, usize, Vector3)> {
+        let features = self.feature_edges(angle);
+
+        if features.is_empty() {
+            return None;
+        }
+
+        let aabb = self.aabb();
+        let mut octree = Octree::<Line>::new(aabb);
+
+        for &(i, _) in features.iter() {
+            let p = self.vertices[self.half_edges[i].origin].point;
+            let q = self.vertices[self.half_edges[self.half_edges[i].next].origin].point;
+            octree.insert(Line::new(p, q));
+        }
+
+        // Search a growing box about the point until the nearest candidate is
+        // within the box (no closer edge can lie outside of it) or the box
+        // covers the full octree.
+        let offset = (*point - aabb.center()).abs() + aabb.halfsize();
+        let reach = offset[offset.argmax()];
+        let mut halfsize = (aabb.halfsize().mag() * 1e-3).max(EPSILON);
+
+        loop {
+            let query = Aabb::new(*point, Vector3::ones() * halfsize);
+            let mut nearest: Option<(usize, Vector3, f64)> = None;
+
+            for index in octree.search(&query) {
+                let closest = octree.item(index).closest_point(*point);
+                let distance = (closest - *point).mag();
+
+                if nearest.is_none_or(|(_, _, d)| distance < d) {
+                    nearest = Some((index, closest, distance));
+                }
+            }
+
+            if let Some((index, closest, distance)) = nearest {
+                if distance <= halfsize || halfsize >= reach {
+                    let (i, j) = features[index];
+                    return Some((i, j, closest));
+                }
+            }
+
+            if halfsize >= reach {
+                return None;
+            }
+
+            halfsize *= 2.;
+        }
+    }
+
+    /// Compute the closest point on the mesh surface to a query point. This
+    /// returns the closest point and the index of the face it lies on.
+    pub fn closest_point(&self, query: Vector3) -> (Vector3, usize) {
+        self.closest_point_with(&self.face_octree(), query)
+    }
+
+    /// Compute the closest point on the mesh surface to a query point using
+    /// an octree of the faces (see face_octree). Building the octree once
+    /// avoids rebuilding it for each of many queries.
+    pub fn closest_point_with(&self, octree: &Octree<Aabb>, query: Vector3) -> (Vector3, usize) {
+        if self.n_faces() == 0 {
+            panic!("mesh has no faces");
+        }
+
+        let aabb = octree.aabb();
+
+        // Search a growing box about the point until the nearest candidate is
+        // within the box (no closer face can lie outside of it) or the box
+        // covers the full octree.
+        let offset = (query - aabb.center()).abs() + aabb.halfsize();
+        let reach = offset[offset.argmax()];
+        let mut halfsize = (aabb.halfsize().mag() * 1e-3).max(EPSILON);
+
+        loop {
+            let search = Aabb::new(query, Vector3::ones() * halfsize);
+            let mut nearest: Option<(usize, Vector3, f64)> = None;
+
+            for index in octree.search(&search) {
+                for triangle in self.face_triangles(index) {
+                    let closest = triangle.closest_point(query);
+                    let distance = (closest - query).mag();
+
+                    if nearest.is_none_or(|(_, _, d)| distance < d) {
+                        nearest = Some((index, closest, distance));
+                    }
+                }
+            }
+
+            if let Some((index, closest, distance)) = nearest {
+                if distance <= halfsize || halfsize >= reach {
+                    return (closest, index);
+                }
+            }
+
+            halfsize *= 2.;
+        }
+    }
+
+    /// Merge the mesh into the current mesh naively. This strictly copies
+    /// the mesh and does not merge vertices, edges, or faces.
+    pub fn merge(&mut self, other: &HeMesh) {
+        self.invalidate();
+
+        let nv = self.n_vertices();
+        let nf = self.n_faces();
+        let nh = self.n_half_edges();
+        let np = self.n_patches();
+
+        for patch in other.patches() {
+            let patch = patch.clone();
+            self.patches.push(patch);
+        }
+
+        // Keep the vertex colors only if both meshes define them
+        self.colors = match (self.colors.take(), other.colors()) {
+            (Some(mut colors), Some(other)) => {
+                colors.extend(other.iter());
+                Some(colors)
+            }
+            _ => None,
+        };
+
+        for vertex in other.vertices().iter() {
+            let mut vertex = *vertex;
+            vertex.half_edge += nh;
+            self.vertices.push(vertex);
+        }
+
+        for face in other.faces().iter() {
+            let mut face = *face;
+            face.half_edge += nh;
+
+            if let Some(patch) = face.patch {
+                face.patch = Some(patch + np);
+            }
+
+            self.faces.push(face);
+        }
+
+        for half_edge in other.half_edges().iter() {
+            let mut half_edge = *half_edge;
+            half_edge.origin += nv;
+            half_edge.face += nf;
+            half_edge.prev += nh;
+            half_edge.next += nh;
+
+            if let Some(twin) = half_edge.twin {
+                half_edge.twin = Some(twin + nh);
+            }
+
+            self.half_edges.push(half_edge)
+        }
+    }
+
+    /// Merge vertices within the geometric tolerance. This may result in a
+    /// non-manifold mesh.
+    pub fn merge_vertices(&mut self) {
+        self.invalidate();
+
+        let lookup = self.coincident_vertices();
+        self.weld_vertices(&lookup);
+    }
+
+    /// Merge vertices within the geometric tolerance. Unlike merge_vertices,
+    /// this returns an error and leaves the mesh unchanged if the merge
+    /// would result in more than two faces sharing an edge.
+    pub fn try_merge_vertices(&mut self) -> Result<(), MeshError> {
+        let lookup = self.coincident_vertices();
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for half_edge in self.half_edges.iter() {
+            let j = lookup[half_edge.origin];
+            let k = lookup[self.half_edges[half_edge.next].origin];
+
+            let count = edges.entry((j.min(k), j.max(k))).or_insert(0);
+            *count += 1;
+
+            if *count > 2 {
+                return Err(MeshError::NonManifoldEdge(j.min(k), j.max(k)));
+            }
+        }
+
+        self.invalidate();
+        self.weld_vertices(&lookup);
+        Ok(())
+    }
+
+    /// Find the lowest index vertex within the geometric tolerance of each
+    /// vertex.
+    fn coincident_vertices(&self) -> Vec<usize> {
+        let aabb = self.aabb();
+        let mut octree = Octree::<Vector3>::new(aabb);
+        let mut queries = vec![];
+
+        for vertex in self.vertices.iter() {
+            octree.insert(vertex.point);
+
+            let query = Sphere::new(vertex.point, EPSILON);
+            queries.push(query);
+        }
+
+        octree
+            .search_many(&queries)
+            .iter()
+            .enumerate()
+            .map(|(i, items)| *items.iter().min().unwrap_or(&i))
+            .collect::<Vec<usize>>()
+    }
+
+    /// Merge vertices within a distance tolerance whose vertex normals (the
+    /// average of the incident face normals) agree within a threshold angle
+    /// in radians. Unlike merge_vertices, this keeps creased corners split.
+    pub fn merge_vertices_by_normal(&mut self, pos_tol: f64, angle_tol: f64) {
+        self.invalidate();
+
+        let aabb = self.aabb();
+        let mut octree = Octree::<Vector3>::new(aabb);
+        let mut queries = vec![];
+        let mut normals = vec![Vector3::zeros(); self.n_vertices()];
+
+        for vertex in self.vertices.iter() {
+            octree.insert(vertex.point);
+
+            let query = Sphere::new(vertex.point, pos_tol.max(EPSILON));
+            queries.push(query);
+        }
+
+        for (i, normal) in self.face_normals().iter().enumerate() {
+            for j in self.face_vertices(i) {
+                normals[j] += *normal;
+            }
+        }
+
+        let mut lookup: Vec<usize> = (0..self.n_vertices()).collect();
+
+        for (i, items) in octree.search_many(&queries).iter().enumerate() {
+            let mut items = items.clone();
+            items.sort();
+
+            for &j in items.iter().take_while(|&&j| j < i) {
+                if lookup[j] == j && Vector3::angle(&normals[i], &normals[j]) <= angle_tol {
+                    lookup[i] = j;
+                    break;
+                }
+            }
+        }
+
+        self.weld_vertices(&lookup);
+    }
+
+    /// Merge vertices which coincide within a per-axis tolerance. This is
+    /// suited to meshes whose spacing differs along each axis, such as thin
+    /// sheets where the spacing through the thickness is much smaller.
+    pub fn merge_vertices_anisotropic(&mut self, tol: Vector3) {
+        self.invalidate();
+
+        let aabb = self.aabb();
+        let mut octree = Octree::<Vector3>::new(aabb);
+        let mut queries = vec![];
+        let halfsize = Vector3::new(
+            tol[0].max(EPSILON),
+            tol[1].max(EPSILON),
+            tol[2].max(EPSILON),
+        );
+
+        for vertex in self.vertices.iter() {
+            octree.insert(vertex.point);
+
+            let query = Aabb::new(vertex.point, halfsize);
+            queries.push(query);
+        }
+
+        let mut lookup: Vec<usize> = (0..self.n_vertices()).collect();
+
+        for (i, items) in octree.search_many(&queries).iter().enumerate() {
+            let mut items = items.clone();
+            items.sort();
+
+            if let Some(&j) = items.iter().find(|&&j| j < i && lookup[j] == j) {
+                lookup[i] = j;
+            }
+        }
+
+        self.weld_vertices(&lookup);
+    }
+
+    /// Subdivide each triangle into four by inserting a vertex at the midpoint
+    /// of each edge. The original vertices are not moved, so the surface and
+    /// any boundaries are preserved exactly. Each new face keeps the patch of
+    /// its parent face. This requires a triangular mesh.
+    pub fn subdivide_midpoint(&mut self) {
+        self.invalidate();
+
+        let mut vertices = self
+            .vertices
+            .iter()
+            .map(|v| Vertex::from(v.point))
+            .collect::<Vec<Vertex>>();
+        let mut colors = self.colors.clone();
+        let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut faces = vec![];
+
+        for (i, face) in self.faces.iter().enumerate() {
+            let corners = self.face_vertices(i);
+
+            if corners.len() != 3 {
+                panic!("mesh must be triangular");
+            }
+
+            let mut midpoint = |p: usize, q: usize| {
+                *midpoints.entry((p.min(q), p.max(q))).or_insert_with(|| {
+                    let point = (self.vertices[p].point + self.vertices[q].point) * 0.5;
+                    vertices.push(Vertex::from(point));
+
+                    if let Some(colors) = colors.as_mut() {
+                        let color = std::array::from_fn(|k| (colors[p][k] + colors[q][k]) * 0.5);
+                        colors.push(color);
+                    }
+
+                    vertices.len() - 1
+                })
+            };
+
+            let [a, b, c] = [corners[0], corners[1], corners[2]];
+            let ab = midpoint(a, b);
+            let bc = midpoint(b, c);
+            let ca = midpoint(c, a);
+
+            for face_vertices in [[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]] {
+                faces.push(Face::new(face_vertices.to_vec(), face.patch));
+            }
+        }
+
+        let patches = self
+            .patches
+            .iter()
+            .map(|p| Patch::new(p.name().to_string()))
+            .collect();
+
+        *self = HeMesh::new(&vertices, &faces, &patches);
+        self.colors = colors;
+    }
+
+    /// Split the vertices along the feature edges (using a threshold angle in
+    /// radians) so each smoothing group of faces around a vertex references
+    /// its own copy of the vertex. The feature edges become boundary edges.
+    /// The first copy of a vertex keeps its index and others are appended.
+    pub fn split_vertices_by_crease(&mut self, angle: f64) {
+        self.invalidate();
+
+        let mut sets = UnionFind::new(self.n_half_edges());
+
+        // Each half edge identifies the face corner at its origin. Corners
+        // on either side of a smooth edge share both of the edge vertices.
+        for (i, j) in self.smooth_edges(angle) {
+            sets.union(i, self.half_edges[j].next);
+            sets.union(self.half_edges[i].next, j);
+        }
+
+        let mut vertices = self
+            .vertices
+            .iter()
+            .map(|v| Vertex::from(v.point))
+            .collect::<Vec<Vertex>>();
+        let mut colors = self.colors.clone();
+        let mut used = vec![false; self.n_vertices()];
+        let mut lookup = HashMap::new();
+        let mut corners = vec![0; self.n_half_edges()];
+
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let origin = self.half_edges[i].origin;
+
+            *corner = *lookup.entry(sets.find(i)).or_insert_with(|| {
+                if !used[origin] {
+                    used[origin] = true;
+                    return origin;
+                }
+
+                vertices.push(vertices[origin]);
+
+                if let Some(colors) = colors.as_mut() {
+                    colors.push(colors[origin]);
+                }
+
+                vertices.len() - 1
+            });
+        }
+
+        let faces = (0..self.n_faces())
+            .map(|i| {
+                let vertices = self
+                    .face_half_edges(i)
+                    .iter()
+                    .map(|&j| corners[j])
+                    .collect();
+                Face::new(vertices, self.faces[i].patch)
+            })
+            .collect();
+
+        let patches = self
+            .patches
+            .iter()
+            .map(|p| Patch::new(p.name().to_string()))
+            .collect();
+
+        *self = HeMesh::new(&vertices, &faces, &patches);
+        self.colors = colors;
+    }
+
+    /// Compute the interior edges which are not feature edges using a
+    /// threshold angle in radians. This returns the pair of half edges
+    /// defining the edge.
+    fn smooth_edges(&self, angle: f64) -> Vec<(usize, usize)> {
+        let features = self
+            .feature_edges(angle)
+            .into_iter()
+            .collect::<HashSet<(usize, usize)>>();
+
+        self.half_edges
+            .iter()
+            .enumerate()
+            .filter_map(|(i, half_edge)| half_edge.twin.map(|j| (i, j)))
+            .filter(|&(i, j)| i < j && !features.contains(&(i, j)))
+            .collect()
+    }
+
+    /// Weld each vertex into the vertex indexed by the lookup. Each lookup
+    /// value must be a vertex which maps onto itself.
+    fn weld_vertices(&mut self, lookup: &[usize]) {
+        let mut indices = BTreeMap::new();
+
+        for &index in lookup.iter() {
+            indices.insert(index, 0);
+        }
+
+        for (i, (index, value)) in indices.iter_mut().enumerate() {
+            self.vertices[i] = self.vertices[*index];
+            *value = i;
+
+            if let Some(colors) = self.colors.as_mut() {
+                colors[i] = colors[*index];
+            }
+        }
+
+        for half_edge in self.half_edges.iter_mut() {
+            (*half_edge).origin = indices[&lookup[half_edge.origin]];
+        }
+
+        self.link_boundary_half_edges();
+        self.vertices.truncate(indices.len());
+
+        if let Some(colors) = self.colors.as_mut() {
+            colors.truncate(indices.len());
+        }
+    }
+
+    /// Join the boundary half edges which share the same end points as twins.
+    /// This panics if more than two half edges share an edge.
+    fn link_boundary_half_edges(&mut self) {
+        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+
+        for (i, half_edge) in self.half_edges.iter().enumerate() {
+            if half_edge.is_boundary() {
+                let j = half_edge.origin;
+                let k = self.half_edges[half_edge.next].origin;
+
+                edges
+                    .entry((j.min(k), j.max(k)))
+                    .and_modify(|p| p.push(i))
+                    .or_insert(vec![i]);
+            }
+        }
+
+        for (_, shared) in edges.iter() {
+            if shared.len() > 2 {
+                panic!("non-manifold mesh");
+            }
+
+            if shared.len() == 2 {
+                self.half_edges[shared[0]].twin = Some(shared[1]);
+                self.half_edges[shared[1]].twin = Some(shared[0]);
+            }
+        }
+    }
+
+    /// Split an edge by inserting a new vertex at a point. Triangular faces
+    /// on either side of the edge are split into two triangles while other
+    /// faces gain a vertex. This returns the index of the new vertex.
+    pub fn split_edge(&mut self, half_edge: usize, point: Vector3) -> usize {
+        self.invalidate();
+
+        let index = self.n_vertices();
+        let j = self.half_edges[half_edge].origin;
+        let k = self.half_edges[self.half_edges[half_edge].next].origin;
+
+        self.vertices.push(HeVertex { point, half_edge });
+
+        if let Some(colors) = self.colors.as_mut() {
+            let color = std::array::from_fn(|i| (colors[j][i] + colors[k][i]) * 0.5);
+            colors.push(color);
+        }
+
+        self.split_edge_at(half_edge, index);
+        index
+    }
+
+    /// Split an edge at an existing vertex
+    fn split_edge_at(&mut self, half_edge: usize, vertex: usize) {
+        let twin = self.half_edges[half_edge].twin;
+        let origin = self.half_edges[half_edge].origin;
+        let split = self.split_face_edge(half_edge, vertex);
+
+        self.half_edges[half_edge].twin = None;
+        self.vertices[vertex].half_edge = split;
+
+        if let Some(twin) = twin {
+            let twin_split = self.split_face_edge(twin, vertex);
+
+            // Pair the halves by their shared end point
+            let pairs = if self.half_edges[twin].origin == origin {
+                [(half_edge, twin), (split, twin_split)]
+            } else {
+                [(half_edge, twin_split), (split, twin)]
+            };
+
+            for (i, j) in pairs {
+                self.half_edges[i].twin = Some(j);
+                self.half_edges[j].twin = Some(i);
+            }
+        }
+    }
+
+    /// Split the face of a half edge at a vertex without updating any twins.
+    /// The half edge is shortened to end at the vertex and the new half edge
+    /// starting at the vertex is returned.
+    fn split_face_edge(&mut self, index: usize, vertex: usize) -> usize {
+        let half_edge = self.half_edges[index];
+        let face = half_edge.face;
+        let next = half_edge.next;
+        let prev = half_edge.prev;
+        let count = self.n_half_edges();
+
+        if self.half_edges[prev].prev != next {
+            let split = HeHalfEdge::new(vertex, face, index, next, None);
+            self.half_edges.push(split);
+            self.half_edges[index].next = count;
+            self.half_edges[next].prev = count;
+            return count;
+        }
+
+        // Triangle (a, b, c) split into (a, m, c) and (m, b, c)
+        let c = self.half_edges[prev].origin;
+        let other = self.n_faces();
+        let (e1, e2, e3) = (count, count + 1, count + 2);
+
+        self.half_edges
+            .push(HeHalfEdge::new(vertex, face, index, prev, Some(e2)));
+        self.half_edges
+            .push(HeHalfEdge::new(c, other, next, e3, Some(e1)));
+        self.half_edges
+            .push(HeHalfEdge::new(vertex, other, e2, next, None));
+
+        self.half_edges[index].next = e1;
+        self.half_edges[prev].prev = e1;
+        self.half_edges[next].face = other;
+        self.half_edges[next].prev = e3;
+        self.half_edges[next].next = e2;
+
+        let patch = self.faces[face].patch;
+        self.faces[face].half_edge = index;
+        self.faces.push(HeFace::new(e3, patch));
+
+        e3
+    }
+
+    /// Flip an interior edge by half edge shared by two triangles so that it
+    /// connects the two opposite vertices. This returns an error if the edge
+    /// is on the boundary, either face is not a triangle, or the flipped edge
+    /// already exists.
+    pub fn flip_edge(&mut self, half_edge: usize) -> Result<(), MeshError> {
+        self.invalidate();
+
+        if half_edge >= self.n_half_edges() {
+            return Err(MeshError::InvalidHalfEdge(half_edge));
+        }
+
+        let h = half_edge;
+        let Some(t) = self.half_edges[h].twin else {
+            return Err(MeshError::InvalidFlip(h));
+        };
+
+        for i in [h, t] {
+            let half_edge = self.half_edges[i];
+
+            if self.half_edges[half_edge.prev].prev != half_edge.next {
+                return Err(MeshError::InvalidFace(half_edge.face));
+            }
+        }
+
+        // Triangles (a, b, c) and (b, a, d) become (d, c, a) and (c, d, b)
+        let (hn, hp) = (self.half_edges[h].next, self.half_edges[h].prev);
+        let (tn, tp) = (self.half_edges[t].next, self.half_edges[t].prev);
+        let (f1, f2) = (self.half_edges[h].face, self.half_edges[t].face);
+        let a = self.half_edges[h].origin;
+        let b = self.half_edges[t].origin;
+        let c = self.half_edges[hp].origin;
+        let d = self.half_edges[tp].origin;
+
+        if c == d || self.vertex_one_ring(c).contains(&d) {
+            return Err(MeshError::InvalidFlip(h));
+        }
+
+        for (i, origin, face, prev, next) in [
+            (h, d, f1, tn, hp),
+            (hp, c, f1, h, tn),
+            (tn, a, f1, hp, h),
+            (t, c, f2, hn, tp),
+            (tp, d, f2, t, hn),
+            (hn, b, f2, tp, t),
+        ] {
+            self.half_edges[i].origin = origin;
+            self.half_edges[i].face = face;
+            self.half_edges[i].prev = prev;
+            self.half_edges[i].next = next;
+        }
+
+        self.faces[f1].half_edge = h;
+        self.faces[f2].half_edge = t;
+
+        if self.vertices[a].half_edge == h {
+            self.vertices[a].half_edge = tn;
+        }
+
+        if self.vertices[b].half_edge == t {
+            self.vertices[b].half_edge = hn;
+        }
+
+        Ok(())
+    }
+
+    /// Adjust the edge lengths of a triangular mesh towards the range between
+    /// a minimum and maximum length. Each iteration splits the edges longer
+    /// than the maximum at their midpoints and then collapses the edges
+    /// shorter than the minimum, skipping collapses which would create an
+    /// edge longer than the maximum so the two never undo each other. This
+    /// stops early once an iteration makes no changes and returns the number
+    /// of splits and collapses.
+    pub fn adjust_edge_lengths(
+        &mut self,
+        min_length: f64,
+        max_length: f64,
+        iterations: usize,
+    ) -> (usize, usize) {
+        self.invalidate();
+
+        let mut n_splits = 0;
+        let mut n_collapses = 0;
+
+        for i in 0..self.n_faces() {
+            if self.face_half_edges(i).len() != 3 {
+                panic!("mesh must be triangular");
+            }
+        }
+
+        for _ in 0..iterations {
+            let splits = self.split_long_edges(max_length);
+            let collapses = self.collapse_short_edges(min_length, max_length);
+
+            n_splits += splits;
+            n_collapses += collapses;
+
+            if splits + collapses == 0 {
+                break;
+            }
+        }
+
+        (n_splits, n_collapses)
+    }
+
+    /// Compute the length of an edge by half edge
+    fn edge_length(&self, half_edge: usize) -> f64 {
+        let j = self.half_edges[half_edge].origin;
+        let k = self.half_edges[self.half_edges[half_edge].next].origin;
+        (self.vertices[k].point - self.vertices[j].point).mag()
+    }
+
+    /// Split each edge longer than a maximum length at its midpoint. This
+    /// returns the number of edges split.
+    fn split_long_edges(&mut self, max_length: f64) -> usize {
+        let mut count = 0;
+
+        for h in 0..self.n_half_edges() {
+            let half_edge = self.half_edges[h];
+
+            if half_edge.twin.is_none_or(|t| h < t) && self.edge_length(h) > max_length {
+                let p = self.vertices[half_edge.origin].point;
+                let q = self.vertices[self.half_edges[half_edge.next].origin].point;
+                self.split_edge(h, (p + q) * 0.5);
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Collapse each edge shorter than a minimum length into its midpoint or
+    /// either end point, whichever is first to not create an edge longer than
+    /// a maximum length. Edges with a single boundary vertex are collapsed
+    /// onto it. This returns the number of edges collapsed.
+    fn collapse_short_edges(&mut self, min_length: f64, max_length: f64) -> usize {
+        let mut removed_vertices = vec![];
+        let mut removed_faces = vec![];
+        let mut removed_half_edges = vec![false; self.n_half_edges()];
+
+        for h in 0..self.n_half_edges() {
+            if removed_half_edges[h] || self.edge_length(h) >= min_length {
+                continue;
+            }
+
+            let j = self.half_edges[h].origin;
+            let k = self.half_edges[self.half_edges[h].next].origin;
+            let (p, q) = (self.vertices[j].point, self.vertices[k].point);
+
+            let points = match (self.is_boundary_vertex(j), self.is_boundary_vertex(k)) {
+                (true, false) => vec![p],
+                (false, true) => vec![q],
+                _ => vec![(p + q) * 0.5, p, q],
+            };
+
+            let ring = [j, k]
+                .iter()
+                .flat_map(|&v| self.vertex_one_ring(v))
+                .collect::<Vec<usize>>();
+
+            let point = points.into_iter().find(|point| {
+                ring.iter()
+                    .all(|&v| (self.vertices[v].point - *point).mag() <= max_length)
+            });
+
+            let Some(point) = point else {
+                continue;
+            };
+
+            if let Ok((faces, half_edges)) = self.collapse_edge_to(h, point) {
+                removed_vertices.push(k);
+                removed_faces.extend(faces);
+
+                for i in half_edges {
+                    removed_half_edges[i] = true;
+                }
+            }
+        }
+
+        let removed_half_edges = removed_half_edges
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &removed)| removed.then_some(i))
+            .collect::<Vec<usize>>();
+
+        self.remove_elements(&removed_vertices, &removed_faces, &removed_half_edges);
+        removed_vertices.len()
+    }
+
+    /// Flip the interior edges between triangles which fail the Delaunay
+    /// criterion (the angles opposite the edge summing to more than pi) until
+    /// no improving flip remains. The number of flips is limited to ten times
+    /// the number of edges. This returns the number of flips performed.
+    pub fn delaunay_flips(&mut self) -> usize {
+        self.invalidate();
+
+        let mut queue = (0..self.n_half_edges())
+            .filter(|&i| self.half_edges[i].twin.is_some_and(|j| i < j))
+            .collect::<VecDeque<usize>>();
+
+        let budget = 10 * (self.n_half_edges() - queue.len());
+        let mut count = 0;
+
+        while let Some(h) = queue.pop_front() {
+            if count >= budget {
+                break;
+            }
+
+            let Some(t) = self.half_edges[h].twin else {
+                continue;
+            };
+
+            let angle = |i: usize| {
+                let half_edge = self.half_edges[i];
+                let prev = self.half_edges[half_edge.prev];
+
+                if prev.prev != half_edge.next {
+                    return 0.;
+                }
+
+                let p = self.vertices[half_edge.origin].point;
+                let q = self.vertices[self.half_edges[half_edge.next].origin].point;
+                let r = self.vertices[prev.origin].point;
+                Vector3::angle(&(p - r), &(q - r))
+            };
+
+            if angle(h) + angle(t) <= std::f64::consts::PI + EPSILON {
+                continue;
+            }
+
+            if self.flip_edge(h).is_ok() {
+                count += 1;
+
+                for i in [h, t] {
+                    let half_edge = self.half_edges[i];
+                    queue.extend([half_edge.next, half_edge.prev]);
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Collapse an edge by half edge into a single vertex placed at the
+    /// midpoint, removing the triangles on either side of the edge. This
+    /// returns the index of the surviving vertex or an error if either face
+    /// is not a triangle or the collapse would make the mesh non-manifold.
+    pub fn collapse_edge(&mut self, half_edge: usize) -> Result<usize, MeshError> {
+        self.invalidate();
+
+        if half_edge >= self.n_half_edges() {
+            return Err(MeshError::InvalidHalfEdge(half_edge));
+        }
+
+        let j = self.half_edges[half_edge].origin;
+        let k = self.half_edges[self.half_edges[half_edge].next].origin;
+        let point = (self.vertices[j].point + self.vertices[k].point) * 0.5;
+
+        let (faces, half_edges) = self.collapse_edge_to(half_edge, point)?;
+        self.remove_elements(&[k], &faces, &half_edges);
+
+        Ok(if k < j { j - 1 } else { j })
+    }
+
+    /// Collapse an edge by half edge into its origin vertex placed at a
+    /// point. The head vertex and the removed faces and half edges are left
+    /// unreferenced rather than removed; the removed faces and half edges
+    /// are returned.
+    fn collapse_edge_to(
+        &mut self,
+        half_edge: usize,
+        point: Vector3,
+    ) -> Result<(Vec<usize>, Vec<usize>), MeshError> {
+        let twin = self.half_edges[half_edge].twin;
+        let sides = [Some(half_edge), twin]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<usize>>();
+
+        let j = self.half_edges[half_edge].origin;
+        let k = self.half_edges[self.half_edges[half_edge].next].origin;
+        let mut faces = vec![];
+        let mut removed = vec![];
+        let mut opposite = vec![];
+
+        for &i in sides.iter() {
+            let h = self.half_edges[i];
+
+            if self.half_edges[h.prev].prev != h.next {
+                return Err(MeshError::InvalidFace(h.face));
+            }
+
+            faces.push(h.face);
+            removed.extend([i, h.next, h.prev]);
+            opposite.push(self.half_edges[h.prev].origin);
+        }
+
+        // The only vertices adjacent to both end points may be the opposite
+        // vertices, an interior edge may not join two boundary vertices, and
+        // the opposite vertices must keep at least one face.
+        let ring = self.vertex_one_ring(k);
+        let shared = self
+            .vertex_one_ring(j)
+            .iter()
+            .filter(|v| ring.contains(v))
+            .count();
+
+        let pinched = twin.is_some() && self.is_boundary_vertex(j) && self.is_boundary_vertex(k);
+
+        let degenerate = opposite.iter().any(|&v| {
+            let min = if self.is_boundary_vertex(v) { 3 } else { 4 };
+            self.vertex_one_ring(v).len() < min
+        });
+
+        if shared != sides.len() || pinched || degenerate {
+            return Err(MeshError::InvalidCollapse(half_edge));
+        }
+
+        let outgoing = self.vertex_half_edges(k);
+        let mut handles = vec![];
+
+        for &v in [j].iter().chain(opposite.iter()) {
+            let mut candidates = self.vertex_half_edges(v);
+
+            if v == j {
+                candidates.extend(outgoing.iter());
+            }
+
+            match candidates.into_iter().find(|i| !removed.contains(i)) {
+                Some(i) => handles.push((v, i)),
+                None => return Err(MeshError::InvalidCollapse(half_edge)),
+            }
+        }
+
+        // Join the twins of the two remaining edges of each removed triangle
+        for &i in sides.iter() {
+            let h = self.half_edges[i];
+            let u = self.half_edges[h.next].twin;
+            let v = self.half_edges[h.prev].twin;
+
+            if let Some(u) = u {
+                self.half_edges[u].twin = v;
+            }
+
+            if let Some(v) = v {
+                self.half_edges[v].twin = u;
+            }
+        }
+
+        for i in outgoing {
+            self.half_edges[i].origin = j;
+        }
+
+        for (v, i) in handles {
+            self.vertices[v].half_edge = i;
+        }
+
+        self.vertices[j].point = point;
+
+        if let Some(colors) = self.colors.as_mut() {
+            colors[j] = std::array::from_fn(|i| (colors[j][i] + colors[k][i]) * 0.5);
+        }
+
+        Ok((faces, removed))
+    }
+
+    /// Remove a face by index. Any vertices which are no longer referenced
+    /// are also removed and the remaining elements are renumbered.
+    pub fn remove_face(&mut self, index: usize) {
+        let mut lookup = vec![None; self.n_vertices()];
+        let mut vertices = vec![];
+        let mut colors = vec![];
+        let mut faces = vec![];
+
+        for i in (0..self.n_faces()).filter(|&i| i != index) {
+            let face_vertices = self
+                .face_vertices(i)
+                .iter()
+                .map(|&j| {
+                    *lookup[j].get_or_insert_with(|| {
+                        vertices.push(Vertex::from(self.vertices[j].point));
+                        colors.extend(self.colors.as_ref().map(|c| c[j]));
+                        vertices.len() - 1
+                    })
+                })
+                .collect();
+
+            faces.push(Face::new(face_vertices, self.faces[i].patch));
+        }
+
+        let patches = self
+            .patches
+            .iter()
+            .map(|p| Patch::new(p.name().to_string()))
+            .collect();
+
+        let has_colors = self.colors.is_some();
+        *self = HeMesh::new(&vertices, &faces, &patches);
+        self.colors = has_colors.then_some(colors);
+    }
+
+    /// Remove unreferenced vertices, faces and half edges by index and
+    /// renumber the handles of those remaining.
+    fn remove_elements(&mut self, vertices: &[usize], faces: &[usize], half_edges: &[usize]) {
+        let renumber = |n: usize, removed: &[usize]| {
+            let removed = removed.iter().collect::<HashSet<&usize>>();
+            let mut count = 0;
+
+            (0..n)
+                .map(|i| {
+                    if removed.contains(&i) {
+                        None
+                    } else {
+                        count += 1;
+                        Some(count - 1)
+                    }
+                })
+                .collect::<Vec<Option<usize>>>()
+        };
+
+        let vertex_ids = renumber(self.n_vertices(), vertices);
+        let face_ids = renumber(self.n_faces(), faces);
+        let half_edge_ids = renumber(self.n_half_edges(), half_edges);
+
+        let mut i = 0;
+        self.vertices.retain(|_| {
+            i += 1;
+            vertex_ids[i - 1].is_some()
+        });
+
+        let mut i = 0;
+        self.faces.retain(|_| {
+            i += 1;
+            face_ids[i - 1].is_some()
+        });
+
+        let mut i = 0;
+        self.half_edges.retain(|_| {
+            i += 1;
+            half_edge_ids[i - 1].is_some()
+        });
+
+        if let Some(colors) = self.colors.as_mut() {
+            let mut i = 0;
+            colors.retain(|_| {
+                i += 1;
+                vertex_ids[i - 1].is_some()
+            });
+        }
+
+        for vertex in self.vertices.iter_mut() {
+            vertex.half_edge = half_edge_ids[vertex.half_edge].unwrap();
+        }
+
+        for face in self.faces.iter_mut() {
+            face.half_edge = half_edge_ids[face.half_edge].unwrap();
+        }
+
+        for half_edge in self.half_edges.iter_mut() {
+            half_edge.origin = vertex_ids[half_edge.origin].unwrap();
+            half_edge.face = face_ids[half_edge.face].unwrap();
+            half_edge.prev = half_edge_ids[half_edge.prev].unwrap();
+            half_edge.next = half_edge_ids[half_edge.next].unwrap();
+            half_edge.twin = half_edge.twin.map(|t| half_edge_ids[t].unwrap());
+        }
+    }
+
+    /// Validate the half edge connectivity. This checks that the half edges
+    /// form closed face loops, that twins are reciprocal and share the same
+    /// end points, that the vertex and face handles are consistent, and that
+    /// no edge is shared by more than two faces.
+    pub fn validate(&self) -> Result<(), MeshError> {
+        let nv = self.n_vertices();
+        let nf = self.n_faces();
+        let nh = self.n_half_edges();
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for (i, half_edge) in self.half_edges.iter().enumerate() {
+            if half_edge.origin >= nv
+                || half_edge.face >= nf
+                || half_edge.next >= nh
+                || half_edge.prev >= nh
+                || self.half_edges[half_edge.next].prev != i
+                || self.half_edges[half_edge.prev].next != i
+                || self.half_edges[half_edge.next].face != half_edge.face
+            {
+                return Err(MeshError::InvalidHalfEdge(i));
+            }
+
+            let j = half_edge.origin;
+            let k = self.half_edges[half_edge.next].origin;
+
+            if let Some(twin) = half_edge.twin {
+                if twin >= nh || twin == i || self.half_edges[twin].twin != Some(i) {
+                    return Err(MeshError::InvalidHalfEdge(i));
+                }
+
+                let p = self.half_edges[twin].origin;
+                let q = self.half_edges[self.half_edges[twin].next].origin;
+
+                if (p.min(q), p.max(q)) != (j.min(k), j.max(k)) {
+                    return Err(MeshError::InvalidHalfEdge(i));
+                }
+            }
+
+            let count = edges.entry((j.min(k), j.max(k))).or_insert(0);
+            *count += 1;
+
+            if *count > 2 {
+                return Err(MeshError::NonManifoldEdge(j.min(k), j.max(k)));
+            }
+        }
+
+        for (i, face) in self.faces.iter().enumerate() {
+            if face.half_edge >= nh || self.half_edges[face.half_edge].face != i {
+                return Err(MeshError::InvalidFace(i));
+            }
+
+            let mut current = self.half_edges[face.half_edge].next;
+            let mut count = 1;
+
+            while current != face.half_edge {
+                current = self.half_edges[current].next;
+                count += 1;
+
+                if count > nh {
+                    return Err(MeshError::InvalidFace(i));
+                }
+            }
+        }
+
+        for (i, vertex) in self.vertices.iter().enumerate() {
+            if vertex.half_edge >= nh || self.half_edges[vertex.half_edge].origin != i {
+                return Err(MeshError::InvalidVertex(i));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Combine patches with the same name explicitly.
+    pub fn remove_duplicate_patches(&mut self) {
+        let mut patches = vec![];
+        let mut index: HashMap<&str, usize> = HashMap::new();
+
+        for (i, patch) in self.patches.iter().enumerate() {
+            let name = patch.name();
+
+            if !index.contains_key(name) {
+                index.insert(name, i);
+                patches.push(patch.clone());
+            }
+        }
+
+        for face in self.faces.iter_mut() {
+            if let Some(patch) = face.patch {
+                let name = self.patches[patch].name();
+                face.patch = Some(index[name]);
+            }
+        }
+
+        self.patches = patches;
+    }
+
+    /// Split each patch into its contiguous regions. Additional regions of a
+    /// patch are named with an increasing suffix (e.g. "name.1", "name.2").
+    pub fn split_disconnected_patches(&mut self) {
+        let mut visited = vec![false; self.n_faces()];
+        let mut counts = vec![0; self.n_patches()];
+
+        for next in 0..visited.len() {
+            let patch = match self.faces[next].patch {
+                Some(patch) if !visited[next] => patch,
+                _ => continue,
+            };
+
+            let target = if counts[patch] == 0 {
+                patch
+            } else {
+                let name = format!("{}.{}", self.patches[patch].name, counts[patch]);
+                self.patches.push(HePatch { name });
+                self.patches.len() - 1
+            };
+
+            counts[patch] += 1;
+
+            let mut queue = VecDeque::from([next]);
+
+            while let Some(current) = queue.pop_front() {
+                if !visited[current] {
+                    visited[current] = true;
+                    self.faces[current].patch = Some(target);
+
+                    for neighbor in self.face_neighbors(current) {
+                        if !visited[neighbor] && self.faces[neighbor].patch == Some(patch) {
+                            queue.push_back(neighbor);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Construct the dual mesh with a vertex at the centroid of each face and
+    /// a face for each vertex connecting the centroids of the faces about it
+    /// in order. The dual keeps the orientation but not the patches. This
+    /// requires a closed mesh.
+    pub fn dual(&self) -> HeMesh {
+        if !self.is_closed() {
+            panic!("mesh must be closed");
+        }
+
+        let vertices = (0..self.n_faces())
+            .map(|i| {
+                let points = self.face_vertices(i);
+                let sum = points
+                    .iter()
+                    .fold(Vector3::zeros(), |sum, &j| sum + self.vertices[j].point);
+                Vertex::from(sum / points.len() as f64)
+            })
+            .collect::<Vec<Vertex>>();
+
+        let faces = (0..self.n_vertices())
+            .map(|i| Face::new(self.vertex_faces(i), None))
+            .collect::<Vec<Face>>();
+
+        HeMesh::new(&vertices, &faces, &vec![])
+    }
+
+    /// Extract a subset from the mesh by the index of the face. This
+    /// copies the target subset into a new mesh.
+    pub fn extract_faces(&self, face_ids: &Vec<usize>) -> HeMesh {
+        let mut faces = Vec::<Face>::with_capacity(face_ids.len());
+        let mut vertices = vec![];
+        let mut patches = vec![];
+        let mut colors = vec![];
+        let mut index_vertices = HashMap::new();
+        let mut index_patches = HashMap::new();
+
+        for &face_id in face_ids.iter() {
+            let mut vertices_ = self.face_vertices(face_id);
+            let mut patch_ = None;
+
+            for old_id in vertices_.iter_mut() {
+                if !index_vertices.contains_key(old_id) {
+                    let new_id = index_vertices.len();
+                    index_vertices.insert(*old_id, new_id);
+
+                    let point = self.vertices[*old_id].point;
+                    let vertex = Vertex::from(point);
+                    vertices.push(vertex);
+
+                    if let Some(self_colors) = self.colors.as_ref() {
+                        colors.push(self_colors[*old_id]);
+                    }
+                }
+
+                *old_id = index_vertices[old_id];
+            }
+
+            if let Some(old_id) = self.faces[face_id].patch {
+                if !index_patches.contains_key(&old_id) {
+                    let new_id = index_patches.len();
+                    index_patches.insert(old_id, new_id);
+
+                    let name = self.patches[old_id].name().to_string();
+                    let patch = Patch::new(name);
+                    patches.push(patch);
+                }
+
+                patch_ = Some(index_patches[&old_id]);
+            }
+
+            let face = Face::new(vertices_, patch_);
+            faces.push(face);
+        }
+
+        let mut mesh = HeMesh::new(&vertices, &faces, &patches);
+        mesh.colors = self.colors.as_ref().map(|_| colors);
+        mesh
+    }
+
+    /// Extract a subset from the mesh by the patch names. This copies the
+    /// target subset into a new mesh.
+    pub fn extract_patches(&self, patches: &Vec<String>) -> HeMesh {
+        let mut selected = HashSet::new();
+        let mut index = vec![false; self.n_patches()];
+        let mut faces = vec![];
+
+        for patch in patches.iter() {
+            selected.insert(patch.clone());
+        }
+
+        for (i, patch) in self.patches.iter().enumerate() {
+            if selected.contains(patch.name()) {
+                index[i] = true;
+            }
+        }
+
+        for (i, face) in self.faces.iter().enumerate() {
+            if let Some(patch) = face.patch {
+                if index[patch] {
+                    faces.push(i);
+                }
+            }
+        }
+
+        self.extract_faces(&faces)
+    }
+
+    /// Orient the mesh such that the faces in each component have the same
+    /// directed normal relative to each other. This does not ensure that the
+    /// components' orientation are consistent.
+    pub fn orient(&mut self) -> usize {
+        self.invalidate();
+
+        let mut oriented = vec![false; self.n_faces()];
+        let mut count = 0;
+
+        for component in self.components() {
+            count += self.orient_flood(component[0], &mut oriented);
+        }
+
+        count
+    }
+
+    /// Orient the component of a seed face such that its faces have the same
+    /// directed normal as the seed. The seed keeps its winding or is flipped
+    /// first. This returns the number of flipped faces (including the seed).
+    pub fn orient_from(&mut self, seed_face: usize, keep_seed_orientation: bool) -> usize {
+        self.invalidate();
+
+        let mut oriented = vec![false; self.n_faces()];
+        let mut count = 0;
+
+        if !keep_seed_orientation {
+            self.flip_face(seed_face);
+            count += 1;
+        }
+
+        count + self.orient_flood(seed_face, &mut oriented)
+    }
+
+    /// Flood the orientation of a seed face outward across its component by
+    /// flipping any neighbor which is inconsistent with the face it was
+    /// reached from. This returns the number of flipped faces.
+    fn orient_flood(&mut self, seed: usize, oriented: &mut [bool]) -> usize {
+        let mut queue = VecDeque::from([seed]);
+        let mut count = 0;
+
+        while let Some(current) = queue.pop_front() {
+            if !oriented[current] {
+                oriented[current] = true;
+
+                for neighbor in self.face_neighbors(current) {
+                    if !oriented[neighbor] {
+                        queue.push_back(neighbor);
+
+                        if !self.is_consistent_faces(current, neighbor) {
+                            self.flip_face(neighbor);
+                            count += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Orient the mesh consistently and then orient each component as a
+    /// whole. Closed components are flipped to face outward (a positive
+    /// enclosed volume) and open components are flipped to face toward the
+    /// reference point, if any.
+    pub fn orient_all(&mut self, reference: Option<Vector3>) {
+        self.invalidate();
+
+        self.orient();
+
+        for component in self.components() {
+            let is_closed = component.iter().all(|&i| {
+                self.face_half_edges(i)
+                    .iter()
+                    .all(|&j| self.half_edges[j].twin.is_some())
+            });
+
+            let is_flipped = if is_closed {
+                self.component_volume(&component) < 0.
+            } else if let Some(reference) = reference {
+                let facing = component.iter().fold(0., |acc, &i| {
+                    let vertices = self.face_vertices(i);
+                    let centroid = vertices
+                        .iter()
+                        .fold(Vector3::zeros(), |acc, &j| acc + self.vertices[j].point)
+                        / vertices.len() as f64;
+                    let u = self.face_normal(i) * self.face_area(i);
+                    acc + Vector3::dot(&u, &(reference - centroid))
+                });
+
+                facing < 0.
+            } else {
+                false
+            };
+
+            if is_flipped {
+                component.iter().for_each(|&i| self.flip_face(i));
+            }
+        }
+    }
+
+    /// Compute the signed volume enclosed by the mesh as the sum of the
+    /// tetrahedra formed by each (fan triangulated) face and the origin. This
+    /// assumes the mesh is closed and oriented; the volume is positive when
+    /// the faces are oriented outward.
+    pub fn volume(&self) -> f64 {
+        self.component_volume(&(0..self.n_faces()).collect::<Vec<usize>>())
+    }
+
+    /// Compute the centroid of the mesh. For a closed mesh enclosing a
+    /// non-zero volume this is the center of mass of the solid, computed from
+    /// the signed tetrahedra formed by each (fan triangulated) face and the
+    /// origin. Otherwise this is the area-weighted average of the face
+    /// centroids.
+    pub fn centroid(&self) -> Vector3 {
+        let mut volume = 0.;
+        let mut moment = Vector3::zeros();
+
+        if self.is_closed() {
+            for (_, triangle) in self.triangles() {
+                let u = Vector3::cross(&triangle.q(), &triangle.r());
+                let v = Vector3::dot(&triangle.p(), &u) / 6.;
+                volume += v;
+                moment += (triangle.p() + triangle.q() + triangle.r()) * (v / 4.);
+            }
+        }
+
+        if volume.abs() > EPSILON {
+            return moment / volume;
+        }
+
+        let mut area = 0.;
+        let mut moment = Vector3::zeros();
+
+        for (_, triangle) in self.triangles() {
+            let a = triangle.area();
+            area += a;
+            moment += triangle.centroid() * a;
+        }
+
+        if area > EPSILON {
+            moment / area
+        } else {
+            Vector3::zeros()
+        }
+    }
+
+    /// Compute the signed volume enclosed by the faces of a component
+    fn component_volume(&self, faces: &[usize]) -> f64 {
+        let mut volume = 0.;
+
+        for &i in faces.iter() {
+            for triangle in self.face_triangles(i) {
+                let u = Vector3::cross(&triangle.q(), &triangle.r());
+                volume += Vector3::dot(&triangle.p(), &u) / 6.;
+            }
+        }
+
+        volume
+    }
+
+    /// Compute the faces for each contiguous component in the mesh. The faces
+    /// of each component are sorted in ascending order and the components are
+    /// sorted by their smallest face index.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut components = vec![];
+        let mut visited = vec![false; self.n_faces()];
+
+        for next in 0..visited.len() {
+            if !visited[next] {
+                let mut queue = VecDeque::from([next]);
+                let mut component = vec![];
+
+                while let Some(current) = queue.pop_front() {
+                    if !visited[current] {
+                        visited[current] = true;
+                        component.push(current);
+
+                        for neighbor in self.face_neighbors(current) {
+                            if !visited[neighbor] {
+                                queue.push_back(neighbor);
+                            }
+                        }
+                    }
+                }
+
+                component.sort();
+                components.push(component);
+            }
+        }
+
+        components
+    }
+
+    /// Compute the faces for each contiguous component in the mesh using a
+    /// union-find over the twinned half edges. This returns the same result
+    /// as components in a single pass over the half edges.
+    pub fn components_union_find(&self) -> Vec<Vec<usize>> {
+        let mut sets = UnionFind::new(self.n_faces());
+
+        for half_edge in self.half_edges.iter() {
+            if let Some(twin) = half_edge.twin {
+                sets.union(half_edge.face, self.half_edges[twin].face);
+            }
+        }
+
+        let mut components: Vec<Vec<usize>> = vec![];
+        let mut lookup = HashMap::new();
+
+        for i in 0..self.n_faces() {
+            let root = sets.find(i);
+            let index = *lookup.entry(root).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+
+            components[index].push(i);
+        }
+
+        components
+    }
+
+    /// Split the mesh into a separate mesh for each component in the order
+    /// of components.
+    pub fn split_components(&self) -> Vec<HeMesh> {
+        self.components()
+            .iter()
+            .map(|component| self.extract_faces(component))
+            .collect()
+    }
+
+    /// Extract the component with the most faces. Ties between components
+    /// of equal size are broken by the lowest face index.
+    pub fn largest_component(&self) -> HeMesh {
+        let components = self.components();
+        let largest = components.iter().min_by_key(|c| Reverse(c.len()));
+
+        match largest {
+            Some(component) => self.extract_faces(component),
+            None => self.extract_faces(&vec![]),
+        }
+    }
+
+    /// Extract the n components with the most faces into a single mesh. Ties
+    /// between components of equal size are broken by the lowest face index.
+    pub fn keep_largest_components(&self, n: usize) -> HeMesh {
+        let mut components = self.components();
+        components.sort_by_key(|c| Reverse(c.len()));
+
+        let mut faces = components
+            .into_iter()
+            .take(n)
+            .flatten()
+            .collect::<Vec<usize>>();
+        faces.sort();
+
+        self.extract_faces(&faces)
+    }
+
+    /// Split the mesh by feature angle (in radians).
+    pub fn split_by_features(&self, angle: f64) -> Vec<Vec<usize>> {
+        let mut components = vec![];
+        let mut visited = vec![false; self.n_faces()];
+        let normals = self.face_normals();
+
+        for next in 0..visited.len() {
+            if !visited[next] {
+                let mut queue = VecDeque::from([next]);
+                let mut component = vec![];
+
+                while let Some(current) = queue.pop_front() {
+                    if !visited[current] {
+                        visited[current] = true;
+                        component.push(current);
+
+                        for neighbor in self.face_neighbors(current) {
+                            let u = &normals[current];
+                            let v = &normals[neighbor];
+
+                            if !visited[neighbor] && Vector3::dot(&u, &v).acos() < angle {
+                                queue.push_back(neighbor);
+                            }
+                        }
+                    }
+                }
+
+                components.push(component);
+            }
+        }
+
+        components
+    }
+
+    /// Segment the mesh by thresholding a per-vertex scalar field. Faces are
+    /// flood filled across edges where both endpoints lie on the same side
+    /// of the threshold. This returns the segment index for each face.
+    pub fn segment_by_scalar(&self, values: &[f64], threshold: f64) -> Vec<usize> {
+        if values.len() != self.n_vertices() {
+            panic!("scalar field must be defined for each vertex");
+        }
+
+        let mut segments = vec![usize::MAX; self.n_faces()];
+        let mut count = 0;
+
+        for next in 0..segments.len() {
+            if segments[next] == usize::MAX {
+                let mut queue = VecDeque::from([next]);
+
+                while let Some(current) = queue.pop_front() {
+                    if segments[current] == usize::MAX {
+                        segments[current] = count;
+
+                        for i in self.face_half_edges(current) {
+                            let half_edge = &self.half_edges[i];
+
+                            if let Some(twin) = half_edge.twin {
+                                let p = values[half_edge.origin] >= threshold;
+                                let q = values[self.half_edges[half_edge.next].origin] >= threshold;
+                                let neighbor = self.half_edges[twin].face;
+
+                                if p == q && segments[neighbor] == usize::MAX {
+                                    queue.push_back(neighbor);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                count += 1;
+            }
+        }
+
+        segments
+    }
+
+    /// Compute the gradient of a scalar field defined at the vertices. This
+    /// is the constant gradient of the piecewise-linear field on each
+    /// triangular face. Degenerate (zero area) faces have a zero gradient.
+    pub fn scalar_gradient(&self, values: &[f64]) -> Vec<Vector3> {
+        if values.len() != self.n_vertices() {
+            panic!("scalar field must be defined for each vertex");
+        }
+
+        let mut gradients = Vec::with_capacity(self.n_faces());
+
+        for i in 0..self.n_faces() {
+            let vertices = self.face_vertices(i);
+
+            if vertices.len() != 3 {
+                panic!("mesh must be triangular");
+            }
+
+            let points = vertices
+                .iter()
+                .map(|&j| self.vertices[j].point)
+                .collect::<Vec<Vector3>>();
+            let normal = Vector3::cross(&(points[1] - points[0]), &(points[2] - points[0]));
+            let area2 = normal.mag();
+
+            if area2 < EPSILON {
+                gradients.push(Vector3::zeros());
+                continue;
+            }
+
+            let normal = normal / area2;
+            let mut gradient = Vector3::zeros();
+
+            // Sum each vertex value times the rotated opposite edge
+            for k in 0..3 {
+                let edge = points[(k + 2) % 3] - points[(k + 1) % 3];
+                gradient += Vector3::cross(&normal, &edge) * values[vertices[k]];
+            }
+
+            gradients.push(gradient / area2);
+        }
+
+        gradients
+    }
+
+    /// Compute the contours of a scalar field defined at the vertices where
+    /// it equals the level. The crossing point on each edge is linearly
+    /// interpolated and the segments of adjacent faces are joined into
+    /// ordered polylines. Closed contours repeat their first point at the end.
+    pub fn iso_contour(&self, values: &[f64], level: f64) -> Vec<Vec<Vector3>> {
+        if values.len() != self.n_vertices() {
+            panic!("scalar field must be defined for each vertex");
+        }
+
+        let mut points: HashMap<(usize, usize), Vector3> = HashMap::new();
+        let mut links: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+
+        for i in 0..self.n_faces() {
+            let vertices = self.face_vertices(i);
+            let n = vertices.len();
+            let mut crossings = vec![];
+
+            for k in 0..n {
+                let j = vertices[k];
+                let l = vertices[(k + 1) % n];
+
+                if (values[j] >= level) != (values[l] >= level) {
+                    let key = (j.min(l), j.max(l));
+                    let t = (level - values[j]) / (values[l] - values[j]);
+                    let p = self.vertices[j].point;
+                    let q = self.vertices[l].point;
+
+                    points.entry(key).or_insert(p + (q - p) * t);
+                    crossings.push(key);
+                }
+            }
+
+            for pair in crossings.chunks_exact(2) {
+                links.entry(pair[0]).or_default().push(pair[1]);
+                links.entry(pair[1]).or_default().push(pair[0]);
+            }
+        }
+
+        // Start open contours from their ends before walking the closed ones
+        let mut keys = links.keys().copied().collect::<Vec<(usize, usize)>>();
+        keys.sort_by_key(|key| (links[key].len() != 1, *key));
+
+        let mut visited = HashSet::new();
+        let mut contours = vec![];
+
+        for start in keys {
+            if visited.contains(&start) {
+                continue;
+            }
+
+            let mut contour = vec![points[&start]];
+            let mut current = start;
+            visited.insert(start);
+
+            while let Some(&next) = links[&current].iter().find(|k| !visited.contains(*k)) {
+                visited.insert(next);
+                contour.push(points[&next]);
+                current = next;
+            }
+
+            if links[&start].len() == 2 && links[&current].contains(&start) {
+                contour.push(points[&start]);
+            }
+
+            contours.push(contour);
+        }
+
+        contours
+    }
+
+    /// Compute the concavity as the maximum distance of any vertex in front
+    /// of any face plane. This is zero (within tolerance) for a convex mesh
+    /// and requires outward oriented faces.
+    pub fn concavity(&self) -> f64 {
+        let faces = (0..self.n_faces()).collect::<Vec<usize>>();
+        self.region_concavity(&faces)
+    }
+
+    /// Compute an approximate convex decomposition of a closed, outward
+    /// oriented mesh. Faces are grown into regions while every vertex of the
+    /// region stays within the concavity tolerance behind every face plane of
+    /// the region. The adjacent regions whose union has the smallest
+    /// concavity are then merged until there are at most max_pieces regions,
+    /// but only while the union stays within the tolerance, so more than
+    /// max_pieces regions may be returned. This is a lightweight
+    /// approximation: each piece is the surface patch of a near-convex part
+    /// (rather than a capped volume) and is suitable as input to a convex
+    /// hull for collision shapes.
+    pub fn approximate_convex_decomposition(
+        &self,
+        max_pieces: usize,
+        concavity: f64,
+    ) -> Vec<HeMesh> {
+        let mut regions: Vec<Option<ConvexRegion>> = vec![];
+        let mut labels = vec![usize::MAX; self.n_faces()];
+        let mut marks = vec![usize::MAX; self.n_vertices()];
+
+        // Each face added to a region is tested incrementally: its new
+        // vertices against the planes of the region and all of the vertices
+        // against its own plane.
+        for next in 0..self.n_faces() {
+            if labels[next] != usize::MAX {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut region = ConvexRegion::default();
+            let mut queue = VecDeque::from([next]);
+
+            while let Some(current) = queue.pop_front() {
+                if labels[current] != usize::MAX {
+                    continue;
+                }
+
+                let vertices = self.face_vertices(current);
+                let plane = (self.face_normal(current), self.vertices[vertices[0]].point);
+                let added = vertices
+                    .into_iter()
+                    .filter(|&j| marks[j] != label)
+                    .collect::<Vec<usize>>();
+
+                let value = region
+                    .concavity
+                    .max(self.planes_concavity(&region.planes, &added))
+                    .max(self.planes_concavity(&[plane], &region.vertices))
+                    .max(self.planes_concavity(&[plane], &added));
+
+                if !region.faces.is_empty() && value > concavity {
+                    continue;
+                }
+
+                for &j in added.iter() {
+                    marks[j] = label;
+                }
+
+                labels[current] = label;
+                region.faces.push(current);
+                region.vertices.extend(added);
+                region.planes.push(plane);
+                region.concavity = value;
+
+                for neighbor in self.face_neighbors(current) {
+                    if labels[neighbor] == usize::MAX {
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+
+            regions.push(Some(region));
+        }
+
+        // Merge the adjacent regions with the smallest combined concavity.
+        // The concavity of each pair is cached until either region changes.
+        let mut cache: HashMap<(usize, usize), f64> = HashMap::new();
+        let mut n_regions = regions.len();
+
+        while n_regions > max_pieces.max(1) {
+            let mut best: Option<(usize, usize, f64)> = None;
+
+            for (i, region) in regions.iter().enumerate() {
+                let Some(region) = region else {
+                    continue;
+                };
+
+                let adjacent = region
+                    .faces
+                    .iter()
+                    .flat_map(|&face| self.face_neighbors(face))
+                    .map(|neighbor| labels[neighbor])
+                    .filter(|&j| j > i)
+                    .collect::<BTreeSet<usize>>();
+
+                for j in adjacent {
+                    let value = *cache.entry((i, j)).or_insert_with(|| {
+                        let other = regions[j].as_ref().unwrap();
+
+                        region
+                            .concavity
+                            .max(other.concavity)
+                            .max(self.planes_concavity(&region.planes, &other.vertices))
+                            .max(self.planes_concavity(&other.planes, &region.vertices))
+                    });
+
+                    if best.is_none_or(|(_, _, v)| value < v) {
+                        best = Some((i, j, value));
+                    }
+                }
+            }
+
+            let Some((i, j, value)) = best else {
+                break;
+            };
+
+            if value > concavity {
+                break;
+            }
+
+            let other = regions[j].take().unwrap();
+            let region = regions[i].as_mut().unwrap();
+
+            for &face in other.faces.iter() {
+                labels[face] = i;
+            }
+
+            region.faces.extend(other.faces);
+            region.vertices.extend(other.vertices);
+            region.vertices.sort();
+            region.vertices.dedup();
+            region.planes.extend(other.planes);
+            region.concavity = value;
+
+            cache.retain(|&(u, v), _| u != i && v != i && u != j && v != j);
+            n_regions -= 1;
+        }
+
+        regions
+            .iter()
+            .flatten()
+            .map(|region| self.extract_faces(&region.faces))
+            .collect()
+    }
+
+    /// Compute the maximum distance of any of the vertices in front of any
+    /// of the planes (unit normal and point). This is zero if there are no
+    /// vertices or planes.
+    fn planes_concavity(&self, planes: &[(Vector3, Vector3)], vertices: &[usize]) -> f64 {
+        let mut concavity: f64 = 0.;
+
+        for (normal, origin) in planes.iter() {
+            for &j in vertices.iter() {
+                let distance = Vector3::dot(normal, &(self.vertices[j].point - *origin));
+                concavity = concavity.max(distance);
+            }
+        }
+
+        concavity
+    }
+
+    /// Compute the maximum distance of any vertex of a subset of faces in
+    /// front of any of the face planes of the subset.
+    fn region_concavity(&self, faces: &[usize]) -> f64 {
+        let vertices = faces
+            .iter()
+            .flat_map(|&i| self.face_vertices(i))
+            .collect::<BTreeSet<usize>>();
+
+        let mut concavity: f64 = 0.;
+
+        for &i in faces.iter() {
+            let normal = self.face_normal(i);
+            let origin = self.vertices[self.half_edges[self.faces[i].half_edge].origin].point;
+
+            for &j in vertices.iter() {
+                let distance = Vector3::dot(&normal, &(self.vertices[j].point - origin));
+                concavity = concavity.max(distance);
+            }
+        }
+
+        concavity
+    }
+
+    /// Flip the orientation of a face. This reverses the direction of all
+    /// half edges for the face.
+    pub fn flip_face(&mut self, index: usize) {
+        self.invalidate();
+
+        let half_edges = self.face_half_edges(index);
+        let origins = half_edges
+            .iter()
+            .map(|&i| self.half_edges[self.half_edges[i].next].origin)
+            .collect::<Vec<usize>>();
+
+        for (&i, &origin) in half_edges.iter().zip(origins.iter()) {
+            let half_edge = self.half_edges[i];
+            self.half_edges[i].next = half_edge.prev;
+            self.half_edges[i].prev = half_edge.next;
+            self.half_edges[i].origin = origin;
+        }
+
+        // Any vertex referencing a half edge of this face must reference the
+        // half edge of this face that now originates from it.
+        for &i in half_edges.iter() {
+            let origin = self.half_edges[i].origin;
+            let current = self.vertices[origin].half_edge;
+
+            if half_edges.contains(&current) && self.half_edges[current].origin != origin {
+                self.vertices[origin].half_edge = i;
+            }
+        }
+    }
+
+    /// Flip the orientation of a half edge.
+    pub fn flip_half_edge(&mut self, index: usize) {
+        self.invalidate();
+
+        let half_edge = self.half_edges[index];
+        let prev = half_edge.next;
+        let origin = self.half_edges[prev].origin;
+
+        self.half_edges[index].next = half_edge.prev;
+        self.half_edges[index].prev = prev;
+        self.half_edges[index].origin = origin;
+    }
+
+    /// Smooth the mesh by moving each interior vertex toward the average of
+    /// its neighbors by the factor lambda for a number of iterations. The
+    /// boundary vertices are held fixed. This shrinks closed meshes.
+    pub fn smooth_laplacian(&mut self, iterations: usize, lambda: f64) {
+        let rings = (0..self.n_vertices())
+            .map(|i| (!self.is_boundary_vertex(i)).then(|| self.vertex_one_ring(i)))
+            .collect::<Vec<Option<Vec<usize>>>>();
+
+        for _ in 0..iterations {
+            self.laplacian_step(&rings, lambda);
+        }
+    }
+
+    /// Smooth the mesh using Taubin's method which alternates a shrinking
+    /// Laplacian step by the factor lambda (positive) and an inflating step
+    /// by the factor mu (negative, with |mu| > lambda) for a number of
+    /// iterations. This avoids the shrinkage of smooth_laplacian. The boundary
+    /// vertices are held fixed.
+    pub fn smooth_taubin(&mut self, iterations: usize, lambda: f64, mu: f64) {
+        let rings = (0..self.n_vertices())
+            .map(|i| (!self.is_boundary_vertex(i)).then(|| self.vertex_one_ring(i)))
+            .collect::<Vec<Option<Vec<usize>>>>();
+
+        for _ in 0..iterations {
+            self.laplacian_step(&rings, lambda);
+            self.laplacian_step(&rings, mu);
+        }
+    }
+
+    /// Move each vertex with a one-ring toward the average of the one-ring
+    /// by a factor. All vertices are updated simultaneously.
+    fn laplacian_step(&mut self, rings: &[Option<Vec<usize>>], factor: f64) {
+        let points = rings
+            .iter()
+            .enumerate()
+            .map(|(i, ring)| {
+                let point = self.vertices[i].point;
+
+                match ring {
+                    Some(ring) if !ring.is_empty() => {
+                        let average = ring
+                            .iter()
+                            .fold(Vector3::zeros(), |acc, &j| acc + self.vertices[j].point)
+                            / ring.len() as f64;
+                        point + (average - point) * factor
+                    }
+                    _ => point,
+                }
+            })
+            .collect::<Vec<Vector3>>();
+
+        for (vertex, point) in self.vertices.iter_mut().zip(points) {
+            vertex.point = point;
+        }
+    }
+
+    /// Compute the angle defect of a vertex by index as 2 pi less the sum of
+    /// the incident face corner angles.
+    pub fn angle_defect(&self, index: usize) -> f64 {
+        let point = self.vertices[index].point;
+
+        self.vertex_half_edges(index)
+            .iter()
+            .fold(2. * std::f64::consts::PI, |defect, &i| {
+                let half_edge = self.half_edges[i];
+                let p = self.vertices[self.half_edges[half_edge.prev].origin].point;
+                let r = self.vertices[self.half_edges[half_edge.next].origin].point;
+                defect - Vector3::angle(&(p - point), &(r - point))
+            })
+    }
+
+    /// Compute the sum of the angle defects of all vertices. For a closed
+    /// mesh this is 2 pi times the Euler characteristic (Gauss-Bonnet).
+    pub fn total_angle_defect(&self) -> f64 {
+        (0..self.n_vertices()).map(|i| self.angle_defect(i)).sum()
+    }
+
+    /// Compute the approximate geodesic distance from a source vertex to all
+    /// vertices as the length of the shortest path along the edges. Vertices
+    /// which cannot be reached have an infinite distance.
+    pub fn geodesic_distances(&self, source: usize) -> Vec<f64> {
+        let mut distances = vec![f64::INFINITY; self.n_vertices()];
+        let mut queue = BinaryHeap::from([Visit {
+            distance: 0.,
+            vertex: source,
+        }]);
+
+        distances[source] = 0.;
+
+        while let Some(Visit { distance, vertex }) = queue.pop() {
+            if distance > distances[vertex] {
+                continue;
+            }
+
+            let point = self.vertices[vertex].point;
+
+            for neighbor in self.vertex_one_ring(vertex) {
+                let distance = distance + (self.vertices[neighbor].point - point).mag();
+
+                if distance < distances[neighbor] {
+                    distances[neighbor] = distance;
+                    queue.push(Visit {
+                        distance,
+                        vertex: neighbor,
+                    });
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Sample well spread vertices by iteratively picking the vertex with the
+    /// largest geodesic distance to those already sampled, beginning with the
+    /// start vertex. Fewer than n vertices are returned if no other vertex
+    /// can be reached.
+    pub fn farthest_point_sample(&self, n: usize, start: usize) -> Vec<usize> {
+        if n == 0 {
+            return vec![];
+        }
+
+        let mut samples = vec![start];
+        let mut distances = self.geodesic_distances(start);
+
+        while samples.len() < n {
+            let next = (0..self.n_vertices())
+                .filter(|&i| distances[i].is_finite() && distances[i] > 0.)
+                .max_by(|&i, &j| distances[i].total_cmp(&distances[j]).then(j.cmp(&i)));
+
+            let Some(next) = next else {
+                break;
+            };
+
+            samples.push(next);
+
+            for (d, other) in distances.iter_mut().zip(self.geodesic_distances(next)) {
+                *d = d.min(other);
+            }
+        }
+
+        samples
+    }
+
+    /// Compute the genus of a closed mesh from its Euler characteristic
+    /// summed over its components. Returns None if the mesh is open.
+    pub fn genus(&self) -> Option<i64> {
+        if !self.is_closed() {
+            return None;
+        }
+
+        let n_edges = self.n_half_edges() / 2;
+        let chi = self.n_vertices() as i64 - n_edges as i64 + self.n_faces() as i64;
+        let n_components = self.components().len() as i64;
+
+        Some(n_components - chi / 2)
+    }
+
+    /// Map a closed genus 0 mesh onto the unit sphere. The vertices are
+    /// projected from the centroid onto the sphere and relaxed toward the
+    /// cotangent weighted average of their one-ring on the sphere for a
+    /// number of iterations to reduce distortion. The mesh is not modified.
+    /// This assumes the mesh is composed of strictly triangular faces.
+    pub fn parameterize_sphere(&self, iterations: usize) -> Vec<Vector3> {
+        if self.genus() != Some(0) || self.components().len() != 1 {
+            panic!("mesh must be closed and genus 0");
+        }
+
+        let n = self.n_vertices() as f64;
+        let laplacian = self.cotangent_laplacian();
+        let offsets = laplacian.row_offsets();
+        let columns = laplacian.columns();
+        let values = laplacian.values();
+
+        // Clamp the obtuse (negative) cotangent weights to keep the
+        // relaxation a convex combination of the one-ring
+        let rings = (0..self.n_vertices())
+            .map(|i| {
+                (offsets[i]..offsets[i + 1])
+                    .filter(|&k| columns[k] != i)
+                    .map(|k| (columns[k], (-values[k]).max(0.)))
+                    .collect::<Vec<(usize, f64)>>()
+            })
+            .collect::<Vec<Vec<(usize, f64)>>>();
+
+        let centroid = self
+            .vertices
+            .iter()
+            .fold(Vector3::zeros(), |c, v| c + v.point)
+            / n;
+        let mut points = self
+            .vertices
+            .iter()
+            .map(|v| (v.point - centroid).unit())
+            .collect::<Vec<Vector3>>();
+
+        for _ in 0..iterations {
+            let relaxed = rings
+                .iter()
+                .enumerate()
+                .map(|(i, ring)| {
+                    let weight = ring.iter().map(|&(_, w)| w).sum::<f64>();
+
+                    if weight < EPSILON {
+                        return points[i];
+                    }
+
+                    let average = ring
+                        .iter()
+                        .fold(Vector3::zeros(), |a, &(j, w)| a + points[j] * w)
+                        / weight;
+                    points[i] + (average - points[i]) * 0.5
+                })
+                .collect::<Vec<Vector3>>();
+
+            // Recenter to keep the vertices from drifting to one side
+            let center = relaxed.iter().fold(Vector3::zeros(), |c, &p| c + p) / n;
+            points = relaxed.iter().map(|&p| (p - center).unit()).collect();
+        }
+
+        points
+    }
+
+    /// Compute the genus of each component in the order of components.
+    /// Open components have no genus.
+    pub fn component_genus(&self) -> Vec<Option<i64>> {
+        self.split_components()
+            .iter()
+            .map(|component| component.genus())
+            .collect()
+    }
+
+    /// Calculate the Gaussian curvature at a vertex. This assumes the mesh
+    /// is composed of strictly trianglar faces and is oriented.
+    pub fn curvature(&self, index: usize) -> f64 {
+        let vertex = &self.vertices[index];
+        let mut current = vertex.half_edge;
+        let mut angle = 2. * std::f64::consts::PI;
+        let mut area = 0.;
+
+        loop {
+            let half_edge = &self.half_edges[current];
+            let next = &self.half_edges[half_edge.next];
+            let prev = &self.half_edges[half_edge.prev];
+
+            let p = self.vertices[prev.origin].point;
+            let q = vertex.point;
+            let r = self.vertices[next.origin].point;
+
+            let u = p - q;
+            let v = r - q;
+            let theta = Vector3::angle(&u, &v);
+
+            angle -= theta;
+            area += Vector3::cross(&u, &v).mag() * 0.5;
+
+            let twin = half_edge.twin.expect("mesh must be closed");
+            current = self.half_edges[twin].next;
+
+            if current == vertex.half_edge {
+                break;
+            }
+        }
+
+        3. * angle / area
+    }
+
+    /// Compute the uniform (combinatorial) graph Laplacian L = D - A where A
+    /// is the vertex adjacency matrix and D is the diagonal degree matrix.
+    pub fn uniform_laplacian(&self) -> SparseMatrix {
+        let mut triplets = vec![];
+
+        for (i, half_edge) in self.half_edges.iter().enumerate() {
+            if let Some(twin) = half_edge.twin {
+                if twin < i {
+                    continue;
+                }
+            }
+
+            let p = half_edge.origin;
+            let q = self.half_edges[half_edge.next].origin;
+
+            triplets.push((p, q, -1.));
+            triplets.push((q, p, -1.));
+            triplets.push((p, p, 1.));
+            triplets.push((q, q, 1.));
+        }
+
+        let n = self.n_vertices();
+        SparseMatrix::from_triplets(n, n, &triplets)
+    }
+
+    /// Compute the cotangent Laplacian L = D - W where W holds the cotangent
+    /// weights (cot(a) + cot(b)) / 2 of the angles opposite each edge. For
+    /// boundary edges only the single opposite angle contributes. Degenerate
+    /// (zero area) triangles have undefined cotangents and are skipped, so
+    /// they contribute no weight. This assumes the mesh is composed of
+    /// strictly triangular faces.
+    pub fn cotangent_laplacian(&self) -> SparseMatrix {
+        let mut triplets = vec![];
+
+        for i in 0..self.n_faces() {
+            let vertices = self.face_vertices(i);
+
+            if vertices.len() != 3 {
+                panic!("mesh must be triangular");
+            }
+
+            let p = self.vertices[vertices[0]].point;
+            let q = self.vertices[vertices[1]].point;
+            let r = self.vertices[vertices[2]].point;
+            let scale = (q - p).mag().max((r - q).mag()).max((p - r).mag());
+
+            if Vector3::cross(&(q - p), &(r - p)).mag() <= EPSILON * scale * scale {
+                continue;
+            }
+
+            for k in 0..3 {
+                let p = vertices[k];
+                let q = vertices[(k + 1) % 3];
+                let r = vertices[(k + 2) % 3];
+
+                // Cotangent of the angle at r opposite the edge (p, q)
+                let u = self.vertices[p].point - self.vertices[r].point;
+                let v = self.vertices[q].point - self.vertices[r].point;
+                let cot = Vector3::dot(&u, &v) / Vector3::cross(&u, &v).mag();
+                let w = 0.5 * cot;
+
+                triplets.push((p, q, -w));
+                triplets.push((q, p, -w));
+                triplets.push((p, p, w));
+                triplets.push((q, q, w));
+            }
+        }
+
+        let n = self.n_vertices();
+        SparseMatrix::from_triplets(n, n, &triplets)
+    }
+}
+
+/// Serializable view of a HeMesh as its vertices, faces, patches and vertex
+/// colors. The half edge connectivity is rebuilt by HeMesh::new on load.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HeMeshData {
+    vertices: Vec<Vertex>,
+    faces: Vec<Face>,
+    patches: Vec<Patch>,
+    #[serde(default)]
+    colors: Option<Vec<[f64; 3]>>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for HeMesh {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let data = HeMeshData {
+            vertices: self
+                .vertices
+                .iter()
+                .map(|v| Vertex::from(v.point))
+                .collect(),
+            faces: (0..self.n_faces())
+                .map(|i| Face::new(self.face_vertices(i), self.faces[i].patch))
+                .collect(),
+            patches: self
+                .patches
+                .iter()
+                .map(|p| Patch::new(p.name().to_string()))
+                .collect(),
+            colors: self.colors.clone(),
+        };
+
+        data.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for HeMesh {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<HeMesh, D::Error> {
+        use serde::de::Error;
+
+        let data = HeMeshData::deserialize(deserializer)?;
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for face in data.faces.iter() {
+            let vertices = face.vertices();
+
+            if vertices.len() < 3 {
+                return Err(D::Error::custom("face must have at least 3 vertices"));
+            }
+
+            if vertices.iter().any(|&i| i >= data.vertices.len()) {
+                return Err(D::Error::custom("face vertex index out of range"));
+            }
+
+            if (1..vertices.len()).any(|i| vertices[..i].contains(&vertices[i])) {
+                return Err(D::Error::custom("face has repeated vertex indices"));
+            }
+
+            if face.patch().is_some_and(|i| i >= data.patches.len()) {
+                return Err(D::Error::custom("face patch index out of range"));
+            }
+
+            // HeMesh::new panics if more than two faces share an edge
+            for edge in face.edges().iter() {
+                let count = edges.entry(edge.as_tuple()).or_insert(0);
+                *count += 1;
+
+                if *count > 2 {
+                    return Err(D::Error::custom("non-manifold edge"));
+                }
+            }
+        }
+
+        if data
+            .colors
+            .as_ref()
+            .is_some_and(|c| c.len() != data.vertices.len())
+        {
+            return Err(D::Error::custom("number of colors must match vertices"));
+        }
+
+        let mut mesh = HeMesh::new(&data.vertices, &data.faces, &data.patches);
+        mesh.colors = data.colors;
+
+        Ok(mesh)
+    }
+}
+
+/// Compute the i-th of n colors with evenly spaced hues
+fn distinct_color(i: usize, n: usize) -> [f64; 3] {
+    let hue = 6. * i as f64 / n.max(1) as f64;
+    let (s, v) = (0.6, 0.9);
+    let c = v * s;
+    let x = c * (1. - (hue % 2. - 1.).abs());
+    let m = v - c;
+
+    let (r, g, b) = match hue as usize {
+        0 => (c, x, 0.),
+        1 => (x, c, 0.),
+        2 => (0., c, x),
+        3 => (0., x, c),
+        4 => (x, 0., c),
+        _ => (c, 0., x),
+    };
+
+    [r + m, g + m, b + m]
+}
+
+/// Compute evenly spread unit directions on the sphere using the Fibonacci
+/// lattice.
+fn fibonacci_sphere(n: usize) -> Vec<Vector3> {
+    let golden = std::f64::consts::PI * (3. - 5_f64.sqrt());
+
+    (0..n)
+        .map(|i| {
+            let z = 1. - 2. * (i as f64 + 0.5) / n as f64;
+            let r = (1. - z * z).sqrt();
+            let theta = golden * i as f64;
+            Vector3::new(r * theta.cos(), r * theta.sin(), z)
+        })
+        .collect()
+}
+
+/// Check if two coplanar triangles overlap using the in-plane edge normals
+/// of both as separating axes. The overlap along every axis must exceed a
+/// tolerance relative to the triangle size, so triangles which only touch
+/// along an edge or at a vertex do not overlap.
+fn overlaps_coplanar_triangles(t: &Triangle, u: &Triangle, normal: &Vector3) -> bool {
+    let tp = [t.p(), t.q(), t.r()];
+    let up = [u.p(), u.q(), u.r()];
+
+    let edges = (0..3)
+        .flat_map(|i| [tp[(i + 1) % 3] - tp[i], up[(i + 1) % 3] - up[i]])
+        .collect::<Vec<Vector3>>();
+    let scale = edges.iter().map(|e| e.mag()).fold(0., f64::max);
+    let tol = EPSILON * scale;
+
+    let interval = |points: &[Vector3; 3], axis: &Vector3| {
+        points
+            .iter()
+            .map(|p| Vector3::dot(p, axis))
+            .fold((f64::MAX, f64::MIN), |(lo, hi), d| (lo.min(d), hi.max(d)))
+    };
+
+    for edge in edges.iter().filter(|e| e.mag() > tol) {
+        let axis = Vector3::cross(normal, edge).unit();
+        let (t_min, t_max) = interval(&tp, &axis);
+        let (u_min, u_max) = interval(&up, &axis);
+
+        if t_max - u_min <= tol || u_max - t_min <= tol {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Region of faces grown for the approximate convex decomposition with its
+/// distinct vertices, the planes (unit normal and point) of its faces and
+/// its concavity.
+#[derive(Debug, Clone, Default)]
+struct ConvexRegion {
+    faces: Vec<usize>,
+    vertices: Vec<usize>,
+    planes: Vec<(Vector3, Vector3)>,
+    concavity: f64,
+}
+
+/// Vertex queued by its tentative geodesic distance. The ordering is
+/// reversed so a BinaryHeap pops the closest vertex first.
+struct Visit {
+    distance: f64,
+    vertex: usize,
+}
+
+impl PartialEq for Visit {
+    fn eq(&self, other: &Visit) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Visit) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Visit) -> Ordering {
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then(other.vertex.cmp(&self.vertex))
+    }
+}
+
+/// Lazily computed topology counts of a HeMesh. These depend only on the
+/// connectivity and are reset by every method which modifies it.
+#[derive(Debug, Clone, Default)]
+struct TopologyCache {
+    n_edges: OnceLock<usize>,
+    n_boundary_edges: OnceLock<usize>,
+    n_components: OnceLock<usize>,
+}
+
+/// Weighting of the incident face normals when computing a vertex normal.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum NormalWeight {
+    Equal,
+    Area,
+    #[default]
+    Angle,
+}
+
+/// Boolean (constructive solid geometry) operation between two meshes.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    Difference,
+}
+
+/// Error describing invalid mesh connectivity or an invalid mesh operation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MeshError {
+    InvalidHalfEdge(usize),
+    InvalidFace(usize),
+    InvalidVertex(usize),
+    NonManifoldEdge(usize, usize),
+    InvalidCollapse(usize),
+    InvalidFlip(usize),
+}
+
+impl std::fmt::Display for MeshError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            MeshError::InvalidHalfEdge(i) => write!(f, "invalid half edge: {}", i),
+            MeshError::InvalidFace(i) => write!(f, "invalid face: {}", i),
+            MeshError::InvalidVertex(i) => write!(f, "invalid vertex: {}", i),
+            MeshError::NonManifoldEdge(i, j) => write!(f, "non-manifold edge: ({}, {})", i, j),
+            MeshError::InvalidCollapse(i) => write!(f, "invalid collapse: {}", i),
+            MeshError::InvalidFlip(i) => write!(f, "invalid flip: {}", i),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+/// Candidate edge collapse for decimation. Candidates are ordered so that
+/// the one with the smallest error is the greatest.
+#[derive(Debug, Copy, Clone)]
+struct Collapse {
+    error: f64,
+    half_edge: usize,
+    vertices: [usize; 2],
+    stamps: [usize; 2],
+    point: Vector3,
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Collapse) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Collapse) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    fn cmp(&self, other: &Collapse) -> Ordering {
+        other
+            .error
+            .total_cmp(&self.error)
+            .then(other.half_edge.cmp(&self.half_edge))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct HeVertex {
+    point: Vector3,
+    half_edge: usize,
+}
+
+impl HeVertex {
+    /// Get the point
+    pub fn point(&self) -> Vector3 {
+        self.point
+    }
+
+    /// Get the half edge handle
+    pub fn half_edge(&self) -> usize {
+        self.half_edge
+    }
+}
+
+impl From<&Vertex> for HeVertex {
+    fn from(vertex: &Vertex) -> HeVertex {
+        HeVertex {
+            point: (*vertex).into(),
+            half_edge: 0,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct HeFace {
+    half_edge: usize,
+    patch: Option<usize>,
+}
+
+impl HeFace {
+    /// Construct a HeFace from its half edge and patch
+    pub fn new(half_edge: usize, patch: Option<usize>) -> HeFace {
+        HeFace { half_edge, patch }
+    }
+
+    /// Get the half edge handle
+    pub fn half_edge(&self) -> usize {
+        self.half_edge
+    }
+
+    /// Get the patch handle
+    pub fn patch(&self) -> Option<usize> {
+        self.patch
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct HeHalfEdge {
+    origin: usize,
+    face: usize,
+    prev: usize,
+    next: usize,
+    twin: Option<usize>,
+}
+
+impl HeHalfEdge {
+    /// Construct a HeHalfEdge from its components
+    pub fn new(
+        origin: usize,
+        face: usize,
+        prev: usize,
+        next: usize,
+        twin: Option<usize>,
+    ) -> HeHalfEdge {
+        HeHalfEdge {
+            origin,
+            face,
+            prev,
+            next,
+            twin,
+        }
+    }
+
+    /// Get the origin handle
+    pub fn origin(&self) -> usize {
+        self.origin
+    }
+
+    /// Get the face handle
+    pub fn face(&self) -> usize {
+        self.face
+    }
+
+    /// Get the previous half edge handle
+    pub fn prev(&self) -> usize {
+        self.prev
+    }
+
+    /// Get the next half edge handle
+    pub fn next(&self) -> usize {
+        self.next
+    }
+
+    /// Get the twin half edge handle
+    pub fn twin(&self) -> Option<usize> {
+        self.twin
+    }
+
+    /// Get if the the half edge is a boundary (no twin)
+    pub fn is_boundary(&self) -> bool {
+        self.twin.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HePatch {
+    name: String,
+}
+
+impl HePatch {
+    /// Get a borrowed reference to the name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl From<&Patch> for HePatch {
+    fn from(patch: &Patch) -> HePatch {
+        HePatch {
+            name: patch.name().to_string(),
+        }
+    }
+}
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::fs::File;
-    use std::io::prelude::*;
 
     #[test]
-    fn test_from_obj() {
-        let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+    fn test_from_obj() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        assert_eq!(mesh.n_vertices(), 8);
+        assert_eq!(mesh.n_faces(), 12);
+        assert_eq!(mesh.n_half_edges(), 36);
+        assert_eq!(mesh.n_patches(), 0);
+    }
+
+    #[test]
+    fn test_from_obj_patches() {
+        let path = "tests/fixtures/box_groups.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        assert_eq!(mesh.n_vertices(), 8);
+        assert_eq!(mesh.n_faces(), 12);
+        assert_eq!(mesh.n_half_edges(), 36);
+        assert_eq!(mesh.n_patches(), 6);
+    }
+
+    #[test]
+    fn test_face_patch_name() {
+        let path = "tests/fixtures/box_groups.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.face_patch_name(0), Some("front"));
+        assert_eq!(mesh.face_patch_name(5), Some("left"));
+        assert_eq!(mesh.patch_index("left"), Some(2));
+        assert_eq!(mesh.patch_index("missing"), None);
+
+        for i in 0..mesh.n_faces() {
+            let name = mesh.face_patch_name(i).unwrap();
+            assert_eq!(mesh.patch_index(name), mesh.face(i).patch());
+        }
+
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.face_patch_name(0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_obj_nonmanifold() {
+        let path = "tests/fixtures/box_nonmanifold.obj";
+        HeMesh::from_obj(&path).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let path = "tests/fixtures/box_groups.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let json = serde_json::to_string(&mesh).unwrap();
+        let other: HeMesh = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.n_vertices(), mesh.n_vertices());
+        assert_eq!(other.n_faces(), mesh.n_faces());
+        assert_eq!(other.n_half_edges(), mesh.n_half_edges());
+        assert_eq!(other.n_patches(), mesh.n_patches());
+        assert!(other.is_closed());
+
+        for i in 0..mesh.n_faces() {
+            assert_eq!(other.face_vertices(i), mesh.face_vertices(i));
+            assert_eq!(other.face_patch_name(i), mesh.face_patch_name(i));
+        }
+
+        let json = r#"{"vertices":[{"x":0,"y":0,"z":0}],"faces":[{"vertices":[0,1,2],"patch":null}],"patches":[]}"#;
+
+        assert!(serde_json::from_str::<HeMesh>(json).is_err());
+
+        // Three faces sharing the edge (0, 1)
+        let vertices = (0..5)
+            .map(|i| r#"{"x":I,"y":0,"z":0}"#.replace('I', &i.to_string()))
+            .collect::<Vec<String>>()
+            .join(",");
+        let faces = [[0, 1, 2], [1, 0, 3], [0, 1, 4]]
+            .iter()
+            .map(|f| format!(r#"{{"vertices":{:?},"patch":null}}"#, f))
+            .collect::<Vec<String>>()
+            .join(",");
+        let json = format!(
+            r#"{{"vertices":[{}],"faces":[{}],"patches":[]}}"#,
+            vertices, faces
+        );
+
+        let error = serde_json::from_str::<HeMesh>(&json).unwrap_err();
+        assert!(error.to_string().contains("non-manifold edge"));
+
+        // Faces with too few or repeated vertices
+        for face in ["[]", "[0,1]", "[0,0,1]"] {
+            let json = format!(
+                r#"{{"vertices":[{}],"faces":[{{"vertices":{},"patch":null}}],"patches":[]}}"#,
+                vertices, face
+            );
+
+            assert!(serde_json::from_str::<HeMesh>(&json).is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_off() {
+        let path = "tests/fixtures/box.off";
+        let mesh = HeMesh::from_off(path).unwrap();
+
+        assert_eq!(mesh.n_vertices(), 8);
+        assert_eq!(mesh.n_faces(), 12);
+        assert_eq!(mesh.n_half_edges(), 36);
+        assert!(mesh.is_closed());
+    }
+
+    #[test]
+    fn test_from_off_counts() {
+        let path = "tests/fixtures/box_counts.off";
+        assert!(HeMesh::from_off(path).is_err());
+    }
+
+    #[test]
+    fn test_export_off() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let out_path = "/tmp/test_export_off.off";
+        mesh.export_off(out_path).unwrap();
+
+        let other = HeMesh::from_off(out_path).unwrap();
+
+        assert_eq!(other.n_vertices(), mesh.n_vertices());
+        assert_eq!(other.n_faces(), mesh.n_faces());
+
+        for i in 0..mesh.n_faces() {
+            assert_eq!(other.face_vertices(i), mesh.face_vertices(i));
+        }
+    }
+
+    #[test]
+    fn test_from_ply() {
+        for path in [
+            "tests/fixtures/box_ascii.ply",
+            "tests/fixtures/box_binary.ply",
+        ] {
+            let mesh = HeMesh::from_ply(path).unwrap();
+
+            assert_eq!(mesh.n_vertices(), 8);
+            assert_eq!(mesh.n_faces(), 6);
+            assert_eq!(mesh.n_half_edges(), 24);
+            assert!(mesh.is_closed());
+        }
+    }
+
+    #[test]
+    fn test_export_ply() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let out_path = "/tmp/test_export_ply.ply";
+        mesh.export_ply(out_path).unwrap();
+
+        let other = HeMesh::from_ply(out_path).unwrap();
+
+        assert_eq!(other.n_vertices(), mesh.n_vertices());
+        assert_eq!(other.n_faces(), mesh.n_faces());
+
+        for i in 0..mesh.n_faces() {
+            assert_eq!(other.face_vertices(i), mesh.face_vertices(i));
+        }
+    }
+
+    #[test]
+    fn test_from_stl() {
+        for path in [
+            "tests/fixtures/box_ascii.stl",
+            "tests/fixtures/box_binary.stl",
+        ] {
+            let mesh = HeMesh::from_stl(path).unwrap();
+
+            assert_eq!(mesh.n_vertices(), 8);
+            assert_eq!(mesh.n_faces(), 12);
+            assert_eq!(mesh.n_half_edges(), 36);
+            assert!(mesh.is_closed());
+            assert!(mesh.is_consistent());
+        }
+    }
+
+    #[test]
+    fn test_export_stl() {
+        let path = "tests/fixtures/box_quads.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        for out_path in ["/tmp/test_export_stl.stl", "/tmp/test_export_stl.stla"] {
+            mesh.export_stl(out_path).unwrap();
+
+            let other = HeMesh::from_stl(out_path).unwrap();
+
+            assert_eq!(other.n_vertices(), 8);
+            assert_eq!(other.n_faces(), 12);
+            assert!(other.is_closed());
+
+            // Binary STL stores single precision coordinates
+            for vertex in other.vertices() {
+                assert!(mesh
+                    .vertices()
+                    .iter()
+                    .any(|v| v.point().approx_eq(&vertex.point(), 1e-6)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_export_stl_degenerate() {
+        let p = Vector3::new(0., 0., 0.);
+        let q = Vector3::new(1., 0., 0.);
+        let r = Vector3::new(0., 1., 0.);
+        let polygons = vec![
+            Polygon::new(vec![p, q, r]),
+            Polygon::new(vec![p, q, q * 2.]),
+        ];
+        let mesh = HeMesh::from_polygons(&polygons);
+
+        let out_path = "/tmp/test_export_stl_degenerate.stl";
+        mesh.export_stl(out_path).unwrap();
+
+        let other = HeMesh::from_stl(out_path).unwrap();
+        assert_eq!(other.n_faces(), 1);
+    }
+
+    #[test]
+    fn test_from_stl_truncated() {
+        let path = "tests/fixtures/box_truncated.stl";
+        assert!(HeMesh::from_stl(path).is_err());
+    }
+
+    #[test]
+    fn test_from_stl_non_manifold() {
+        let path = "/tmp/test_from_stl_non_manifold.stl";
+        let contents = [
+            "solid fan",
+            "facet normal 0 0 1",
+            "outer loop",
+            "vertex 0 0 0",
+            "vertex 1 0 0",
+            "vertex 0 1 0",
+            "endloop",
+            "endfacet",
+            "facet normal 0 1 0",
+            "outer loop",
+            "vertex 0 0 0",
+            "vertex 1 0 0",
+            "vertex 0 0 1",
+            "endloop",
+            "endfacet",
+            "facet normal 0 -1 0",
+            "outer loop",
+            "vertex 1 0 0",
+            "vertex 0 0 0",
+            "vertex 0 0 -1",
+            "endloop",
+            "endfacet",
+            "endsolid fan",
+        ];
+        std::fs::write(path, contents.join("\n")).unwrap();
+
+        let error = HeMesh::from_stl(path).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("non-manifold"));
+    }
+
+    #[test]
+    fn test_export_obj() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let out_path = "/tmp/test_export_obj.obj";
+        mesh.export_obj(out_path).unwrap();
+
+        let other = HeMesh::from_obj(out_path).unwrap();
+
+        assert_eq!(other.n_vertices(), mesh.n_vertices());
+        assert_eq!(other.n_faces(), mesh.n_faces());
+
+        for (u, v) in other.vertices().iter().zip(mesh.vertices().iter()) {
+            assert!(u.point().approx_eq(&v.point(), EPSILON));
+        }
+
+        for i in 0..mesh.n_faces() {
+            assert_eq!(other.face_vertices(i), mesh.face_vertices(i));
+        }
+    }
+
+    #[test]
+    fn test_export_obj_mtl() {
+        let path = "tests/fixtures/box_groups.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let out_path = "/tmp/test_export_obj_mtl.obj";
+        mesh.export_obj_mtl(out_path).unwrap();
+
+        let obj = std::fs::read_to_string(out_path).unwrap();
+        let mtl = std::fs::read_to_string("/tmp/test_export_obj_mtl.mtl").unwrap();
+
+        assert!(obj.starts_with("mtllib test_export_obj_mtl.mtl\n"));
+        assert_eq!(obj.matches("usemtl ").count(), 6);
+        assert_eq!(mtl.matches("newmtl ").count(), 6);
+
+        let colors = mtl
+            .lines()
+            .filter(|line| line.starts_with("Kd "))
+            .collect::<HashSet<&str>>();
+
+        assert_eq!(colors.len(), 6);
+        assert_eq!(HeMesh::from_obj(out_path).unwrap().n_patches(), 6);
+    }
+
+    #[test]
+    fn test_to_obj_string() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let out_path = "/tmp/test_to_obj_string.obj";
+        mesh.export_obj(out_path).unwrap();
+
+        let contents = std::fs::read_to_string(out_path).unwrap();
+        assert_eq!(mesh.to_obj_string(), contents);
+        assert!(contents.starts_with("v -0.5 -0.5 -0.5\n"));
+    }
+
+    #[test]
+    fn test_from_obj_z_up() {
+        use crate::mesh::wavefront::CoordinateSystem;
+
+        let path = "tests/fixtures/box.obj";
+        let mut options = ObjOptions::new();
+        options.set_coordinate_system(CoordinateSystem::ZUp);
+
+        let default = HeMesh::from_obj(path).unwrap();
+        let mesh = HeMesh::from_obj_with_options(path, &options).unwrap();
+
+        assert_eq!(default.vertex(1).point(), Vector3::new(-0.5, -0.5, 0.5));
+        assert_eq!(mesh.vertex(1).point(), Vector3::new(-0.5, 0.5, 0.5));
+        assert_eq!(mesh.face_normal(4), Vector3::new(0., 0., 1.));
+        assert_eq!(default.face_normal(4), Vector3::new(0., -1., 0.));
+        assert!(mesh.is_consistent());
+    }
+
+    #[test]
+    fn test_from_obj_colors() {
+        let path = "tests/fixtures/box_colors.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.colors().unwrap().len(), 8);
+        assert_eq!(mesh.colors().unwrap()[7], [1., 1., 1.]);
+
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!(mesh.colors().is_none());
+    }
+
+    #[test]
+    fn test_export_obj_colors() {
+        let path = "tests/fixtures/box_colors.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let out_path = "/tmp/box_colors_export.obj";
+        mesh.export_obj(out_path).unwrap();
+
+        let other = HeMesh::from_obj(out_path).unwrap();
+
+        assert_eq!(other.colors(), mesh.colors());
+
+        // The colors are remapped to the extracted vertices
+        let extracted = mesh.extract_faces(&vec![2, 3]);
+        let colors = extracted.colors().unwrap();
+
+        assert_eq!(colors.len(), extracted.n_vertices());
+
+        for (i, face) in [2, 3].iter().enumerate() {
+            let old = mesh.face_vertices(*face);
+            let new = extracted.face_vertices(i);
+
+            for (&j, &k) in old.iter().zip(new.iter()) {
+                assert_eq!(colors[k], mesh.colors().unwrap()[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_export_components() {
+        let mut mesh = HeMesh::from_obj("tests/fixtures/box.obj").unwrap();
+        let other = HeMesh::from_obj("tests/fixtures/sphere.obj").unwrap();
+        mesh.merge(&other);
+
+        let directory = "/tmp/meshx_export_components";
+        std::fs::create_dir_all(directory).unwrap();
+
+        let paths = mesh.export_components(directory, "part").unwrap();
+
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0], format!("{}/part_0.obj", directory));
+        assert_eq!(HeMesh::from_obj(&paths[0]).unwrap().n_faces(), 12);
+        assert_eq!(HeMesh::from_obj(&paths[1]).unwrap().n_faces(), 96);
+    }
+
+    #[test]
+    fn test_export_obj_z_up() {
+        use crate::mesh::wavefront::CoordinateSystem;
+
+        let path = "tests/fixtures/box.obj";
+        let mut options = ObjOptions::new();
+        options.set_coordinate_system(CoordinateSystem::ZUp);
+
+        let mesh = HeMesh::from_obj_with_options(path, &options).unwrap();
+        let out_path = "/tmp/test_export_obj_z_up.obj";
+        mesh.export_obj_with_options(out_path, &options).unwrap();
+
+        let other = HeMesh::from_obj_with_options(out_path, &options).unwrap();
+        let default = HeMesh::from_obj(out_path).unwrap();
+        let expected = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(other.n_vertices(), mesh.n_vertices());
+
+        for i in 0..mesh.n_vertices() {
+            let point = mesh.vertex(i).point();
+            assert!(other.vertex(i).point().approx_eq(&point, EPSILON));
+
+            // The file is written in the original (z-up) coordinates
+            let point = expected.vertex(i).point();
+            assert!(default.vertex(i).point().approx_eq(&point, EPSILON));
+        }
+    }
+
+    #[test]
+    fn test_aabb() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        let aabb = mesh.aabb();
+
+        assert_eq!(aabb.min(), Vector3::new(-0.5, -0.5, -0.5));
+        assert_eq!(aabb.max(), Vector3::new(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn test_scale_axes() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        mesh.scale_axes(-1., 1., 1.);
+
+        assert!(mesh.validate().is_ok());
+        assert!(mesh.is_consistent());
+        assert!((mesh.volume() - 1.).abs() < EPSILON);
+
+        mesh.scale_axes(2., -3., -4.);
+
+        assert!((mesh.volume() - 24.).abs() < EPSILON);
+
+        for (i, normal) in mesh.face_normals().iter().enumerate() {
+            let vertices = mesh.face_vertices(i);
+            let centroid = vertices
+                .iter()
+                .fold(Vector3::zeros(), |acc, &j| acc + mesh.vertex(j).point());
+
+            assert!(Vector3::dot(normal, &centroid) > 0.);
+        }
+    }
+
+    #[test]
+    fn test_transform() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let offset = Vector3::new(1., -2., 3.);
+
+        mesh.translate(offset);
+
+        let aabb = mesh.aabb();
+
+        assert!((aabb.center() - offset).mag() < EPSILON);
+        assert!((aabb.halfsize() - Vector3::ones() * 0.5).mag() < EPSILON);
+
+        mesh.translate(-offset);
+        mesh.rotate(Vector3::new(0., 0., 1.), std::f64::consts::FRAC_PI_4);
+
+        let halfsize = mesh.aabb().halfsize();
+
+        assert!((halfsize[0] - 0.5 * 2_f64.sqrt()).abs() < EPSILON);
+        assert!((halfsize[2] - 0.5).abs() < EPSILON);
+        assert!((mesh.volume() - 1.).abs() < EPSILON);
+
+        mesh.scale(Vector3::new(-2., 1., 1.));
+
+        assert!(mesh.is_consistent());
+        assert!((mesh.volume() - 2.).abs() < EPSILON);
+
+        let mirror = [
+            [-1., 0., 0., 0.],
+            [0., 1., 0., 0.],
+            [0., 0., 1., 0.],
+            [0., 0., 0., 1.],
+        ];
+        mesh.transform(mirror, false);
+
+        assert!((mesh.volume() + 2.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_project_onto_plane() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        let plane = Plane::new(Vector3::new(0., 0., 1.), 0.);
+        mesh.project_onto_plane(&plane);
+
+        assert_eq!(mesh.n_vertices(), 8);
+
+        for vertex in mesh.vertices() {
+            assert_eq!(vertex.point()[2], 0.);
+        }
+    }
+
+    #[test]
+    fn test_obb() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let (a, b) = (0.5_f64, 0.3_f64);
+        let rz = Matrix3::new([
+            [a.cos(), -a.sin(), 0.],
+            [a.sin(), a.cos(), 0.],
+            [0., 0., 1.],
+        ]);
+        let rx = Matrix3::new([
+            [1., 0., 0.],
+            [0., b.cos(), -b.sin()],
+            [0., b.sin(), b.cos()],
+        ]);
+        let offset = Vector3::new(1., -2., 3.);
+
+        let polygons = (0..mesh.n_faces())
+            .map(|i| {
+                let vertices = mesh
+                    .face_vertices(i)
+                    .iter()
+                    .map(|&j| {
+                        let p = mesh.vertex(j).point() * Vector3::new(1., 2., 3.);
+                        rx * (rz * p) + offset
+                    })
+                    .collect();
+                Polygon::new(vertices)
+            })
+            .collect::<Vec<Polygon>>();
+
+        let mut other = HeMesh::from_polygons(&polygons);
+        other.merge_vertices();
+
+        let (rotation, center, halfsize) = other.obb();
+
+        assert!((center - offset).mag() < 1e-6);
+        assert!((halfsize - Vector3::new(0.5, 1., 1.5)).mag() < 1e-6);
+
+        let axis = Vector3::new(rotation[(0, 2)], rotation[(1, 2)], rotation[(2, 2)]);
+        let expected = rx * (rz * Vector3::new(0., 0., 1.));
+        assert!((Vector3::dot(&axis, &expected).abs() - 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_is_closed() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        assert!(mesh.is_closed());
+    }
+
+    #[test]
+    fn test_is_closed_open() {
+        let path = "tests/fixtures/box_open.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        assert!(!mesh.is_closed());
+    }
+
+    #[test]
+    fn test_boundary_metrics() {
+        let path = "tests/fixtures/box_open.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.n_holes(), 1);
+        assert!((mesh.total_boundary_length() - (2. + 2_f64.sqrt())).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_boundary_metrics_closed() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.n_holes(), 0);
+        assert_eq!(mesh.total_boundary_length(), 0.);
+    }
+
+    #[test]
+    fn test_simplify_boundaries() {
+        let path = "tests/fixtures/plane.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        let count = mesh.simplify_boundaries(1e-6);
+
+        assert_eq!(count, 36);
+        assert_eq!(mesh.n_vertices(), 85);
+        assert!(mesh.validate().is_ok());
+
+        let loops = mesh.boundary_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].len(), 4);
+
+        for &i in loops[0].iter() {
+            let point = mesh.vertex(i).point();
+            assert_eq!(point[0].abs(), 1.);
+            assert_eq!(point[1].abs(), 1.);
+        }
+
+        let normals = mesh.face_normals();
+        assert!(normals.iter().all(|n| n[2] > 0.));
+    }
+
+    #[test]
+    fn test_bridge() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        // Open the top of one box and the bottom of another box above it
+        let faces = |y: f64| {
+            (0..mesh.n_faces())
+                .filter(|&i| (mesh.face_normal(i).y() - y).abs() > EPSILON)
+                .collect::<Vec<usize>>()
+        };
+
+        let mut lower = mesh.extract_faces(&faces(1.));
+        let mut upper = mesh.extract_faces(&faces(-1.));
+
+        for vertex in upper.vertices.iter_mut() {
+            vertex.point += Vector3::new(0., 2., 0.);
+        }
+
+        lower.merge(&upper);
+        let loops = lower.boundary_loops();
+
+        assert_eq!(loops.len(), 2);
+        assert!(!lower.is_closed());
+
+        let mut reversed = lower.clone();
+        let loop_b = loops[1].iter().rev().copied().collect::<Vec<usize>>();
+        reversed.bridge(&loops[0], &loop_b);
+
+        assert!(reversed.is_consistent());
+        assert!((reversed.volume() - 3.).abs() < EPSILON);
+
+        lower.bridge(&loops[0], &loops[1]);
+
+        assert_eq!(lower.n_faces(), 24);
+        assert!(lower.is_closed());
+        assert!(lower.is_consistent());
+        assert_eq!(lower.validate(), Ok(()));
+        assert!((lower.volume() - 3.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_resample_boundary() {
+        let path = "tests/fixtures/box_open.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        let lengths = |mesh: &HeMesh| {
+            (0..mesh.n_half_edges())
+                .filter(|&i| mesh.half_edge(i).is_boundary())
+                .map(|i| {
+                    let half_edge = mesh.half_edge(i);
+                    let p = mesh.vertex(half_edge.origin()).point();
+                    let q = mesh
+                        .vertex(mesh.half_edge(half_edge.next()).origin())
+                        .point();
+                    (q - p).mag()
+                })
+                .collect::<Vec<f64>>()
+        };
+
+        mesh.resample_boundary(0.25);
+        let result = lengths(&mesh);
+
+        assert_eq!(result.len(), 14);
+        assert!(result.iter().all(|l| (l - 0.25).abs() < 0.25 * 0.1));
+        assert_eq!(mesh.validate(), Ok(()));
+
+        mesh.resample_boundary(0.5);
+        let result = lengths(&mesh);
+
+        assert_eq!(result.len(), 7);
+        assert!(result.iter().all(|l| (l - 0.5).abs() < 0.5 * 0.1));
+        assert_eq!(mesh.n_holes(), 1);
+        assert_eq!(mesh.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_t_junctions() {
+        let path = "tests/fixtures/box_tjunction.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.t_junctions(EPSILON), vec![8]);
+    }
+
+    #[test]
+    fn test_t_junctions_none() {
+        let path = "tests/fixtures/box_open.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!(mesh.t_junctions(EPSILON).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_t_junctions() {
+        let path = "tests/fixtures/box_tjunction.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!(!mesh.is_closed());
+        assert_eq!(mesh.resolve_t_junctions(EPSILON), 1);
+
+        assert_eq!(mesh.n_vertices(), 9);
+        assert_eq!(mesh.n_faces(), 14);
+        assert!(mesh.is_closed());
+        assert!(mesh.is_consistent());
+        assert!(mesh.t_junctions(EPSILON).is_empty());
+        assert_eq!(mesh.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_split_edge() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        let p = mesh.vertex(mesh.half_edge(0).origin()).point();
+        let q = mesh.vertex(mesh.half_edge(1).origin()).point();
+        let index = mesh.split_edge(0, (p + q) * 0.5);
+
+        assert_eq!(index, 8);
+        assert_eq!(mesh.n_faces(), 14);
+        assert_eq!(mesh.n_half_edges(), 42);
+        assert_eq!(mesh.vertex_neighbors(index).len(), 4);
+        assert!(mesh.is_closed());
+        assert!(mesh.is_consistent());
+        assert_eq!(mesh.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_flip_edge() {
+        let path = "tests/fixtures/quad.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        let diagonal = (0..mesh.n_half_edges())
+            .find(|&i| !mesh.half_edge(i).is_boundary())
+            .unwrap();
+
+        let edge = |mesh: &HeMesh, i: usize| {
+            let j = mesh.half_edge(i).origin();
+            let k = mesh.half_edge(mesh.half_edge(i).next()).origin();
+            (j.min(k), j.max(k))
+        };
+
+        assert_eq!(edge(&mesh, diagonal), (0, 2));
+        assert_eq!(mesh.flip_edge(diagonal), Ok(()));
+        assert_eq!(edge(&mesh, diagonal), (1, 3));
+        assert_eq!(mesh.n_faces(), 2);
+        assert!(mesh.is_consistent());
+        assert_eq!(mesh.validate(), Ok(()));
+
+        for i in 0..mesh.n_faces() {
+            assert!(mesh.face_normal(i).z() > 0.);
+        }
+
+        let boundary = (0..mesh.n_half_edges())
+            .find(|&i| mesh.half_edge(i).is_boundary())
+            .unwrap();
+
+        assert_eq!(
+            mesh.flip_edge(boundary),
+            Err(MeshError::InvalidFlip(boundary))
+        );
+    }
+
+    #[test]
+    fn test_merge_vertices_anisotropic() {
+        // Two strips of a 2 x 2 grid of quads where the shared column of the
+        // second strip is offset by the vector d
+        let grid = |d: Vector3| {
+            let mut vertices = vec![];
+
+            for (x, offset) in [
+                (0., Vector3::zeros()),
+                (1., Vector3::zeros()),
+                (1., d),
+                (2., d),
+            ] {
+                for y in 0..3 {
+                    let point = Vector3::new(x, y as f64, 0.) + offset;
+                    vertices.push(Vertex::from(point));
+                }
+            }
+
+            let mut faces = vec![];
+
+            for strip in [0, 6] {
+                for y in 0..2 {
+                    let i = strip + y;
+                    faces.push(Face::new(vec![i, i + 3, i + 4, i + 1], None));
+                }
+            }
+
+            HeMesh::new(&vertices, &faces, &vec![])
+        };
+
+        let tol = Vector3::new(1e-6, 1e-6, 1e-3);
+
+        let mut mesh = grid(Vector3::new(0., 0., 1e-4));
+        mesh.merge_vertices_anisotropic(tol);
+
+        assert_eq!(mesh.n_vertices(), 9);
+        assert_eq!(mesh.n_boundary_edges(), 8);
+        assert_eq!(mesh.validate(), Ok(()));
+
+        let mut mesh = grid(Vector3::new(1e-4, 0., 0.));
+        mesh.merge_vertices_anisotropic(tol);
+
+        assert_eq!(mesh.n_vertices(), 12);
+        assert_eq!(mesh.n_boundary_edges(), 12);
+    }
+
+    #[test]
+    fn test_flip_edge_duplicate() {
+        let vertices = vec![
+            Vertex::new(0., 0., 0.),
+            Vertex::new(1., 0., 0.),
+            Vertex::new(0., 1., 0.),
+            Vertex::new(0., 0., 1.),
+        ];
+
+        let faces = vec![
+            Face::new(vec![0, 2, 1], None),
+            Face::new(vec![0, 1, 3], None),
+            Face::new(vec![1, 2, 3], None),
+            Face::new(vec![0, 3, 2], None),
+        ];
+
+        let mut mesh = HeMesh::new(&vertices, &faces, &vec![]);
+
+        assert_eq!(mesh.flip_edge(0), Err(MeshError::InvalidFlip(0)));
+        assert_eq!(mesh.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_adjust_edge_lengths() {
+        let path = "tests/fixtures/sphere.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        let (splits, collapses) = mesh.adjust_edge_lengths(0.1, 0.25, 10);
+
+        assert!(splits > 0);
+        assert!(collapses > 0);
+        assert!((0..mesh.n_half_edges())
+            .map(|i| mesh.edge_length(i))
+            .all(|l| (0.1..=0.25).contains(&l)));
+
+        assert_eq!(mesh.adjust_edge_lengths(0.1, 0.25, 10), (0, 0));
+        assert!(mesh.is_closed());
+        assert!(mesh.is_consistent());
+        assert_eq!(mesh.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_delaunay_flips() {
+        let path = "tests/fixtures/quad.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.delaunay_flips(), 1);
+        assert_eq!(mesh.delaunay_flips(), 0);
+
+        let mut faces = (0..mesh.n_faces())
+            .map(|i| {
+                let mut vertices = mesh.face_vertices(i);
+                vertices.sort();
+                vertices
+            })
+            .collect::<Vec<Vec<usize>>>();
+
+        faces.sort();
+
+        assert_eq!(faces, vec![vec![0, 1, 3], vec![1, 2, 3]]);
+        assert_eq!(mesh.validate(), Ok(()));
+
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.delaunay_flips(), 0);
+    }
+
+    #[test]
+    fn test_collapse_edge() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        let p = mesh.vertex(mesh.half_edge(0).origin()).point();
+        let q = mesh.vertex(mesh.half_edge(1).origin()).point();
+        let index = mesh.collapse_edge(0).unwrap();
+
+        assert_eq!(mesh.n_vertices(), 7);
+        assert_eq!(mesh.n_faces(), 10);
+        assert_eq!(mesh.n_half_edges(), 30);
+        assert_eq!(mesh.vertex(index).point(), (p + q) * 0.5);
+        assert!(mesh.is_closed());
+        assert!(mesh.is_consistent());
+        assert_eq!(mesh.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_collapse_edge_boundary() {
+        let vertices = vec![
+            Vertex::new(0., 0., 0.),
+            Vertex::new(1., 0., 0.),
+            Vertex::new(1., 1., 0.),
+            Vertex::new(0., 1., 0.),
+        ];
+
+        let faces = vec![
+            Face::new(vec![0, 1, 2], None),
+            Face::new(vec![0, 2, 3], None),
+        ];
+
+        let mut mesh = HeMesh::new(&vertices, &faces, &vec![]);
+
+        // The diagonal joins two boundary vertices
+        let diagonal = (0..mesh.n_half_edges())
+            .find(|&i| !mesh.half_edge(i).is_boundary())
+            .unwrap();
+
+        assert_eq!(
+            mesh.collapse_edge(diagonal),
+            Err(MeshError::InvalidCollapse(diagonal))
+        );
+
+        let index = mesh.collapse_edge(0).unwrap();
+
+        assert_eq!(index, 0);
+        assert_eq!(mesh.n_vertices(), 3);
+        assert_eq!(mesh.n_faces(), 1);
+        assert_eq!(mesh.vertex(0).point(), Vector3::new(0.5, 0., 0.));
+        assert_eq!(mesh.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_collapse_edge_tetrahedron() {
+        let vertices = vec![
+            Vertex::new(0., 0., 0.),
+            Vertex::new(1., 0., 0.),
+            Vertex::new(0., 1., 0.),
+            Vertex::new(0., 0., 1.),
+        ];
+
+        let faces = vec![
+            Face::new(vec![0, 2, 1], None),
+            Face::new(vec![0, 1, 3], None),
+            Face::new(vec![1, 2, 3], None),
+            Face::new(vec![0, 3, 2], None),
+        ];
+
+        let mut mesh = HeMesh::new(&vertices, &faces, &vec![]);
+
+        assert_eq!(mesh.collapse_edge(0), Err(MeshError::InvalidCollapse(0)));
+        assert_eq!(mesh.n_faces(), 4);
+        assert_eq!(mesh.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate() {
+        let path = "tests/fixtures/box_groups.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.validate(), Ok(()));
+
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        mesh.half_edges[0].next = 0;
+
+        assert_eq!(mesh.validate(), Err(MeshError::InvalidHalfEdge(0)));
+    }
+
+    #[test]
+    fn test_overlapping_coplanar_faces() {
+        let path = "tests/fixtures/quads_stacked.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.overlapping_coplanar_faces(1e-6), vec![(0, 1)]);
+
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!(mesh.overlapping_coplanar_faces(1e-6).is_empty());
+        // Neighboring triangles which share only a vertex or an edge
+        let path = "tests/fixtures/plane.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!(mesh.overlapping_coplanar_faces(1e-6).is_empty());
+    }
+
+    #[test]
+    fn test_is_consistent() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        assert!(mesh.is_consistent());
+    }
+
+    #[test]
+    fn test_is_consistent_inverted() {
+        let path = "tests/fixtures/box_inconsistent.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        assert!(!mesh.is_consistent());
+    }
+
+    #[test]
+    fn test_vertex_neighbors() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        let neighbors = mesh.vertex_neighbors(1);
+
+        assert_eq!(neighbors.len(), 5);
+        assert_eq!(neighbors[0], 3);
+        assert_eq!(neighbors[1], 2);
+        assert_eq!(neighbors[2], 0);
+        assert_eq!(neighbors[3], 4);
+        assert_eq!(neighbors[4], 5);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_vertex_neighbors_inverted() {
+        // TODO: implement
+    }
+
+    #[test]
+    fn test_vertex_faces() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        let faces = mesh.vertex_faces(1);
+
+        assert_eq!(faces.len(), 5);
+        assert_eq!(faces[0], 10);
+        assert_eq!(faces[1], 1);
+        assert_eq!(faces[2], 0);
+        assert_eq!(faces[3], 4);
+        assert_eq!(faces[4], 5);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_vertex_faces_inverted() {
+        // TODO: implement
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_vertex_faces_open() {
+        let path = "tests/fixtures/box_open.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        mesh.vertex_faces(2);
+    }
+
+    #[test]
+    fn test_face_neighbors() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        let neighbors = mesh.face_neighbors(1);
+
+        assert_eq!(neighbors.len(), 3);
+        assert_eq!(neighbors[0], 10);
+        assert_eq!(neighbors[1], 6);
+        assert_eq!(neighbors[2], 0);
+    }
+
+    #[test]
+    fn test_face_half_edges() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        let half_edges = mesh.face_half_edges(1);
+
+        assert_eq!(half_edges.len(), 3);
+        assert_eq!(mesh.half_edge(half_edges[0]).origin, 1);
+        assert_eq!(mesh.half_edge(half_edges[1]).origin, 3);
+        assert_eq!(mesh.half_edge(half_edges[2]).origin, 2);
+    }
+
+    #[test]
+    fn test_face_normal() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        let normal = mesh.face_normal(0);
+
+        assert_eq!(normal, Vector3::new(-1., 0., 0.));
+    }
+
+    #[test]
+    fn test_contains() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!(mesh.contains(Vector3::zeros()));
+        assert!(mesh.contains(Vector3::new(0.25, -0.4, 0.1)));
+        assert!(!mesh.contains(Vector3::new(1., 0., 0.)));
+        assert!(!mesh.contains(Vector3::new(0.45, 0.45, 0.55)));
+
+        // The ray from here along the first direction passes through a corner
+        let direction = Vector3::new(0.5773, 0.5774, 0.5775).unit();
+        let point = Vector3::new(0.5, 0.5, 0.5) - direction * 0.5;
+        let ray = Ray::new(point, direction);
+
+        let hits = mesh.raycast_all(&ray);
+
+        assert_eq!(hits.len(), 2);
+        assert!((hits[0].1 - hits[1].1).abs() < EPSILON);
+        assert!(mesh.contains(point));
+    }
+
+    #[test]
+    fn test_ray_crossings() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let origin = Vector3::new(-2., 0.2, 0.1);
+        let ray = Ray::new(origin, -origin);
+
+        assert_eq!(mesh.ray_crossings(&ray), 2);
+
+        let ray = Ray::new(Vector3::zeros(), -origin);
+
+        assert_eq!(mesh.ray_crossings(&ray), 1);
+
+        let ray = Ray::new(origin, origin);
+
+        assert_eq!(mesh.ray_crossings(&ray), 0);
+
+        // Rays through the shared edges and diagonals of the triangles
+        let ray = Ray::new(Vector3::new(-2., 0., 0.), Vector3::new(1., 0., 0.));
+
+        assert_eq!(mesh.ray_crossings(&ray), 2);
+
+        let ray = Ray::new(Vector3::ones() * -2., Vector3::ones());
+
+        assert_eq!(mesh.ray_crossings(&ray), 2);
+
+        // A ray which only touches the edge at x = 0.5, z = 0.5
+        let ray = Ray::new(Vector3::new(0., 0.1, 1.), Vector3::new(1., 0., -1.));
+
+        assert_eq!(mesh.ray_crossings(&ray), 0);
+    }
+
+    #[test]
+    fn test_raycast_all() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let origin = Vector3::new(-2., 0.2, 0.1);
+        let ray = Ray::new(origin, Vector3::new(1., 0., 0.));
+        let hits = mesh.raycast_all(&ray);
+
+        assert_eq!(hits.len(), 2);
+        assert!((hits[0].1 - 1.5).abs() < EPSILON);
+        assert!((hits[1].1 - 2.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_concavity() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!(mesh.concavity() < EPSILON);
+
+        let path = "tests/fixtures/l_shape.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!((mesh.concavity() - 1.).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_approximate_convex_decomposition() {
+        let path = "tests/fixtures/l_shape.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let concavity = 0.01;
+        let pieces = mesh.approximate_convex_decomposition(4, concavity);
+
+        assert!(pieces.len() >= 2);
+        assert!(pieces.len() <= 4);
+        assert_eq!(pieces.iter().map(|p| p.n_faces()).sum::<usize>(), 20);
+
+        for piece in pieces.iter() {
+            assert!(piece.concavity() <= concavity);
+        }
+    }
+
+    #[test]
+    fn test_approximate_convex_decomposition_tolerance() {
+        let path = "tests/fixtures/l_shape.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        // Merging down to a single piece would exceed the tolerance
+        let concavity = 0.01;
+        let pieces = mesh.approximate_convex_decomposition(1, concavity);
+
+        assert!(pieces.len() >= 2);
+        assert!(pieces.iter().all(|p| p.concavity() <= concavity));
+
+        let pieces = mesh.approximate_convex_decomposition(1, 10.);
+
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].n_faces(), 20);
+    }
+
+    #[test]
+    fn test_approximate_convex_decomposition_convex() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let pieces = mesh.approximate_convex_decomposition(4, 0.01);
+
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].n_faces(), 12);
+    }
+
+    #[test]
+    fn test_vertex_quadrics() {
+        let path = "tests/fixtures/plane.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let quadrics = mesh.vertex_quadrics();
+
+        let error = |q: &[f64; 10], p: Vector3| {
+            let v = [p.x(), p.y(), p.z(), 1.];
+            let mut k = 0;
+            let mut error = 0.;
+
+            for a in 0..4 {
+                for b in a..4 {
+                    let scale = if a == b { 1. } else { 2. };
+                    error += scale * q[k] * v[a] * v[b];
+                    k += 1;
+                }
+            }
+
+            error
+        };
+
+        let index = 60;
+        let point = mesh.vertex(index).point();
+        let n = mesh.vertex_faces(index).len() as f64;
+
+        assert_eq!(quadrics.len(), mesh.n_vertices());
+        assert!(error(&quadrics[index], point).abs() < EPSILON);
+        assert!(error(&quadrics[index], point + Vector3::new(0.05, -0.03, 0.)).abs() < EPSILON);
+        assert!(
+            (error(&quadrics[index], point + Vector3::new(0., 0., 0.1)) - 0.01 * n).abs() < EPSILON
+        );
+    }
+
+    #[test]
+    fn test_decimate() {
+        let path = "tests/fixtures/sphere.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let volume = mesh.volume();
+
+        mesh.decimate(48);
+
+        assert!(mesh.n_faces() <= 48);
+        assert!(mesh.n_faces() >= 46);
+        assert!(mesh.is_closed());
+        assert!(mesh.is_consistent());
+        assert_eq!(mesh.validate(), Ok(()));
+        assert!((mesh.volume() - volume).abs() / volume < 0.2);
+    }
+
+    #[test]
+    fn test_decimate_with_preserve_boundary() {
+        let path = "tests/fixtures/box_open.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        mesh.subdivide_midpoint();
+
+        let boundary = |mesh: &HeMesh| {
+            let mut points = mesh
+                .boundary_loops()
+                .iter()
+                .flatten()
+                .map(|&i| mesh.vertex(i).point())
+                .map(|p| [p.x(), p.y(), p.z()])
+                .collect::<Vec<[f64; 3]>>();
+
+            points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            points
+        };
+
+        let expected = boundary(&mesh);
+        let n_faces = mesh.n_faces();
+        mesh.decimate_with(0, true);
+
+        assert!(mesh.n_faces() < n_faces);
+        assert_eq!(boundary(&mesh), expected);
+        assert_eq!(mesh.validate(), Ok(()));
+    }
+
+    fn boolean_boxes() -> (HeMesh, HeMesh) {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let offset = Vector3::new(0.5, 0.25, 0.125);
+        let polygons = (0..mesh.n_faces())
+            .map(|i| {
+                let vertices = mesh
+                    .face_vertices(i)
+                    .iter()
+                    .map(|&j| mesh.vertex(j).point() + offset)
+                    .collect();
+                Polygon::new(vertices)
+            })
+            .collect::<Vec<Polygon>>();
+
+        let mut other = HeMesh::from_polygons(&polygons);
+        other.merge_vertices();
+
+        (mesh, other)
+    }
+
+    #[test]
+    fn test_boolean_union() {
+        let (mesh, other) = boolean_boxes();
+        let result = mesh.boolean(&other, BooleanOp::Union);
+
+        assert_eq!(result.validate(), Ok(()));
+        assert!(result.is_closed());
+        assert!(result.is_consistent());
+        assert!((result.volume() - 1.671875).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_boolean_intersection() {
+        let (mesh, other) = boolean_boxes();
+        let result = mesh.boolean(&other, BooleanOp::Intersection);
+
+        assert!(result.is_closed());
+        assert!(result.is_consistent());
+        assert!((result.volume() - 0.328125).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_boolean_difference() {
+        let (mesh, other) = boolean_boxes();
+        let result = mesh.boolean(&other, BooleanOp::Difference);
 
-        assert_eq!(mesh.n_vertices(), 8);
-        assert_eq!(mesh.n_faces(), 12);
-        assert_eq!(mesh.n_half_edges(), 36);
-        assert_eq!(mesh.n_patches(), 0);
+        assert!(result.is_closed());
+        assert!(result.is_consistent());
+        assert!((result.volume() - 0.671875).abs() < 1e-6);
     }
 
     #[test]
-    fn test_from_obj_patches() {
-        let path = "tests/fixtures/box_groups.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+    fn test_scalar_gradient() {
+        let path = "tests/fixtures/plane.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
 
-        assert_eq!(mesh.n_vertices(), 8);
-        assert_eq!(mesh.n_faces(), 12);
-        assert_eq!(mesh.n_half_edges(), 36);
-        assert_eq!(mesh.n_patches(), 6);
+        let values = mesh
+            .vertices()
+            .iter()
+            .map(|v| 2. * v.point().x() - 0.5 * v.point().y() + 1.)
+            .collect::<Vec<f64>>();
+
+        let gradients = mesh.scalar_gradient(&values);
+
+        assert_eq!(gradients.len(), mesh.n_faces());
+
+        for gradient in gradients.iter() {
+            assert!((*gradient - Vector3::new(2., -0.5, 0.)).mag() < 1e-9);
+        }
+
+        // The gradient on a degenerate (zero area) face is zero
+        let vertices = vec![
+            Vertex::new(0., 0., 0.),
+            Vertex::new(1., 0., 0.),
+            Vertex::new(2., 0., 0.),
+            Vertex::new(1., 1., 0.),
+        ];
+        let faces = vec![
+            Face::new(vec![0, 1, 3], None),
+            Face::new(vec![1, 2, 3], None),
+            Face::new(vec![0, 2, 1], None),
+        ];
+        let mesh = HeMesh::new(&vertices, &faces, &vec![]);
+        let gradients = mesh.scalar_gradient(&[0., 1., 2., 3.]);
+
+        assert!(gradients[0].mag() > 0.);
+        assert_eq!(gradients[2], Vector3::zeros());
     }
 
     #[test]
-    #[should_panic]
-    fn test_from_obj_nonmanifold() {
-        let path = "tests/fixtures/box_nonmanifold.obj";
-        HeMesh::from_obj(&path).unwrap();
+    fn test_iso_contour() {
+        let path = "tests/fixtures/plane.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let values = mesh
+            .vertices()
+            .iter()
+            .map(|v| v.point().mag())
+            .collect::<Vec<f64>>();
+
+        let contours = mesh.iso_contour(&values, 0.5);
+
+        assert_eq!(contours.len(), 1);
+        assert!(contours[0].len() > 8);
+        assert_eq!(contours[0].first(), contours[0].last());
+
+        for point in contours[0].iter() {
+            assert!((point.mag() - 0.5).abs() < 0.05);
+        }
     }
 
     #[test]
-    fn test_export_obj() {
-        let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
-
-        let out_path = "/tmp/test_export_obj.obj";
-        mesh.export_obj(&out_path).unwrap();
+    fn test_iso_contour_open() {
+        let path = "tests/fixtures/plane.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
 
-        let mut expected_content = String::new();
-        let mut actual_content = String::new();
+        let values = mesh
+            .vertices()
+            .iter()
+            .map(|v| v.point().x())
+            .collect::<Vec<f64>>();
 
-        File::open(&path)
-            .unwrap()
-            .read_to_string(&mut expected_content)
-            .unwrap();
+        let contours = mesh.iso_contour(&values, 0.1);
 
-        File::open(&out_path)
-            .unwrap()
-            .read_to_string(&mut actual_content)
-            .unwrap();
+        assert_eq!(contours.len(), 1);
+        assert_ne!(contours[0].first(), contours[0].last());
 
-        assert_eq!(actual_content, expected_content);
+        for point in contours[0].iter() {
+            assert!((point.x() - 0.1).abs() < EPSILON);
+        }
     }
 
     #[test]
-    fn test_aabb() {
-        let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+    fn test_thickness_at() {
+        let path = "tests/fixtures/box_shell.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
 
-        let aabb = mesh.aabb();
+        for i in 0..mesh.n_faces() {
+            let thickness = mesh.thickness_at(i).unwrap();
+            assert!((thickness - 0.5).abs() < EPSILON);
+        }
 
-        assert_eq!(aabb.min(), Vector3::new(-0.5, -0.5, -0.5));
-        assert_eq!(aabb.max(), Vector3::new(0.5, 0.5, 0.5));
+        let path = "tests/fixtures/plane.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!((0..mesh.n_faces()).all(|i| mesh.thickness_at(i).is_none()));
     }
 
     #[test]
-    fn test_is_closed() {
+    fn test_boundary_loops() {
+        let path = "tests/fixtures/box_open.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let loops = mesh.boundary_loops();
+
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].len(), 3);
+
+        let mut vertices = loops[0].clone();
+        vertices.sort();
+
+        assert_eq!(vertices, vec![2, 3, 6]);
+
         let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+        let mesh = HeMesh::from_obj(path).unwrap();
 
-        assert!(mesh.is_closed());
+        assert!(mesh.boundary_loops().is_empty());
     }
 
     #[test]
-    fn test_is_closed_open() {
+    fn test_fill_holes() {
         let path = "tests/fixtures/box_open.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+        let mut mesh = HeMesh::from_obj(path).unwrap();
 
-        assert!(!mesh.is_closed());
-    }
+        assert_eq!(mesh.fill_holes(), 1);
+        assert_eq!(mesh.n_faces(), 12);
+        assert!(mesh.is_closed());
+        assert!(mesh.is_consistent());
+        assert!((mesh.volume() - 1.).abs() < EPSILON);
+        assert_eq!(mesh.fill_holes(), 0);
 
-    #[test]
-    fn test_is_consistent() {
+        // Remove the faces of two opposite sides leaving two quad holes
         let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let mut mesh = mesh.extract_faces(&(4..12).collect());
 
+        assert_eq!(mesh.fill_holes(), 2);
+        assert_eq!(mesh.n_faces(), 12);
+        assert!(mesh.is_closed());
+        assert!(mesh.is_consistent());
+        assert!((mesh.volume() - 1.).abs() < EPSILON);
+
+        // Fill a hole with two vertices which project to the same point
+        let vertices = vec![
+            Vertex::new(0., 0., 0.),
+            Vertex::new(2., 0., 0.),
+            Vertex::new(2., 2., 0.),
+            Vertex::new(1., 2., 0.5),
+            Vertex::new(1., 2., -0.5),
+            Vertex::new(0., 2., 0.),
+        ];
+        let faces = vec![Face::new((0..6).collect(), None)];
+        let mut mesh = HeMesh::new(&vertices, &faces, &vec![]);
+
+        assert_eq!(mesh.fill_holes(), 1);
+        assert_eq!(mesh.n_faces(), 5);
+        assert!(mesh.is_closed());
         assert!(mesh.is_consistent());
+
+        for i in 1..mesh.n_faces() {
+            let mut vertices = mesh.face_vertices(i);
+            vertices.sort();
+            vertices.dedup();
+            assert_eq!(vertices.len(), 3);
+        }
     }
 
     #[test]
-    fn test_is_consistent_inverted() {
-        let path = "tests/fixtures/box_inconsistent.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+    fn test_boundary_loops_by_component() {
+        let path = "tests/fixtures/box_open.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        mesh.merge(&HeMesh::from_obj("tests/fixtures/box.obj").unwrap());
+        mesh.merge(&HeMesh::from_obj("tests/fixtures/plane.obj").unwrap());
 
-        assert!(!mesh.is_consistent());
+        let groups = mesh.boundary_loops_by_component();
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].len(), 1);
+        assert_eq!(groups[0][0].len(), 3);
+        assert!(groups[1].is_empty());
+        assert_eq!(groups[2].len(), 1);
+        assert_eq!(groups[2][0].len(), 40);
+
+        assert!(groups[0][0].iter().all(|&i| i < 8));
+        assert!(groups[2][0].iter().all(|&i| i >= 16));
     }
 
     #[test]
-    fn test_vertex_neighbors() {
-        let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+    fn test_boundary_loops_by_component_bowtie() {
+        // Two triangles which only share the vertex 0
+        let vertices = (0..5)
+            .map(|i| Vertex::new(i as f64, (i * i) as f64, 0.))
+            .collect::<Vec<Vertex>>();
+        let faces = vec![
+            Face::new(vec![0, 1, 2], None),
+            Face::new(vec![0, 3, 4], None),
+        ];
+        let mesh = HeMesh::new(&vertices, &faces, &vec![]);
+
+        let loops = mesh.boundary_loops();
+        let groups = mesh.boundary_loops_by_component();
+
+        // The loop is grouped by the face of its first edge (0, 1)
+        assert_eq!(loops.len(), 1);
+        assert_eq!(&loops[0][..2], &[0, 1]);
+        assert_eq!(groups, vec![loops, vec![]]);
+    }
 
-        let neighbors = mesh.vertex_neighbors(1);
+    #[test]
+    fn test_keep_visible_shell() {
+        let path = "tests/fixtures/box_shell.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
 
-        assert_eq!(neighbors.len(), 5);
-        assert_eq!(neighbors[0], 3);
-        assert_eq!(neighbors[1], 2);
-        assert_eq!(neighbors[2], 0);
-        assert_eq!(neighbors[3], 4);
-        assert_eq!(neighbors[4], 5);
+        let shell = mesh.keep_visible_shell(64);
+
+        assert_eq!(mesh.n_faces(), 24);
+        assert_eq!(shell.n_faces(), 12);
+        assert!(shell.is_closed());
+
+        for vertex in shell.vertices() {
+            assert_eq!(vertex.point().abs(), Vector3::ones());
+        }
     }
 
     #[test]
-    #[ignore]
-    fn test_vertex_neighbors_inverted() {
-        // TODO: implement
+    fn test_vertex_normals() {
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        for (i, normal) in mesh.vertex_normals().iter().enumerate() {
+            let radial = mesh.vertex(i).point().unit();
+            assert!((normal.mag() - 1.).abs() < EPSILON);
+            assert!(Vector3::dot(normal, &radial) > 0.99);
+        }
     }
 
     #[test]
-    fn test_vertex_faces() {
-        let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+    fn test_vertex_normals_weighted() {
+        let path = "tests/fixtures/box_quads.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        mesh.orient_all(None);
 
-        let faces = mesh.vertex_faces(1);
+        let equal = mesh.vertex_normals_weighted(NormalWeight::Equal);
+        let area = mesh.vertex_normals_weighted(NormalWeight::Area);
+        let angle = mesh.vertex_normals_weighted(NormalWeight::Angle);
 
-        assert_eq!(faces.len(), 5);
-        assert_eq!(faces[0], 10);
-        assert_eq!(faces[1], 1);
-        assert_eq!(faces[2], 0);
-        assert_eq!(faces[3], 4);
-        assert_eq!(faces[4], 5);
+        for i in 0..mesh.n_vertices() {
+            assert!((equal[i] - area[i]).mag() < EPSILON);
+            assert!((equal[i] - angle[i]).mag() < EPSILON);
+            assert!((mesh.vertices[i].point.unit() - angle[i]).mag() < EPSILON);
+        }
+
+        let path = "tests/fixtures/l_shape.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let equal = mesh.vertex_normal_weighted(0, NormalWeight::Equal);
+        let area = mesh.vertex_normal_weighted(0, NormalWeight::Area);
+        let angle = mesh.vertex_normal_weighted(0, NormalWeight::Angle);
+
+        assert!((equal - area).mag() > 0.01);
+        assert!((equal - angle).mag() > 0.01);
+        assert!((area - angle).mag() > 0.01);
+        assert!((angle - Vector3::new(-1., -1., -1.).unit()).mag() < EPSILON);
+        assert_eq!(angle, mesh.vertex_normals()[0]);
     }
 
     #[test]
-    #[ignore]
-    fn test_vertex_faces_inverted() {
-        // TODO: implement
+    fn test_vertex_normals_open() {
+        let path = "tests/fixtures/plane.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        for normal in mesh.vertex_normals() {
+            assert!((normal - Vector3::new(0., 0., 1.)).mag() < EPSILON);
+        }
+
+        let path = "tests/fixtures/box_open.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let normal = mesh.vertex_normal(2);
+
+        assert!((normal - Vector3::new(-1., 0., -1.).unit()).mag() < EPSILON);
     }
 
     #[test]
-    #[should_panic]
-    fn test_vertex_faces_open() {
-        let path = "tests/fixtures/box_open.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+    fn test_triangles() {
+        let path = "tests/fixtures/box_quads.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
 
-        mesh.vertex_faces(2);
+        let triangles = mesh.triangles().collect::<Vec<(usize, Triangle)>>();
+
+        assert_eq!(triangles.len(), 12);
+        assert_eq!(triangles[0].0, 0);
+        assert_eq!(triangles[11].0, 5);
+
+        for (i, triangle) in triangles.iter() {
+            assert!((triangle.normal().unit() - mesh.face_normal(*i)).mag() < EPSILON);
+        }
     }
 
     #[test]
-    fn test_face_neighbors() {
+    fn test_area() {
         let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+        let mesh = HeMesh::from_obj(path).unwrap();
 
-        let neighbors = mesh.face_neighbors(1);
+        assert!((mesh.area() - 6.).abs() < EPSILON);
+        assert!((mesh.face_area(0) - 0.5).abs() < EPSILON);
 
-        assert_eq!(neighbors.len(), 3);
-        assert_eq!(neighbors[0], 10);
-        assert_eq!(neighbors[1], 6);
-        assert_eq!(neighbors[2], 0);
+        let path = "tests/fixtures/box_quads.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!((mesh.area() - 6.).abs() < EPSILON);
+        assert!((mesh.face_area(0) - 1.).abs() < EPSILON);
     }
 
     #[test]
-    fn test_face_half_edges() {
+    fn test_fit_plane() {
         let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+        let mesh = HeMesh::from_obj(path).unwrap();
 
-        let half_edges = mesh.face_half_edges(1);
+        let (plane, error) = mesh.fit_plane(&[2, 3]);
 
-        assert_eq!(half_edges.len(), 3);
-        assert_eq!(mesh.half_edge(half_edges[0]).origin, 1);
-        assert_eq!(mesh.half_edge(half_edges[1]).origin, 3);
-        assert_eq!(mesh.half_edge(half_edges[2]).origin, 2);
+        assert!(error < EPSILON);
+        assert!((plane.normal().x().abs() - 1.).abs() < EPSILON);
+        assert!(plane.distance(&Vector3::new(0.5, 0., 0.)).abs() < EPSILON);
     }
 
     #[test]
-    fn test_face_normal() {
-        let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+    fn test_fit_plane_sphere() {
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
 
-        let normal = mesh.face_normal(0);
+        let faces = (0..mesh.n_faces())
+            .filter(|&i| {
+                mesh.face_vertices(i)
+                    .iter()
+                    .all(|&j| mesh.vertex(j).point().z() > 0.2)
+            })
+            .collect::<Vec<usize>>();
 
-        assert_eq!(normal, Vector3::new(-1., 0., 0.));
+        let (plane, error) = mesh.fit_plane(&faces);
+
+        assert!(!faces.is_empty());
+        assert!(error > 1e-3);
+        assert!((plane.normal().z().abs() - 1.).abs() < 1e-6);
     }
 
     #[test]
@@ -1119,6 +6627,31 @@ mod test {
         assert_eq!(mesh1.n_patches(), 6);
     }
 
+    #[test]
+    fn test_split_disconnected_patches() {
+        let path = "tests/fixtures/box_groups.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let other = HeMesh::from_obj(path).unwrap();
+
+        mesh.merge(&other);
+        mesh.remove_duplicate_patches();
+        mesh.split_disconnected_patches();
+
+        assert_eq!(mesh.n_patches(), 12);
+        assert_eq!(mesh.patch(6).name(), "front.1");
+
+        for i in 0..12 {
+            let patch = mesh.face(i).patch().unwrap();
+            let other = mesh.face(i + 12).patch().unwrap();
+
+            assert_ne!(patch, other);
+            assert_eq!(
+                format!("{}.1", mesh.patch(patch).name()),
+                mesh.patch(other).name()
+            );
+        }
+    }
+
     #[test]
     fn test_extract_faces() {
         let path = "tests/fixtures/box_groups.obj";
@@ -1138,41 +6671,244 @@ mod test {
         let path = "tests/fixtures/box_groups.obj";
         let mesh1 = HeMesh::from_obj(&path).unwrap();
 
-        let patches: Vec<String> = vec!["front".to_string(), "right".to_string()];
-        let mesh2 = mesh1.extract_patches(&patches);
+        let patches: Vec<String> = vec!["front".to_string(), "right".to_string()];
+        let mesh2 = mesh1.extract_patches(&patches);
+
+        assert_eq!(mesh2.n_vertices(), 6);
+        assert_eq!(mesh2.n_faces(), 4);
+        assert_eq!(mesh2.n_half_edges(), 12);
+        assert_eq!(mesh2.n_patches(), 2);
+    }
+
+    #[test]
+    fn test_ambient_occlusion() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let occlusion = mesh.ambient_occlusion(64, 10., 0);
+
+        assert!(occlusion.iter().all(|&v| v == 0.));
+
+        let path = "tests/fixtures/l_shape.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let occlusion = mesh.ambient_occlusion(64, 10., 0);
+
+        // Vertices 3 and 9 lie on the concave edge of the L-shape while the
+        // vertices 0, 6 are convex corners far from the concave edge.
+        for i in 0..mesh.n_vertices() {
+            if i != 3 && i != 9 {
+                assert!(occlusion[i] < occlusion[3]);
+                assert!(occlusion[i] < occlusion[9]);
+            }
+        }
+
+        assert_eq!(occlusion[0], 0.);
+        assert_eq!(occlusion[6], 0.);
+
+        assert_eq!(occlusion, mesh.ambient_occlusion(64, 10., 0));
+    }
+
+    #[test]
+    fn test_centroid() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!(mesh.centroid().mag() < EPSILON);
+
+        let offset = Vector3::new(1., 0., 0.);
+        let vertices = mesh
+            .vertices()
+            .iter()
+            .map(|v| Vertex::from(v.point() + offset))
+            .collect::<Vec<Vertex>>();
+        let faces = (0..mesh.n_faces())
+            .map(|i| Face::new(mesh.face_vertices(i), None))
+            .collect::<Vec<Face>>();
+        let translated = HeMesh::new(&vertices, &faces, &vec![]);
+
+        assert!((translated.centroid() - offset).mag() < EPSILON);
+
+        // Without the bottom and top faces the open box falls back to the
+        // area-weighted face centroids
+        let open = translated.extract_faces(&(0..8).collect());
+
+        assert!(!open.is_closed());
+        assert!((open.centroid() - offset).mag() < EPSILON);
+    }
+
+    #[test]
+    fn test_volume() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!((mesh.volume() - 1.).abs() < EPSILON);
+
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let expected = 4. / 3. * std::f64::consts::PI * 0.5_f64.powi(3);
+
+        // The coarse tessellation is inscribed in the sphere and so encloses
+        // less volume than the analytic sphere.
+        assert!(mesh.volume() < expected);
+        assert!((mesh.volume() - expected).abs() / expected < 0.15);
+    }
+
+    #[test]
+    fn test_orient_all() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        let path = "tests/fixtures/sphere.obj";
+        let sphere = HeMesh::from_obj(path).unwrap();
+
+        (0..mesh.n_faces()).for_each(|i| mesh.flip_face(i));
+        mesh.merge(&sphere);
+        mesh.flip_face(20);
+
+        mesh.orient_all(None);
+
+        assert!(mesh.is_consistent());
+
+        for component in mesh.components() {
+            assert!(mesh.component_volume(&component) > 0.);
+        }
+    }
+
+    #[test]
+    fn test_orient_all_open() {
+        let path = "tests/fixtures/plane.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        for z in [-10., 10.] {
+            mesh.orient_all(Some(Vector3::new(0., 0., z)));
+
+            for normal in mesh.face_normals() {
+                assert!(normal[2] * z > 0.);
+            }
+        }
+    }
+
+    #[test]
+    fn test_components() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(&path).unwrap();
+
+        let components = mesh.components();
+
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0], (0..mesh.n_faces()).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_components_multi() {
+        let path = "tests/fixtures/box.obj";
+        let mesh1 = HeMesh::from_obj(path).unwrap();
+
+        let path = "tests/fixtures/sphere.obj";
+        let mesh2 = HeMesh::from_obj(path).unwrap();
+
+        let mut mesh3 = mesh1.clone();
+        mesh3.merge(&mesh2);
+        let components = mesh3.components();
+
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0], (0..12).collect::<Vec<usize>>());
+        assert_eq!(components[1], (12..108).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_split_components() {
+        let path = "tests/fixtures/box_groups.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        let path = "tests/fixtures/sphere.obj";
+        mesh.merge(&HeMesh::from_obj(path).unwrap());
+
+        let meshes = mesh.split_components();
+
+        assert_eq!(meshes.len(), 2);
+        assert_eq!(meshes[0].n_faces(), 12);
+        assert_eq!(meshes[0].n_vertices(), 8);
+        assert_eq!(meshes[0].n_patches(), 6);
+        assert_eq!(meshes[0].face_patch_name(0), mesh.face_patch_name(0));
+        assert_eq!(meshes[1].n_faces(), 96);
+        assert_eq!(meshes[1].n_patches(), mesh.n_patches() - 6);
+        assert!(meshes.iter().all(|m| m.is_closed()));
+    }
+
+    #[test]
+    fn test_components_union_find() {
+        for path in [
+            "tests/fixtures/box.obj",
+            "tests/fixtures/box_split.obj",
+            "tests/fixtures/box_shell.obj",
+            "tests/fixtures/sphere.obj",
+        ] {
+            let mesh = HeMesh::from_obj(path).unwrap();
+            assert_eq!(mesh.components_union_find(), mesh.components());
+        }
+
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let other = HeMesh::from_obj("tests/fixtures/torus.obj").unwrap();
+        mesh.merge(&other);
+        mesh.merge(&HeMesh::from_obj(path).unwrap());
 
-        assert_eq!(mesh2.n_vertices(), 6);
-        assert_eq!(mesh2.n_faces(), 4);
-        assert_eq!(mesh2.n_half_edges(), 12);
-        assert_eq!(mesh2.n_patches(), 2);
+        assert_eq!(mesh.components_union_find().len(), 3);
+        assert_eq!(mesh.components_union_find(), mesh.components());
     }
 
     #[test]
-    fn test_components() {
+    fn test_components_ordering() {
         let path = "tests/fixtures/box.obj";
-        let mesh = HeMesh::from_obj(&path).unwrap();
+        let mut mesh = HeMesh::from_obj(path).unwrap();
 
-        let components = mesh.components();
+        let path = "tests/fixtures/sphere.obj";
+        mesh.merge(&HeMesh::from_obj(path).unwrap());
 
-        assert_eq!(components.len(), 1);
-        assert_eq!(components[0].len(), mesh.n_faces());
+        // Reverse the face order so the traversal of each component starts
+        // from a different face.
+        let n = mesh.n_faces();
+        let order = (0..n).rev().collect::<Vec<usize>>();
+
+        let vertices = mesh
+            .vertices()
+            .iter()
+            .map(|v| Vertex::from(v.point()))
+            .collect::<Vec<Vertex>>();
+
+        let faces = order
+            .iter()
+            .map(|&i| Face::new(mesh.face_vertices(i), None))
+            .collect::<Vec<Face>>();
+
+        let other = HeMesh::new(&vertices, &faces, &vec![]);
+        let components = other.components();
+
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0], (0..96).collect::<Vec<usize>>());
+        assert_eq!(components[1], (96..108).collect::<Vec<usize>>());
+
+        for component in components.iter() {
+            let mut original = component.iter().map(|&i| order[i]).collect::<Vec<usize>>();
+            original.sort();
+
+            assert!(mesh.components().contains(&original));
+        }
     }
 
     #[test]
-    fn test_components_multi() {
+    fn test_flip_face() {
         let path = "tests/fixtures/box.obj";
-        let mesh1 = HeMesh::from_obj(path).unwrap();
+        let mut mesh = HeMesh::from_obj(path).unwrap();
 
-        let path = "tests/fixtures/sphere.obj";
-        let mesh2 = HeMesh::from_obj(path).unwrap();
+        mesh.flip_face(0);
 
-        let mut mesh3 = mesh1.clone();
-        mesh3.merge(&mesh2);
-        let components = mesh3.components();
+        assert_eq!(mesh.face_vertices(0), vec![1, 0, 2]);
 
-        assert_eq!(components.len(), 2);
-        assert_eq!(components[0].len(), mesh1.n_faces());
-        assert_eq!(components[1].len(), mesh2.n_faces());
+        for i in 0..mesh.n_vertices() {
+            let half_edge = mesh.vertex(i).half_edge();
+            assert_eq!(mesh.half_edge(half_edge).origin(), i);
+        }
     }
 
     #[test]
@@ -1188,6 +6924,30 @@ mod test {
         assert_eq!(count, 3);
     }
 
+    #[test]
+    fn test_orient_from() {
+        let path = "tests/fixtures/box_inconsistent.obj";
+        let original = HeMesh::from_obj(path).unwrap();
+
+        for seed in 0..original.n_faces() {
+            let normal = original.face_normal(seed);
+
+            let mut mesh = original.clone();
+            let count = mesh.orient_from(seed, true);
+
+            assert!(mesh.is_consistent());
+            assert!(count == 3 || count == 9);
+            assert_eq!(mesh.face_vertices(seed), original.face_vertices(seed));
+
+            let mut mesh = original.clone();
+            let count = mesh.orient_from(seed, false);
+
+            assert!(mesh.is_consistent());
+            assert!(count == 3 || count == 9);
+            assert!((mesh.face_normal(seed) + normal).mag() < EPSILON);
+        }
+    }
+
     #[test]
     fn test_orient_consistent() {
         let path = "tests/fixtures/box.obj";
@@ -1223,6 +6983,80 @@ mod test {
         assert_eq!(features.len(), 12);
     }
 
+    #[test]
+    fn test_nearest_feature_edge() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let angle = 30. * std::f64::consts::PI / 180.;
+        let point = Vector3::new(0.6, 0.55, 0.1);
+        let (i, j, closest) = mesh.nearest_feature_edge(&point, angle).unwrap();
+
+        let p = mesh.half_edge(i).origin();
+        let q = mesh.half_edge(j).origin();
+
+        assert_eq!((p.min(q), p.max(q)), (6, 7));
+        assert!((closest - Vector3::new(0.5, 0.5, 0.1)).mag() < EPSILON);
+    }
+
+    #[test]
+    fn test_closest_point() {
+        let on_face = |mesh: &HeMesh, index: usize, point: Vector3| {
+            mesh.face_triangles(index)
+                .iter()
+                .any(|t| (t.closest_point(point) - point).mag() < EPSILON)
+        };
+
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let (closest, index) = mesh.closest_point(Vector3::new(0.2, 1.5, -0.1));
+
+        assert!((closest - Vector3::new(0.2, 0.5, -0.1)).mag() < EPSILON);
+        assert!(on_face(&mesh, index, closest));
+
+        // Equidistant from the three faces at the corner
+        let query = Vector3::new(0.3, 0.3, 0.3);
+        let (closest, index) = mesh.closest_point(query);
+
+        assert!(((closest - query).mag() - 0.2).abs() < EPSILON);
+        assert!(on_face(&mesh, index, closest));
+
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let query = Vector3::new(2., 0.1, -0.3);
+        let (closest, index) = mesh.closest_point(query);
+
+        let distance = mesh
+            .triangles()
+            .map(|(_, t)| (t.closest_point(query) - query).mag())
+            .fold(f64::INFINITY, f64::min);
+
+        assert!(((closest - query).mag() - distance).abs() < EPSILON);
+        assert!(on_face(&mesh, index, closest));
+
+        // Reuse a single octree for many queries
+        let octree = mesh.face_octree();
+
+        for query in fibonacci_sphere(16) {
+            let query = query * 1.5;
+            assert_eq!(
+                mesh.closest_point_with(&octree, query),
+                mesh.closest_point(query)
+            );
+        }
+    }
+
+    #[test]
+    fn test_nearest_feature_edge_none() {
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let angle = 60. * std::f64::consts::PI / 180.;
+        let point = Vector3::zeros();
+
+        assert!(mesh.nearest_feature_edge(&point, angle).is_none());
+    }
+
     #[test]
     fn test_split_by_features_box_triangles() {
         let path = "tests/fixtures/box.obj";
@@ -1268,6 +7102,324 @@ mod test {
         assert_eq!(components.len(), 1);
     }
 
+    #[test]
+    fn test_segment_by_scalar() {
+        let path = "tests/fixtures/plane.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let values: Vec<f64> = mesh.vertices().iter().map(|v| v.point().x()).collect();
+        let segments = mesh.segment_by_scalar(&values, 0.1);
+
+        assert_eq!(segments.len(), mesh.n_faces());
+        assert_eq!(*segments.iter().max().unwrap(), 1);
+
+        for (i, &segment) in segments.iter().enumerate() {
+            let vertices = mesh.face_vertices(i);
+
+            if vertices.iter().all(|&v| values[v] < 0.1) {
+                assert_eq!(segment, segments[0]);
+            }
+
+            if vertices.iter().all(|&v| values[v] >= 0.1) {
+                assert_ne!(segment, segments[0]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_smooth_laplacian() {
+        let path = "tests/fixtures/sphere.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let halfsize = mesh.aabb().halfsize();
+
+        mesh.smooth_laplacian(5, 0.5);
+
+        assert_eq!(mesh.n_vertices(), 50);
+
+        for k in 0..3 {
+            assert!(mesh.aabb().halfsize()[k] < halfsize[k]);
+            assert!(mesh.aabb().halfsize()[k] > 0.5 * halfsize[k]);
+        }
+    }
+
+    #[test]
+    fn test_smooth_taubin() {
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let volume = mesh.volume();
+
+        let mut laplacian = mesh.clone();
+        laplacian.smooth_laplacian(10, 0.33);
+
+        let mut taubin = mesh.clone();
+        taubin.smooth_taubin(10, 0.33, -0.35);
+
+        let laplacian_change = (laplacian.volume() - volume).abs() / volume;
+        let taubin_change = (taubin.volume() - volume).abs() / volume;
+
+        assert!(taubin_change < 0.05);
+        assert!(laplacian_change > 0.5);
+    }
+
+    #[test]
+    fn test_smooth_laplacian_open() {
+        let path = "tests/fixtures/plane.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let boundary = mesh.boundary_loops()[0].clone();
+        let points = mesh
+            .vertices()
+            .iter()
+            .map(|v| v.point())
+            .collect::<Vec<Vector3>>();
+
+        mesh.vertices[60].point += Vector3::new(0., 0., 1.);
+        mesh.smooth_laplacian(10, 0.5);
+
+        assert!(mesh.vertex(60).point()[2] < 0.5);
+
+        for i in boundary {
+            assert_eq!(mesh.vertex(i).point(), points[i]);
+        }
+    }
+
+    #[test]
+    fn test_angle_defect() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let pi = std::f64::consts::PI;
+
+        assert!((mesh.angle_defect(0) - pi * 0.5).abs() < EPSILON);
+        assert!((mesh.total_angle_defect() - 4. * pi).abs() < EPSILON);
+
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert!((mesh.total_angle_defect() - 4. * pi).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_topology_counts() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.n_edges(), 18);
+        assert_eq!(mesh.n_boundary_edges(), 0);
+        assert_eq!(mesh.n_components(), 1);
+
+        mesh.remove_face(0);
+
+        assert_eq!(mesh.n_faces(), 11);
+        assert_eq!(mesh.n_edges(), 18);
+        assert_eq!(mesh.n_boundary_edges(), 3);
+        assert_eq!(mesh.n_components(), 1);
+
+        let path = "tests/fixtures/box_split.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let n_boundary_edges = mesh.n_boundary_edges();
+        let n_edges = mesh.n_edges();
+
+        assert!(n_boundary_edges > 0);
+
+        mesh.merge_vertices();
+
+        assert_eq!(mesh.n_boundary_edges(), 0);
+        assert_eq!(mesh.n_edges(), n_edges - n_boundary_edges / 2);
+        assert_eq!(mesh.n_components(), 1);
+    }
+
+    #[test]
+    fn test_geodesic_distances() {
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let distances = mesh.geodesic_distances(0);
+        let half_circumference = std::f64::consts::PI * 0.5;
+
+        // The shortest path along the edges between the poles is a chain of
+        // chords which is slightly shorter than the arc of the sphere
+        assert_eq!(distances[0], 0.);
+        assert!(distances[1] < half_circumference);
+        assert!(distances[1] > half_circumference * 0.98);
+    }
+
+    #[test]
+    fn test_farthest_point_sample() {
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let samples = mesh.farthest_point_sample(6, 0);
+
+        assert_eq!(samples.len(), 6);
+        assert_eq!(samples[0], 0);
+        assert_eq!(samples[1], 1);
+
+        for &i in samples.iter() {
+            let distances = mesh.geodesic_distances(i);
+
+            for &j in samples.iter().filter(|&&j| j != i) {
+                assert!(distances[j] > 0.6);
+            }
+        }
+
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.farthest_point_sample(20, 0).len(), 8);
+        assert!(mesh.farthest_point_sample(0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_dual() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let dual = mesh.dual();
+
+        assert_eq!(dual.n_vertices(), mesh.n_faces());
+        assert_eq!(dual.n_faces(), mesh.n_vertices());
+        assert!(dual.is_closed());
+        assert!(dual.volume() > 0.);
+        assert_eq!(dual.validate(), Ok(()));
+        assert_eq!(dual.dual().n_faces(), mesh.n_faces());
+    }
+
+    #[test]
+    fn test_raycast_bary() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let ray = Ray::new(Vector3::new(0.1, 0.2, 5.), Vector3::new(0., 0., -1.));
+        let (face, bary, t) = mesh.raycast_bary(&ray).unwrap();
+        let triangle = mesh.face_triangles(face)[0];
+        let point = triangle.point_from_barycentric(bary);
+
+        assert!((t - 4.5).abs() < EPSILON);
+        assert!((bary[0] + bary[1] + bary[2] - 1.).abs() < EPSILON);
+        assert!((point - Vector3::new(0.1, 0.2, 0.5)).mag() < EPSILON);
+        assert!(mesh.face_normal(face)[2] > 0.);
+
+        let ray = Ray::new(Vector3::new(2., 0., 0.), Vector3::new(0., 1., 0.));
+
+        assert_eq!(mesh.raycast_bary(&ray), None);
+    }
+
+    #[test]
+    fn test_genus() {
+        let path = "tests/fixtures/torus.obj";
+        let torus = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(torus.genus(), Some(1));
+
+        let path = "tests/fixtures/box_open.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.genus(), None);
+
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        mesh.merge(&torus);
+
+        assert_eq!(mesh.genus(), Some(1));
+        assert_eq!(mesh.component_genus(), vec![Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn test_largest_component() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let torus = HeMesh::from_obj("tests/fixtures/torus.obj").unwrap();
+        mesh.merge(&torus);
+
+        let largest = mesh.largest_component();
+
+        assert_eq!(largest.n_faces(), 64);
+        assert_eq!(largest.genus(), Some(1));
+
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let mut other = mesh.clone();
+        other.scale_axes(2., 2., 2.);
+        mesh.merge(&other);
+
+        let largest = mesh.largest_component();
+
+        assert_eq!(largest.n_faces(), 12);
+        assert_eq!(largest.aabb().halfsize(), Vector3::ones() * 0.5);
+    }
+
+    #[test]
+    fn test_keep_largest_components() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let torus = HeMesh::from_obj("tests/fixtures/torus.obj").unwrap();
+        mesh.merge(&torus);
+        mesh.merge(&HeMesh::from_obj(path).unwrap().extract_faces(&vec![0]));
+
+        let kept = mesh.keep_largest_components(2);
+
+        assert_eq!(kept.n_faces(), 76);
+        assert_eq!(kept.n_components(), 2);
+        assert_eq!(kept.component_genus(), vec![Some(0), Some(1)]);
+
+        assert_eq!(mesh.keep_largest_components(5).n_faces(), 77);
+        assert_eq!(mesh.keep_largest_components(0).n_faces(), 0);
+    }
+
+    #[test]
+    fn test_smoothed_vertex_normals() {
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let variation = |normals: &Vec<Vector3>| {
+            (0..mesh.n_half_edges())
+                .map(|i| {
+                    let half_edge = mesh.half_edge(i);
+                    let j = mesh.half_edge(half_edge.next()).origin();
+                    Vector3::angle(&normals[half_edge.origin()], &normals[j])
+                })
+                .sum::<f64>()
+        };
+
+        let original = variation(&mesh.vertex_normals());
+        let smoothed = mesh.smoothed_vertex_normals(3);
+
+        assert_eq!(mesh.smoothed_vertex_normals(0), mesh.vertex_normals());
+        assert!(variation(&smoothed) < original);
+        assert!(smoothed.iter().all(|n| (n.mag() - 1.).abs() < EPSILON));
+    }
+
+    #[test]
+    fn test_parameterize_sphere() {
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let points = mesh.parameterize_sphere(10);
+
+        for (i, point) in points.iter().enumerate() {
+            let expected = mesh.vertex(i).point().unit();
+            assert!((point.mag() - 1.).abs() < EPSILON);
+            assert!((*point - expected).mag() < 0.02);
+        }
+
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        let points = mesh.parameterize_sphere(10);
+
+        let center = points.iter().fold(Vector3::zeros(), |c, &p| c + p) / points.len() as f64;
+        assert!(center.mag() < 1e-6);
+
+        for i in 0..points.len() {
+            assert!((points[i].mag() - 1.).abs() < EPSILON);
+
+            for j in (i + 1)..points.len() {
+                assert!((points[i] - points[j]).mag() > 0.5);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_parameterize_sphere_open() {
+        let path = "tests/fixtures/box_open.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+        mesh.parameterize_sphere(1);
+    }
+
     #[test]
     fn test_curvature_sphere() {
         let path = "tests/fixtures/sphere.obj";
@@ -1298,4 +7450,142 @@ mod test {
         assert_eq!(mesh.n_faces(), 59);
         assert_eq!(mesh.components().len(), 1);
     }
+
+    #[test]
+    fn test_subdivide_midpoint() {
+        let path = "tests/fixtures/box_groups.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let patches = (0..mesh.n_faces())
+            .map(|i| mesh.face(i).patch())
+            .collect::<Vec<Option<usize>>>();
+        let volume = mesh.volume();
+
+        mesh.subdivide_midpoint();
+
+        assert_eq!(mesh.n_faces(), 48);
+        assert_eq!(mesh.n_patches(), 6);
+        assert!(mesh.is_closed());
+        assert!((mesh.volume() - volume).abs() < EPSILON);
+
+        for i in 0..mesh.n_faces() {
+            assert_eq!(mesh.face(i).patch(), patches[i / 4]);
+        }
+    }
+
+    #[test]
+    fn test_subdivide_midpoint_box() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+        let n_edges = mesh.n_half_edges() / 2;
+
+        mesh.subdivide_midpoint();
+
+        assert_eq!(mesh.n_faces(), 48);
+        assert_eq!(mesh.n_vertices(), 8 + n_edges);
+        assert_eq!(mesh.n_vertices(), 26);
+        assert!(mesh.validate().is_ok());
+    }
+
+    #[test]
+    fn test_split_vertices_by_crease() {
+        let path = "tests/fixtures/box.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        mesh.split_vertices_by_crease(30_f64.to_radians());
+
+        assert_eq!(mesh.n_vertices(), 24);
+        assert_eq!(mesh.n_faces(), 12);
+        assert!(!mesh.is_closed());
+
+        let normals = mesh.face_normals();
+        let mut vertex_normals = vec![None; mesh.n_vertices()];
+
+        for (i, normal) in normals.iter().enumerate() {
+            for j in mesh.face_vertices(i) {
+                let expected = *vertex_normals[j].get_or_insert(*normal);
+                assert!((expected - *normal).mag() < EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_vertices_by_normal() {
+        let path = "tests/fixtures/box_split.obj";
+        let mut mesh = HeMesh::from_obj(path).unwrap();
+
+        assert_eq!(mesh.n_vertices(), 26);
+        assert_eq!(mesh.components().len(), 7);
+
+        let angle = 30. * std::f64::consts::PI / 180.;
+        mesh.merge_vertices_by_normal(EPSILON, angle);
+
+        assert_eq!(mesh.n_vertices(), 24);
+        assert_eq!(mesh.n_faces(), 12);
+        assert_eq!(mesh.components().len(), 6);
+        assert!(!mesh.is_closed());
+    }
+
+    #[test]
+    fn test_uniform_laplacian() {
+        let path = "tests/fixtures/box.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let laplacian = mesh.uniform_laplacian();
+
+        assert_eq!(laplacian.n_rows(), 8);
+        assert_eq!(laplacian.n_cols(), 8);
+        assert_eq!(laplacian.get(1, 1), 5.);
+        assert_eq!(laplacian.get(1, 3), -1.);
+        assert_eq!(laplacian.get(1, 7), 0.);
+
+        for (i, j, value) in laplacian.triplets() {
+            assert_eq!(laplacian.get(j, i), value);
+        }
+    }
+
+    #[test]
+    fn test_cotangent_laplacian() {
+        let path = "tests/fixtures/sphere.obj";
+        let mesh = HeMesh::from_obj(path).unwrap();
+
+        let laplacian = mesh.cotangent_laplacian();
+        let mut sums = vec![0.; mesh.n_vertices()];
+
+        for (i, j, value) in laplacian.triplets() {
+            sums[i] += value;
+
+            let error = (laplacian.get(j, i) - value).abs();
+            assert!(error <= 1e-12);
+        }
+
+        for sum in sums.iter() {
+            assert!(sum.abs() <= 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cotangent_laplacian_degenerate() {
+        let vertices = vec![
+            Vertex::new(0., 0., 0.),
+            Vertex::new(1., 0., 0.),
+            Vertex::new(2., 0., 0.),
+            Vertex::new(1., 1., 0.),
+        ];
+        let faces = vec![
+            Face::new(vec![0, 1, 3], None),
+            Face::new(vec![1, 2, 3], None),
+            Face::new(vec![0, 2, 1], None),
+        ];
+        let mesh = HeMesh::new(&vertices, &faces, &vec![]);
+        let other = HeMesh::new(&vertices, &faces[..2].to_vec(), &vec![]);
+
+        // The collinear face is skipped rather than producing inf or NaN
+        let laplacian = mesh.cotangent_laplacian();
+        let expected = other.cotangent_laplacian();
+
+        for (i, j, value) in laplacian.triplets() {
+            assert!(value.is_finite());
+            assert!((expected.get(i, j) - value).abs() <= 1e-12);
+        }
+    }
 }
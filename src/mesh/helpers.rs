@@ -1,5 +1,6 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
+use crate::geometry::{Line, Vector3, EPSILON};
 use crate::mesh::Face;
 
 /// Given a list of Faces, merge faces sharing at least one edge. For
@@ -67,9 +68,225 @@ pub fn merge_faces(faces: &Vec<Face>) -> Face {
     Face::new(vertices, patch)
 }
 
+/// Check that no edge is shared by more than two faces before constructing a
+/// mesh from them. This returns the sorted, non-manifold edges (as sorted
+/// pairs of vertex indices) on failure.
+pub fn check_manifold(faces: &[Face]) -> Result<(), Vec<(usize, usize)>> {
+    let mut counts: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+
+    for face in faces.iter() {
+        for edge in face.edges() {
+            let p = edge.p();
+            let q = edge.q();
+            *counts.entry((p.min(q), p.max(q))).or_default() += 1;
+        }
+    }
+
+    let edges = counts
+        .into_iter()
+        .filter(|&(_, count)| count > 2)
+        .map(|(edge, _)| edge)
+        .collect::<Vec<(usize, usize)>>();
+
+    if edges.is_empty() {
+        Ok(())
+    } else {
+        Err(edges)
+    }
+}
+
+/// Compute the outward oriented triangles of the convex hull of a set of
+/// points using an incremental construction. This returns no triangles if
+/// the points are coplanar.
+pub fn convex_hull(points: &[Vector3]) -> Vec<[usize; 3]> {
+    if points.len() < 4 {
+        return vec![];
+    }
+
+    let distance = |face: &[usize; 3], v: Vector3| {
+        let [a, b, c] = face.map(|i| points[i]);
+        let normal = Vector3::cross(&(b - a), &(c - a)).unit();
+        Vector3::dot(&normal, &(v - a))
+    };
+
+    // Construct the initial tetrahedron from the extreme points
+    let i0 = (0..points.len())
+        .min_by(|&i, &j| points[i].x().total_cmp(&points[j].x()))
+        .unwrap();
+
+    let i1 = (0..points.len())
+        .max_by(|&i, &j| {
+            (points[i] - points[i0])
+                .mag()
+                .total_cmp(&(points[j] - points[i0]).mag())
+        })
+        .unwrap();
+
+    let axis = (points[i1] - points[i0]).unit();
+    let line_distance = |i: usize| Vector3::cross(&axis, &(points[i] - points[i0])).mag();
+    let i2 = (0..points.len())
+        .max_by(|&i, &j| line_distance(i).total_cmp(&line_distance(j)))
+        .unwrap();
+
+    if line_distance(i2) < EPSILON {
+        return vec![];
+    }
+
+    let base = [i0, i1, i2];
+    let i3 = (0..points.len())
+        .max_by(|&i, &j| {
+            distance(&base, points[i])
+                .abs()
+                .total_cmp(&distance(&base, points[j]).abs())
+        })
+        .unwrap();
+
+    if distance(&base, points[i3]).abs() < EPSILON {
+        return vec![];
+    }
+
+    let mut faces = if distance(&base, points[i3]) < 0. {
+        vec![[i0, i1, i2], [i0, i3, i1], [i1, i3, i2], [i2, i3, i0]]
+    } else {
+        vec![[i0, i2, i1], [i0, i1, i3], [i1, i2, i3], [i2, i0, i3]]
+    };
+
+    // Add each point outside of the hull by replacing the faces it can see
+    // with a fan connecting the point to the horizon edges.
+    for (i, &point) in points.iter().enumerate() {
+        let visible = faces
+            .iter()
+            .map(|face| distance(face, point) > EPSILON)
+            .collect::<Vec<bool>>();
+
+        if !visible.contains(&true) {
+            continue;
+        }
+
+        let edges = faces
+            .iter()
+            .zip(visible.iter())
+            .filter(|(_, &v)| v)
+            .flat_map(|(f, _)| [(f[0], f[1]), (f[1], f[2]), (f[2], f[0])])
+            .collect::<HashSet<(usize, usize)>>();
+
+        let mut next = faces
+            .iter()
+            .zip(visible.iter())
+            .filter(|(_, &v)| !v)
+            .map(|(f, _)| *f)
+            .collect::<Vec<[usize; 3]>>();
+
+        for &(a, b) in edges.iter() {
+            if !edges.contains(&(b, a)) {
+                next.push([a, b, i]);
+            }
+        }
+
+        faces = next;
+    }
+
+    faces.sort();
+    faces
+}
+
+/// Simplify an open polyline using the Douglas-Peucker algorithm. This
+/// returns whether each point is kept such that the removed points are within
+/// the tolerance of the simplified polyline. The end points are always kept.
+pub fn douglas_peucker(points: &[Vector3], tol: f64) -> Vec<bool> {
+    let n = points.len();
+    let mut keep = vec![false; n];
+
+    if n == 0 {
+        return keep;
+    }
+
+    keep[0] = true;
+    keep[n - 1] = true;
+
+    let mut queue = vec![(0, n - 1)];
+
+    while let Some((i, j)) = queue.pop() {
+        let line = Line::new(points[i], points[j]);
+        let farthest = (i + 1..j)
+            .map(|k| (k, (line.closest_point(points[k]) - points[k]).mag()))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((k, distance)) = farthest {
+            if distance > tol {
+                keep[k] = true;
+                queue.push((i, k));
+                queue.push((k, j));
+            }
+        }
+    }
+
+    keep
+}
+
+/// Disjoint-set forest over the indices 0..n using path compression and
+/// union by size.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Construct a UnionFind of n singleton sets
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+        }
+    }
+
+    /// Find the representative of the set containing an index
+    pub fn find(&mut self, index: usize) -> usize {
+        let mut root = index;
+
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut current = index;
+
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Merge the sets containing two indices. This returns false if they
+    /// were already in the same set.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let i = self.find(i);
+        let j = self.find(j);
+
+        if i == j {
+            return false;
+        }
+
+        let (large, small) = if self.sizes[i] >= self.sizes[j] {
+            (i, j)
+        } else {
+            (j, i)
+        };
+
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+
+        true
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::mesh::wavefront::ObjReader;
 
     #[test]
     fn test_merge_faces_multiple() {
@@ -122,4 +339,93 @@ mod test {
 
         merge_faces(&vec![face0, face1]);
     }
+
+    #[test]
+    fn test_check_manifold() {
+        let path = "tests/fixtures/box.obj";
+        let mut reader = ObjReader::new(path);
+        reader.read().unwrap();
+
+        assert!(check_manifold(reader.faces()).is_ok());
+
+        let path = "tests/fixtures/box_nonmanifold.obj";
+        let mut reader = ObjReader::new(path);
+        reader.read().unwrap();
+
+        let result = check_manifold(reader.faces());
+        assert_eq!(result, Err(vec![(5, 6), (5, 7), (6, 7)]));
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let mut points = vec![];
+
+        for i in 0..8 {
+            let x = if (i & 4) == 0 { 0. } else { 1. };
+            let y = if (i & 2) == 0 { 0. } else { 1. };
+            let z = if (i & 1) == 0 { 0. } else { 1. };
+            points.push(Vector3::new(x, y, z));
+        }
+
+        points.push(Vector3::new(0.5, 0.5, 0.5));
+        points.push(Vector3::new(0.2, 0.7, 0.4));
+
+        let hull = convex_hull(&points);
+        let vertices = hull.iter().flatten().copied().collect::<BTreeSet<usize>>();
+
+        assert_eq!(hull.len(), 12);
+        assert_eq!(vertices, (0..8).collect::<BTreeSet<usize>>());
+
+        // Each hull face must be outward facing with respect to the center
+        for face in hull.iter() {
+            let [a, b, c] = face.map(|i| points[i]);
+            let normal = Vector3::cross(&(b - a), &(c - a));
+            assert!(Vector3::dot(&normal, &(a - points[8])) > 0.);
+        }
+    }
+
+    #[test]
+    fn test_convex_hull_coplanar() {
+        let points = vec![
+            Vector3::new(0., 0., 0.),
+            Vector3::new(1., 0., 0.),
+            Vector3::new(0., 1., 0.),
+            Vector3::new(1., 1., 0.),
+        ];
+
+        assert!(convex_hull(&points).is_empty());
+    }
+
+    #[test]
+    fn test_union_find() {
+        let mut sets = UnionFind::new(5);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+
+        assert_eq!(sets.find(0), sets.find(4));
+        assert_ne!(sets.find(0), sets.find(2));
+    }
+
+    #[test]
+    fn test_douglas_peucker() {
+        let points = vec![
+            Vector3::new(0., 0., 0.),
+            Vector3::new(1., 0.01, 0.),
+            Vector3::new(2., 0., 0.),
+            Vector3::new(2., 1., 0.),
+            Vector3::new(2., 2., 0.),
+        ];
+
+        assert_eq!(
+            douglas_peucker(&points, 0.1),
+            vec![true, false, true, false, true]
+        );
+        assert_eq!(
+            douglas_peucker(&points, 0.001),
+            vec![true, true, true, false, true]
+        );
+    }
 }
@@ -0,0 +1,325 @@
+use std::fs::File;
+use std::io::prelude::*;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::mesh::utils::is_gzip;
+use crate::mesh::{Face, Vertex};
+
+#[derive(Debug, Clone)]
+pub struct OffReader {
+    filename: String,
+    vertices: Vec<Vertex>,
+    faces: Vec<Face>,
+}
+
+impl OffReader {
+    /// Construct an OffReader
+    pub fn new(filename: &str) -> OffReader {
+        OffReader {
+            filename: filename.to_string(),
+            vertices: vec![],
+            faces: vec![],
+        }
+    }
+
+    /// Get a borrowed reference to the vertices
+    pub fn vertices(&self) -> &Vec<Vertex> {
+        &self.vertices
+    }
+
+    /// Get a borrowed reference to the faces
+    pub fn faces(&self) -> &Vec<Face> {
+        &self.faces
+    }
+
+    /// Read the file contents
+    pub fn read(&mut self) -> std::io::Result<()> {
+        let mut contents = String::new();
+        let mut file = File::open(&self.filename)?;
+
+        if is_gzip(&self.filename) {
+            let mut file = GzDecoder::new(file);
+            file.read_to_string(&mut contents)?;
+        } else {
+            file.read_to_string(&mut contents)?;
+        }
+
+        if let Err(error) = self.parse(&contents) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                error.to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Parse the file contents. Blank lines and comments are skipped.
+    fn parse(&mut self, contents: &str) -> Result<(), ParseOffError> {
+        let mut counts = None;
+        let mut last = 0;
+
+        let lines = contents
+            .lines()
+            .enumerate()
+            .map(|(count, line)| (count + 1, line.split('#').next().unwrap().trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        for (count, line) in lines {
+            last = count;
+
+            match counts {
+                None if self.vertices.is_empty() && line.starts_with("OFF") => {
+                    let entry = line.trim_start_matches("OFF").trim();
+
+                    if !entry.is_empty() {
+                        counts = Some(self.parse_counts(entry, count)?);
+                    }
+                }
+                None => counts = Some(self.parse_counts(line, count)?),
+                Some((n_vertices, _)) if self.vertices.len() < n_vertices => {
+                    self.parse_vertex(line, count)?
+                }
+                Some((n_vertices, n_faces)) if self.faces.len() < n_faces => {
+                    self.parse_face(line, n_vertices, count)?
+                }
+                Some(_) => {
+                    let context = format!("unexpected entry: {}", line);
+                    return Err(ParseOffError::new(context, count));
+                }
+            }
+        }
+
+        match counts {
+            Some((n_vertices, n_faces))
+                if self.vertices.len() == n_vertices && self.faces.len() == n_faces =>
+            {
+                Ok(())
+            }
+            Some((n_vertices, n_faces)) => {
+                let context = format!(
+                    "expected {} vertices and {} faces but found {} and {}",
+                    n_vertices,
+                    n_faces,
+                    self.vertices.len(),
+                    self.faces.len()
+                );
+                Err(ParseOffError::new(context, last))
+            }
+            None => Err(ParseOffError::new("missing counts".to_string(), last)),
+        }
+    }
+
+    /// Parse the vertex and face counts from an entry
+    fn parse_counts(&self, entry: &str, count: usize) -> Result<(usize, usize), ParseOffError> {
+        let values = entry
+            .split_whitespace()
+            .map(|v| v.parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>();
+
+        match values.as_deref() {
+            Ok([n_vertices, n_faces, ..]) => Ok((*n_vertices, *n_faces)),
+            _ => {
+                let context = format!("invalid counts: {}", entry);
+                Err(ParseOffError::new(context, count))
+            }
+        }
+    }
+
+    /// Parse a vertex from an entry
+    fn parse_vertex(&mut self, entry: &str, count: usize) -> Result<(), ParseOffError> {
+        let values = entry
+            .split_whitespace()
+            .map(|v| v.parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>();
+
+        match values.as_deref() {
+            Ok([x, y, z]) => {
+                self.vertices.push(Vertex::new(*x, *y, *z));
+                Ok(())
+            }
+            _ => {
+                let context = format!("invalid vertex: {}", entry);
+                Err(ParseOffError::new(context, count))
+            }
+        }
+    }
+
+    /// Parse a face from an entry. Any values following the vertex indices
+    /// (such as a face color) are ignored. Vertex indices must be less than
+    /// the number of vertices.
+    fn parse_face(
+        &mut self,
+        entry: &str,
+        n_vertices: usize,
+        count: usize,
+    ) -> Result<(), ParseOffError> {
+        let mut values = entry.split_whitespace();
+        let n = values.next().and_then(|v| v.parse::<usize>().ok());
+
+        let vertices = values
+            .take(n.unwrap_or(0))
+            .map(|v| v.parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>();
+
+        match (n, vertices) {
+            (Some(n), Ok(vertices)) if n >= 3 && vertices.len() == n => {
+                if let Some(index) = vertices.iter().find(|&&v| v >= n_vertices) {
+                    let context = format!("invalid vertex index: {}", index);
+                    return Err(ParseOffError::new(context, count));
+                }
+
+                self.faces.push(Face::new(vertices, None));
+                Ok(())
+            }
+            _ => {
+                let context = format!("invalid face: {}", entry);
+                Err(ParseOffError::new(context, count))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OffWriter {
+    vertices: Vec<Vertex>,
+    faces: Vec<Face>,
+}
+
+impl OffWriter {
+    /// Construct an OffWriter
+    pub fn new() -> OffWriter {
+        OffWriter::default()
+    }
+
+    /// Set the vertices
+    pub fn set_vertices(&mut self, vertices: Vec<Vertex>) {
+        self.vertices = vertices;
+    }
+
+    /// Set the faces
+    pub fn set_faces(&mut self, faces: Vec<Face>) {
+        self.faces = faces;
+    }
+
+    /// Write the mesh to file
+    pub fn write(&self, filename: &str) -> std::io::Result<()> {
+        let mut data = String::from("OFF\n");
+        data.push_str(&format!("{} {} 0\n", self.vertices.len(), self.faces.len()));
+
+        for vertex in self.vertices.iter() {
+            data.push_str(&format!("{} {} {}\n", vertex[0], vertex[1], vertex[2]));
+        }
+
+        for face in self.faces.iter() {
+            let vertices = face
+                .vertices()
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            data.push_str(&format!("{} {}\n", face.vertices().len(), vertices));
+        }
+
+        let mut file = File::create(filename)?;
+        let content = data.as_bytes();
+
+        if is_gzip(filename) {
+            let mut encoder = GzEncoder::new(&mut file, Compression::default());
+            encoder.write_all(content)?;
+        } else {
+            file.write_all(content)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParseOffError {
+    context: String,
+    line_id: usize,
+}
+
+impl ParseOffError {
+    /// Construct a ParseOffError
+    pub fn new(context: String, line_id: usize) -> ParseOffError {
+        ParseOffError { context, line_id }
+    }
+}
+
+impl std::fmt::Display for ParseOffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line_id, self.context)
+    }
+}
+
+impl std::error::Error for ParseOffError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_off_reader() {
+        let path = "tests/fixtures/box.off";
+        let mut reader = OffReader::new(path);
+        reader.read().unwrap();
+
+        assert_eq!(reader.vertices().len(), 8);
+        assert_eq!(reader.faces().len(), 12);
+        assert_eq!(reader.faces()[1].vertices(), &vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn test_off_reader_counts() {
+        let path = "tests/fixtures/box_counts.off";
+        let mut reader = OffReader::new(path);
+        let error = reader.read().unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("line 22:"));
+    }
+
+    #[test]
+    fn test_off_reader_invalid_face() {
+        let mut reader = OffReader::new("");
+        let contents = "OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n4 0 1 2\n";
+        let error = reader.parse(contents).unwrap_err();
+
+        assert_eq!(error.to_string(), "line 6: invalid face: 4 0 1 2");
+    }
+
+    #[test]
+    fn test_off_reader_invalid_index() {
+        let mut reader = OffReader::new("");
+        let contents = "OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n3 0 1 7\n";
+        let error = reader.parse(contents).unwrap_err();
+
+        assert_eq!(error.to_string(), "line 6: invalid vertex index: 7");
+    }
+
+    #[test]
+    fn test_off_writer() {
+        let path = "tests/fixtures/box.off";
+        let mut reader = OffReader::new(path);
+        reader.read().unwrap();
+
+        let mut writer = OffWriter::new();
+        writer.set_vertices(reader.vertices().clone());
+        writer.set_faces(reader.faces().clone());
+
+        let out_path = "/tmp/test_off_writer.off";
+        writer.write(out_path).unwrap();
+
+        let mut other = OffReader::new(out_path);
+        other.read().unwrap();
+
+        assert_eq!(other.vertices(), reader.vertices());
+        assert_eq!(other.faces(), reader.faces());
+    }
+}
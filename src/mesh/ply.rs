@@ -0,0 +1,406 @@
+use std::fs::File;
+use std::io::prelude::*;
+
+use flate2::read::GzDecoder;
+
+use crate::mesh::utils::is_gzip;
+use crate::mesh::{Face, Vertex};
+
+#[derive(Debug, Clone)]
+pub struct PlyReader {
+    filename: String,
+    vertices: Vec<Vertex>,
+    faces: Vec<Face>,
+}
+
+impl PlyReader {
+    /// Construct a PlyReader
+    pub fn new(filename: &str) -> PlyReader {
+        PlyReader {
+            filename: filename.to_string(),
+            vertices: vec![],
+            faces: vec![],
+        }
+    }
+
+    /// Get a borrowed reference to the vertices
+    pub fn vertices(&self) -> &Vec<Vertex> {
+        &self.vertices
+    }
+
+    /// Get a borrowed reference to the faces
+    pub fn faces(&self) -> &Vec<Face> {
+        &self.faces
+    }
+
+    /// Read the file contents. This supports the ASCII and little-endian
+    /// binary formats. Only the vertex positions and face vertex indices are
+    /// kept; all other elements and properties are skipped.
+    pub fn read(&mut self) -> std::io::Result<()> {
+        let mut contents = vec![];
+        let mut file = File::open(&self.filename)?;
+
+        if is_gzip(&self.filename) {
+            let mut file = GzDecoder::new(file);
+            file.read_to_end(&mut contents)?;
+        } else {
+            file.read_to_end(&mut contents)?;
+        }
+
+        let (header, offset) = PlyHeader::parse(&contents)?;
+        let mut body = match header.format {
+            PlyFormat::Ascii => PlyBody::Ascii(
+                std::str::from_utf8(&contents[offset..])
+                    .map_err(|_| invalid_data("invalid ascii body"))?
+                    .split_whitespace(),
+            ),
+            PlyFormat::BinaryLittleEndian => PlyBody::Binary(&contents[offset..]),
+        };
+
+        for element in header.elements.iter() {
+            for _ in 0..element.count {
+                let mut vertex = Vertex::default();
+                let mut face_vertices = vec![];
+
+                for property in element.properties.iter() {
+                    match (element.name.as_str(), property) {
+                        ("vertex", PlyProperty::Scalar(name, kind)) => {
+                            let value = body.read(*kind)?;
+
+                            match name.as_str() {
+                                "x" => vertex[0] = value,
+                                "y" => vertex[1] = value,
+                                "z" => vertex[2] = value,
+                                _ => {}
+                            }
+                        }
+                        ("face", PlyProperty::List(name, count, kind))
+                            if name == "vertex_indices" || name == "vertex_index" =>
+                        {
+                            let n = body.read(*count)? as usize;
+
+                            for _ in 0..n {
+                                face_vertices.push(body.read(*kind)? as usize);
+                            }
+                        }
+                        (_, PlyProperty::Scalar(_, kind)) => {
+                            body.read(*kind)?;
+                        }
+                        (_, PlyProperty::List(_, count, kind)) => {
+                            let n = body.read(*count)? as usize;
+
+                            for _ in 0..n {
+                                body.read(*kind)?;
+                            }
+                        }
+                    }
+                }
+
+                match element.name.as_str() {
+                    "vertex" => self.vertices.push(vertex),
+                    "face" => self.faces.push(Face::new(face_vertices, None)),
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(face) = self.faces.iter().find(|f| {
+            f.vertices().len() < 3 || f.vertices().iter().any(|&i| i >= self.vertices.len())
+        }) {
+            let context = format!("invalid face: {:?}", face.vertices());
+            return Err(invalid_data(&context));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlyWriter {
+    vertices: Vec<Vertex>,
+    faces: Vec<Face>,
+}
+
+impl PlyWriter {
+    /// Construct a PlyWriter
+    pub fn new() -> PlyWriter {
+        PlyWriter::default()
+    }
+
+    /// Set the vertices
+    pub fn set_vertices(&mut self, vertices: Vec<Vertex>) {
+        self.vertices = vertices;
+    }
+
+    /// Set the faces
+    pub fn set_faces(&mut self, faces: Vec<Face>) {
+        self.faces = faces;
+    }
+
+    /// Write the mesh to an ASCII file
+    pub fn write(&self, filename: &str) -> std::io::Result<()> {
+        let mut data = String::new();
+
+        data.push_str("ply\n");
+        data.push_str("format ascii 1.0\n");
+        data.push_str(&format!("element vertex {}\n", self.vertices.len()));
+        data.push_str("property float x\n");
+        data.push_str("property float y\n");
+        data.push_str("property float z\n");
+        data.push_str(&format!("element face {}\n", self.faces.len()));
+        data.push_str("property list uchar int vertex_indices\n");
+        data.push_str("end_header\n");
+
+        for vertex in self.vertices.iter() {
+            data.push_str(&format!("{} {} {}\n", vertex[0], vertex[1], vertex[2]));
+        }
+
+        for face in self.faces.iter() {
+            let vertices = face
+                .vertices()
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            data.push_str(&format!("{} {}\n", face.vertices().len(), vertices));
+        }
+
+        let mut file = File::create(filename)?;
+        file.write_all(data.as_bytes())
+    }
+}
+
+/// Storage format of the PLY body
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+}
+
+/// Scalar data type of a PLY property
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum PlyType {
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Float32,
+    Float64,
+}
+
+impl PlyType {
+    /// Parse the data type from its name
+    fn parse(name: &str) -> std::io::Result<PlyType> {
+        match name {
+            "char" | "int8" => Ok(PlyType::Int8),
+            "uchar" | "uint8" => Ok(PlyType::UInt8),
+            "short" | "int16" => Ok(PlyType::Int16),
+            "ushort" | "uint16" => Ok(PlyType::UInt16),
+            "int" | "int32" => Ok(PlyType::Int32),
+            "uint" | "uint32" => Ok(PlyType::UInt32),
+            "float" | "float32" => Ok(PlyType::Float32),
+            "double" | "float64" => Ok(PlyType::Float64),
+            _ => Err(invalid_data(&format!("invalid property type: {}", name))),
+        }
+    }
+
+    /// Get the size in bytes
+    fn size(&self) -> usize {
+        match self {
+            PlyType::Int8 | PlyType::UInt8 => 1,
+            PlyType::Int16 | PlyType::UInt16 => 2,
+            PlyType::Int32 | PlyType::UInt32 | PlyType::Float32 => 4,
+            PlyType::Float64 => 8,
+        }
+    }
+
+    /// Decode a little-endian value
+    fn decode(&self, bytes: &[u8]) -> f64 {
+        match self {
+            PlyType::Int8 => bytes[0] as i8 as f64,
+            PlyType::UInt8 => bytes[0] as f64,
+            PlyType::Int16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+            PlyType::UInt16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+            PlyType::Int32 => i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            PlyType::UInt32 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            PlyType::Float32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            PlyType::Float64 => f64::from_le_bytes(std::array::from_fn(|i| bytes[i])),
+        }
+    }
+}
+
+/// Property of a PLY element as either a named scalar or a named list with
+/// the count and item data types.
+#[derive(Debug, Clone, PartialEq)]
+enum PlyProperty {
+    Scalar(String, PlyType),
+    List(String, PlyType, PlyType),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PlyHeader {
+    format: PlyFormat,
+    elements: Vec<PlyElement>,
+}
+
+impl PlyHeader {
+    /// Parse the header. This returns the header and the byte offset of the
+    /// start of the body.
+    fn parse(contents: &[u8]) -> std::io::Result<(PlyHeader, usize)> {
+        let mut format = None;
+        let mut elements: Vec<PlyElement> = vec![];
+        let mut offset = 0;
+
+        for (count, line) in contents.split(|&b| b == b'\n').enumerate() {
+            offset += line.len() + 1;
+
+            let line = String::from_utf8_lossy(line);
+            let args = line.split_whitespace().collect::<Vec<&str>>();
+
+            match args.as_slice() {
+                ["ply"] if count == 0 => {}
+                _ if count == 0 => return Err(invalid_data("missing ply magic number")),
+                ["format", "ascii", _] => format = Some(PlyFormat::Ascii),
+                ["format", "binary_little_endian", _] => {
+                    format = Some(PlyFormat::BinaryLittleEndian)
+                }
+                ["format", name, _] => {
+                    return Err(invalid_data(&format!("unsupported format: {}", name)))
+                }
+                ["element", name, n] => {
+                    let count = n
+                        .parse()
+                        .map_err(|_| invalid_data(&format!("invalid element: {}", line)))?;
+
+                    elements.push(PlyElement {
+                        name: name.to_string(),
+                        count,
+                        properties: vec![],
+                    });
+                }
+                ["property", "list", count, kind, name] => {
+                    let property = PlyProperty::List(
+                        name.to_string(),
+                        PlyType::parse(count)?,
+                        PlyType::parse(kind)?,
+                    );
+
+                    elements
+                        .last_mut()
+                        .ok_or_else(|| invalid_data("property before element"))?
+                        .properties
+                        .push(property);
+                }
+                ["property", kind, name] => {
+                    let property = PlyProperty::Scalar(name.to_string(), PlyType::parse(kind)?);
+
+                    elements
+                        .last_mut()
+                        .ok_or_else(|| invalid_data("property before element"))?
+                        .properties
+                        .push(property);
+                }
+                ["end_header"] => {
+                    let format = format.ok_or_else(|| invalid_data("missing format"))?;
+                    return Ok((PlyHeader { format, elements }, offset));
+                }
+                _ => {}
+            }
+        }
+
+        Err(invalid_data("missing end_header"))
+    }
+}
+
+/// Cursor over the values of the PLY body
+enum PlyBody<'a> {
+    Ascii(std::str::SplitWhitespace<'a>),
+    Binary(&'a [u8]),
+}
+
+impl PlyBody<'_> {
+    /// Read the next value of the data type
+    fn read(&mut self, kind: PlyType) -> std::io::Result<f64> {
+        match self {
+            PlyBody::Ascii(tokens) => tokens
+                .next()
+                .and_then(|token| token.parse().ok())
+                .ok_or_else(|| invalid_data("invalid or missing value")),
+            PlyBody::Binary(bytes) => {
+                let size = kind.size();
+
+                if bytes.len() < size {
+                    return Err(invalid_data("unexpected end of file"));
+                }
+
+                let value = kind.decode(&bytes[..size]);
+                *bytes = &bytes[size..];
+                Ok(value)
+            }
+        }
+    }
+}
+
+/// Construct an invalid data error
+fn invalid_data(context: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, context.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ply_reader_ascii() {
+        let path = "tests/fixtures/box_ascii.ply";
+        let mut reader = PlyReader::new(path);
+        reader.read().unwrap();
+
+        assert_eq!(reader.vertices().len(), 8);
+        assert_eq!(reader.faces().len(), 6);
+        assert_eq!(reader.vertices()[1], Vertex::new(-0.5, -0.5, 0.5));
+        assert_eq!(reader.faces()[0].vertices(), &vec![0, 1, 3, 2]);
+    }
+
+    #[test]
+    fn test_ply_reader_binary() {
+        let path = "tests/fixtures/box_binary.ply";
+        let mut reader = PlyReader::new(path);
+        reader.read().unwrap();
+
+        assert_eq!(reader.vertices().len(), 8);
+        assert_eq!(reader.faces().len(), 6);
+        assert_eq!(reader.vertices()[1], Vertex::new(-0.5, -0.5, 0.5));
+        assert_eq!(reader.faces()[0].vertices(), &vec![0, 1, 3, 2]);
+    }
+
+    #[test]
+    fn test_ply_writer() {
+        let path = "tests/fixtures/box_binary.ply";
+        let mut reader = PlyReader::new(path);
+        reader.read().unwrap();
+
+        let mut writer = PlyWriter::new();
+        writer.set_vertices(reader.vertices().clone());
+        writer.set_faces(reader.faces().clone());
+
+        let out_path = "/tmp/test_ply_writer.ply";
+        writer.write(out_path).unwrap();
+
+        let mut other = PlyReader::new(out_path);
+        other.read().unwrap();
+
+        assert_eq!(other.vertices(), reader.vertices());
+        assert_eq!(other.faces(), reader.faces());
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+/// Sparse matrix in compressed sparse row (CSR) format.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SparseMatrix {
+    n_rows: usize,
+    n_cols: usize,
+    row_offsets: Vec<usize>,
+    columns: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl SparseMatrix {
+    /// Construct a SparseMatrix from its (row, column, value) triplets.
+    /// Duplicate entries are summed.
+    pub fn from_triplets(
+        n_rows: usize,
+        n_cols: usize,
+        triplets: &[(usize, usize, f64)],
+    ) -> SparseMatrix {
+        let mut rows: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); n_rows];
+
+        for &(i, j, value) in triplets.iter() {
+            if i >= n_rows || j >= n_cols {
+                panic!("index out of range");
+            }
+
+            *rows[i].entry(j).or_insert(0.) += value;
+        }
+
+        let mut row_offsets = Vec::with_capacity(n_rows + 1);
+        let mut columns = vec![];
+        let mut values = vec![];
+        row_offsets.push(0);
+
+        for row in rows.iter() {
+            for (&j, &value) in row.iter() {
+                columns.push(j);
+                values.push(value);
+            }
+
+            row_offsets.push(columns.len());
+        }
+
+        SparseMatrix {
+            n_rows,
+            n_cols,
+            row_offsets,
+            columns,
+            values,
+        }
+    }
+
+    /// Get the number of rows
+    pub fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    /// Get the number of columns
+    pub fn n_cols(&self) -> usize {
+        self.n_cols
+    }
+
+    /// Get the number of stored (non-zero) entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Get a borrowed reference to the row offsets
+    pub fn row_offsets(&self) -> &Vec<usize> {
+        &self.row_offsets
+    }
+
+    /// Get a borrowed reference to the column indices
+    pub fn columns(&self) -> &Vec<usize> {
+        &self.columns
+    }
+
+    /// Get a borrowed reference to the values
+    pub fn values(&self) -> &Vec<f64> {
+        &self.values
+    }
+
+    /// Get the value at (row, column). Entries which are not stored are zero.
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        let start = self.row_offsets[i];
+        let end = self.row_offsets[i + 1];
+
+        match self.columns[start..end].binary_search(&j) {
+            Ok(k) => self.values[start + k],
+            Err(_) => 0.,
+        }
+    }
+
+    /// Compute the (row, column, value) triplets of the stored entries
+    pub fn triplets(&self) -> Vec<(usize, usize, f64)> {
+        let mut triplets = Vec::with_capacity(self.nnz());
+
+        for i in 0..self.n_rows {
+            for k in self.row_offsets[i]..self.row_offsets[i + 1] {
+                triplets.push((i, self.columns[k], self.values[k]));
+            }
+        }
+
+        triplets
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_triplets() {
+        let triplets = vec![(1, 0, 2.), (0, 1, 1.), (1, 0, 3.), (0, 0, 4.)];
+        let matrix = SparseMatrix::from_triplets(2, 3, &triplets);
+
+        assert_eq!(matrix.nnz(), 3);
+        assert_eq!(matrix.row_offsets(), &vec![0, 2, 3]);
+        assert_eq!(matrix.columns(), &vec![0, 1, 0]);
+        assert_eq!(matrix.get(0, 0), 4.);
+        assert_eq!(matrix.get(0, 1), 1.);
+        assert_eq!(matrix.get(1, 0), 5.);
+        assert_eq!(matrix.get(1, 2), 0.);
+    }
+
+    #[test]
+    fn test_triplets() {
+        let triplets = vec![(0, 1, 1.), (1, 0, 2.), (0, 0, 4.)];
+        let matrix = SparseMatrix::from_triplets(2, 2, &triplets);
+
+        assert_eq!(matrix.triplets(), vec![(0, 0, 4.), (0, 1, 1.), (1, 0, 2.)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_triplets_out_of_range() {
+        SparseMatrix::from_triplets(2, 2, &[(2, 0, 1.)]);
+    }
+}
@@ -0,0 +1,339 @@
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+
+use crate::geometry::{Triangle, Vector3};
+use crate::mesh::utils::is_gzip;
+use crate::mesh::{Face, Vertex};
+
+/// Size in bytes of the binary STL header (including the triangle count)
+const HEADER_SIZE: usize = 84;
+
+/// Size in bytes of a binary STL triangle record
+const RECORD_SIZE: usize = 50;
+
+#[derive(Debug, Clone)]
+pub struct StlReader {
+    filename: String,
+    vertices: Vec<Vertex>,
+    faces: Vec<Face>,
+}
+
+impl StlReader {
+    /// Construct an StlReader
+    pub fn new(filename: &str) -> StlReader {
+        StlReader {
+            filename: filename.to_string(),
+            vertices: vec![],
+            faces: vec![],
+        }
+    }
+
+    /// Get a borrowed reference to the vertices. Each triangle references
+    /// its own three vertices, so shared corners are duplicated.
+    pub fn vertices(&self) -> &Vec<Vertex> {
+        &self.vertices
+    }
+
+    /// Get a borrowed reference to the faces
+    pub fn faces(&self) -> &Vec<Face> {
+        &self.faces
+    }
+
+    /// Read the file contents. Binary files are detected by the triangle
+    /// count in the header matching the file length; otherwise a file
+    /// starting with "solid" is read as ASCII.
+    pub fn read(&mut self) -> std::io::Result<()> {
+        let mut contents = vec![];
+        let mut file = File::open(&self.filename)?;
+
+        if is_gzip(&self.filename) {
+            let mut file = GzDecoder::new(file);
+            file.read_to_end(&mut contents)?;
+        } else {
+            file.read_to_end(&mut contents)?;
+        }
+
+        let count = if contents.len() >= HEADER_SIZE {
+            let bytes = [contents[80], contents[81], contents[82], contents[83]];
+            Some(u32::from_le_bytes(bytes) as usize)
+        } else {
+            None
+        };
+
+        let is_binary = count.is_some_and(|n| HEADER_SIZE + n * RECORD_SIZE == contents.len());
+
+        if is_binary {
+            return self.parse_binary(&contents);
+        }
+
+        // Binary files written by some CAD tools also start with "solid", so
+        // a mismatched header count is reported if the contents are not
+        // ASCII facets.
+        if contents.trim_ascii_start().starts_with(b"solid") {
+            if let Ok(text) = std::str::from_utf8(&contents) {
+                self.parse_ascii(text)?;
+
+                if !self.faces.is_empty() || count.is_none() {
+                    return Ok(());
+                }
+            }
+        }
+
+        let context = match count {
+            Some(count) => format!(
+                "binary triangle count {} does not match file length {}",
+                count,
+                contents.len()
+            ),
+            None => "invalid stl file".to_string(),
+        };
+
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            context,
+        ))
+    }
+
+    /// Parse the triangle records of a binary file
+    fn parse_binary(&mut self, contents: &[u8]) -> std::io::Result<()> {
+        for record in contents[HEADER_SIZE..].chunks_exact(RECORD_SIZE) {
+            let mut face_vertices = vec![];
+
+            // Skip the facet normal (the first 12 bytes) and read the three
+            // vertices. The trailing attribute byte count is ignored.
+            for corner in record[12..48].chunks_exact(12) {
+                let mut vertex = Vertex::default();
+
+                for (i, bytes) in corner.chunks_exact(4).enumerate() {
+                    let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+                    vertex[i] = f32::from_le_bytes(bytes) as f64;
+                }
+
+                face_vertices.push(self.vertices.len());
+                self.vertices.push(vertex);
+            }
+
+            let face = Face::new(face_vertices, None);
+            self.faces.push(face);
+        }
+
+        Ok(())
+    }
+
+    /// Parse the facets of an ASCII file
+    fn parse_ascii(&mut self, contents: &str) -> std::io::Result<()> {
+        let mut face_vertices = vec![];
+
+        for (count, line) in contents.lines().enumerate() {
+            let count = count + 1;
+            let args = line.split_whitespace().collect::<Vec<&str>>();
+
+            match args.first() {
+                Some(&"vertex") => {
+                    let mut vertex = Vertex::default();
+
+                    if args.len() != 4 {
+                        return Err(invalid_line(line, count));
+                    }
+
+                    for (i, value) in args[1..].iter().enumerate() {
+                        vertex[i] = value.parse().map_err(|_| invalid_line(line, count))?;
+                    }
+
+                    face_vertices.push(self.vertices.len());
+                    self.vertices.push(vertex);
+                }
+                Some(&"endfacet") => {
+                    if face_vertices.len() != 3 {
+                        return Err(invalid_line(line, count));
+                    }
+
+                    let face = Face::new(face_vertices, None);
+                    self.faces.push(face);
+                    face_vertices = vec![];
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StlWriter {
+    triangles: Vec<Triangle>,
+    normals: Vec<Vector3>,
+}
+
+impl StlWriter {
+    /// Construct an StlWriter
+    pub fn new() -> StlWriter {
+        StlWriter::default()
+    }
+
+    /// Set the triangles
+    pub fn set_triangles(&mut self, triangles: Vec<Triangle>) {
+        self.triangles = triangles;
+    }
+
+    /// Set the facet normals. These are only written if there is a normal
+    /// for every triangle; otherwise the triangle normals are used.
+    pub fn set_normals(&mut self, normals: Vec<Vector3>) {
+        self.normals = normals;
+    }
+
+    /// Write the triangles to file. Files with the "stla" extension are
+    /// written as ASCII and all others are written as binary.
+    pub fn write(&self, filename: &str) -> std::io::Result<()> {
+        let content = if is_ascii(filename) {
+            self.format_ascii().into_bytes()
+        } else {
+            self.format_binary()
+        };
+
+        let mut file = File::create(filename)?;
+        file.write_all(&content)
+    }
+
+    /// Get the normal of a facet
+    fn normal(&self, index: usize) -> Vector3 {
+        if self.normals.len() == self.triangles.len() {
+            self.normals[index]
+        } else {
+            self.triangles[index].normal().unit()
+        }
+    }
+
+    /// Format the triangles as an ASCII file
+    fn format_ascii(&self) -> String {
+        let mut data = String::from("solid meshx\n");
+
+        for (i, triangle) in self.triangles.iter().enumerate() {
+            let normal = self.normal(i);
+            data.push_str(&format!(
+                "facet normal {} {} {}\n",
+                normal[0], normal[1], normal[2]
+            ));
+            data.push_str("outer loop\n");
+
+            for vertex in [triangle.p(), triangle.q(), triangle.r()] {
+                data.push_str(&format!(
+                    "vertex {} {} {}\n",
+                    vertex[0], vertex[1], vertex[2]
+                ));
+            }
+
+            data.push_str("endloop\nendfacet\n");
+        }
+
+        data.push_str("endsolid meshx\n");
+        data
+    }
+
+    /// Format the triangles as a binary file
+    fn format_binary(&self) -> Vec<u8> {
+        let mut data = vec![0; 80];
+        data.extend((self.triangles.len() as u32).to_le_bytes());
+
+        for (i, triangle) in self.triangles.iter().enumerate() {
+            for vector in [self.normal(i), triangle.p(), triangle.q(), triangle.r()] {
+                for k in 0..3 {
+                    data.extend((vector[k] as f32).to_le_bytes());
+                }
+            }
+
+            data.extend(0u16.to_le_bytes());
+        }
+
+        data
+    }
+}
+
+/// Check if the filename is an ASCII STL file
+fn is_ascii(filename: &str) -> bool {
+    let path = Path::new(filename);
+    let extension = path.extension().and_then(OsStr::to_str);
+
+    extension.is_some_and(|ext| ext.to_lowercase() == "stla")
+}
+
+/// Construct the error for an invalid ASCII line
+fn invalid_line(line: &str, count: usize) -> std::io::Error {
+    let context = format!("line {}: invalid entry: {}", count, line.trim());
+    std::io::Error::new(std::io::ErrorKind::InvalidData, context)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stl_reader_ascii() {
+        let path = "tests/fixtures/box_ascii.stl";
+        let mut reader = StlReader::new(path);
+        reader.read().unwrap();
+
+        assert_eq!(reader.vertices().len(), 36);
+        assert_eq!(reader.faces().len(), 12);
+        assert_eq!(reader.vertices()[3], Vertex::new(-0.5, -0.5, 0.5));
+    }
+
+    #[test]
+    fn test_stl_reader_binary() {
+        let path = "tests/fixtures/box_binary.stl";
+        let mut reader = StlReader::new(path);
+        reader.read().unwrap();
+
+        assert_eq!(reader.vertices().len(), 36);
+        assert_eq!(reader.faces().len(), 12);
+        assert_eq!(reader.vertices()[3], Vertex::new(-0.5, -0.5, 0.5));
+    }
+
+    #[test]
+    fn test_stl_writer() {
+        let p = Vector3::new(0., 0., 0.);
+        let q = Vector3::new(1., 0., 0.);
+        let r = Vector3::new(0., 1., 0.);
+
+        let mut writer = StlWriter::new();
+        writer.set_triangles(vec![Triangle::new(p, q, r)]);
+
+        for path in ["/tmp/test_stl_writer.stl", "/tmp/test_stl_writer.stla"] {
+            writer.write(path).unwrap();
+
+            let mut reader = StlReader::new(path);
+            reader.read().unwrap();
+
+            assert_eq!(reader.faces().len(), 1);
+            assert_eq!(reader.vertices()[1], Vertex::new(1., 0., 0.));
+        }
+
+        let size = std::fs::metadata("/tmp/test_stl_writer.stl").unwrap().len();
+        assert_eq!(size, (HEADER_SIZE + RECORD_SIZE) as u64);
+    }
+
+    #[test]
+    fn test_stl_reader_truncated() {
+        let path = "tests/fixtures/box_truncated.stl";
+        let mut reader = StlReader::new(path);
+        let error = reader.read().unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("triangle count 12"));
+    }
+
+    #[test]
+    fn test_stl_reader_truncated_solid() {
+        let path = "tests/fixtures/box_solid_truncated.stl";
+        let mut reader = StlReader::new(path);
+        let error = reader.read().unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("triangle count 12"));
+    }
+}
@@ -12,6 +12,7 @@ use crate::mesh::{Edge, Face, Patch, Vertex};
 pub struct ObjReader {
     filename: String,
     vertices: Vec<Vertex>,
+    colors: Vec<[f64; 3]>,
     faces: Vec<Face>,
     patches: Vec<Patch>,
 }
@@ -22,6 +23,7 @@ impl ObjReader {
         ObjReader {
             filename: filename.to_string(),
             vertices: vec![],
+            colors: vec![],
             faces: vec![],
             patches: vec![],
         }
@@ -32,6 +34,12 @@ impl ObjReader {
         &self.vertices
     }
 
+    /// Get a borrowed reference to the vertex colors. These are only read
+    /// for vertices using the extended (x, y, z, r, g, b) format.
+    pub fn colors(&self) -> &Vec<[f64; 3]> {
+        &self.colors
+    }
+
     /// Get a borrowed reference to the faces
     pub fn faces(&self) -> &Vec<Face> {
         &self.faces
@@ -80,10 +88,12 @@ impl ObjReader {
     /// Parse a vertex from an entry
     fn parse_vertex(&mut self, entry: &str, count: usize) -> Result<(), ParseObjError> {
         let mut vertex = Vertex::default();
+        let mut color = [0.; 3];
         let mut is_error = false;
+        let n = entry.split_whitespace().count();
 
         for (i, value) in entry.split_whitespace().enumerate() {
-            if i > 3 {
+            if i > 5 || n == 5 {
                 is_error = true;
                 break;
             }
@@ -91,6 +101,8 @@ impl ObjReader {
             if let Ok(v) = value.parse::<f64>() {
                 if i < 3 {
                     vertex[i] = v;
+                } else if n == 6 {
+                    color[i - 3] = v;
                 }
             }
         }
@@ -101,6 +113,10 @@ impl ObjReader {
             return Err(error);
         }
 
+        if n == 6 {
+            self.colors.push(color);
+        }
+
         self.vertices.push(vertex);
 
         Ok(())
@@ -156,9 +172,11 @@ impl ObjReader {
 #[derive(Debug, Clone, Default)]
 pub struct ObjWriter {
     vertices: Vec<Vertex>,
+    colors: Vec<[f64; 3]>,
     faces: Vec<Face>,
     edges: Vec<Edge>,
     patches: Vec<Patch>,
+    material_library: Option<String>,
 }
 
 impl ObjWriter {
@@ -172,6 +190,12 @@ impl ObjWriter {
         self.vertices = vertices;
     }
 
+    /// Set the vertex colors. These are only written if there is a color for
+    /// every vertex.
+    pub fn set_colors(&mut self, colors: Vec<[f64; 3]>) {
+        self.colors = colors;
+    }
+
     /// Set the faces
     pub fn set_faces(&mut self, faces: Vec<Face>) {
         self.faces = faces;
@@ -187,8 +211,30 @@ impl ObjWriter {
         self.patches = patches;
     }
 
+    /// Set the material library. When set, the library is referenced and
+    /// each patch uses the material of the same name.
+    pub fn set_material_library(&mut self, filename: &str) {
+        self.material_library = Some(filename.to_string());
+    }
+
     /// Write the mesh to file
     pub fn write(&self, filename: &str) -> std::io::Result<()> {
+        let data = self.format();
+        let mut file = File::create(filename)?;
+        let content = data.as_bytes();
+
+        if is_gzip(&filename) {
+            let mut encoder = GzEncoder::new(&mut file, Compression::default());
+            encoder.write_all(&content)?;
+        } else {
+            file.write_all(&content)?;
+        }
+
+        Ok(())
+    }
+
+    /// Format the mesh as the contents of an OBJ file
+    pub fn format(&self) -> String {
         let mut data = String::new();
         let mut patch_faces: Vec<Vec<usize>> = vec![vec![]; self.patches.len() + 1];
         let mut patch_edges: Vec<Vec<usize>> = vec![vec![]; self.patches.len() + 1];
@@ -213,9 +259,20 @@ impl ObjWriter {
             }
         }
 
-        // Format all the vertices.
-        for vertex in self.vertices.iter() {
-            let entry = self.format_vertex(vertex);
+        if let Some(filename) = &self.material_library {
+            data.push_str(&format!("mtllib {}\n", filename));
+        }
+
+        // Format all the vertices (with their colors if defined).
+        let has_colors = self.colors.len() == self.vertices.len();
+
+        for (i, vertex) in self.vertices.iter().enumerate() {
+            let color = if has_colors {
+                Some(&self.colors[i])
+            } else {
+                None
+            };
+            let entry = self.format_vertex(vertex, color);
             data.push_str(&entry);
         }
 
@@ -248,23 +305,18 @@ impl ObjWriter {
             }
         }
 
-        // Write the data to file.
-        let mut file = File::create(filename)?;
-        let content = data.as_bytes();
-
-        if is_gzip(&filename) {
-            let mut encoder = GzEncoder::new(&mut file, Compression::default());
-            encoder.write_all(&content)?;
-        } else {
-            file.write_all(&content)?;
-        }
-
-        Ok(())
+        data
     }
 
     /// Format a vertex to an entry
-    fn format_vertex(&self, vertex: &Vertex) -> String {
-        format!("v {} {} {}\n", vertex[0], vertex[1], vertex[2])
+    fn format_vertex(&self, vertex: &Vertex, color: Option<&[f64; 3]>) -> String {
+        match color {
+            Some(c) => format!(
+                "v {} {} {} {} {} {}\n",
+                vertex[0], vertex[1], vertex[2], c[0], c[1], c[2]
+            ),
+            None => format!("v {} {} {}\n", vertex[0], vertex[1], vertex[2]),
+        }
     }
 
     /// Format a face to an entry
@@ -286,7 +338,97 @@ impl ObjWriter {
 
     /// Format a patch to an entry
     fn format_patch(&self, patch: &Patch) -> String {
-        format!("g {}\n", patch.name())
+        match self.material_library {
+            Some(_) => format!("g {}\nusemtl {}\n", patch.name(), patch.name()),
+            None => format!("g {}\n", patch.name()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MtlWriter {
+    materials: Vec<(String, [f64; 3])>,
+}
+
+impl MtlWriter {
+    /// Construct an MtlWriter
+    pub fn new() -> MtlWriter {
+        MtlWriter::default()
+    }
+
+    /// Add a material by name with its diffuse color
+    pub fn add_material(&mut self, name: &str, diffuse: [f64; 3]) {
+        self.materials.push((name.to_string(), diffuse));
+    }
+
+    /// Write the materials to file
+    pub fn write(&self, filename: &str) -> std::io::Result<()> {
+        let mut file = File::create(filename)?;
+        file.write_all(self.format().as_bytes())
+    }
+
+    /// Format the materials as the contents of an MTL file
+    pub fn format(&self) -> String {
+        let mut data = String::new();
+
+        for (name, color) in self.materials.iter() {
+            data.push_str(&format!("newmtl {}\n", name));
+            data.push_str(&format!("Kd {} {} {}\n", color[0], color[1], color[2]));
+        }
+
+        data
+    }
+}
+
+/// Up-axis convention of the coordinates stored in a file. Meshes are
+/// represented internally as Y-up.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum CoordinateSystem {
+    #[default]
+    YUp,
+    ZUp,
+}
+
+impl CoordinateSystem {
+    /// Convert a vertex from this coordinate system to Y-up. The conversion
+    /// is a proper rotation about the x-axis, so the face winding (and
+    /// handedness) is preserved without flipping.
+    pub fn to_y_up(&self, vertex: Vertex) -> Vertex {
+        match self {
+            CoordinateSystem::YUp => vertex,
+            CoordinateSystem::ZUp => Vertex::new(vertex[0], vertex[2], -vertex[1]),
+        }
+    }
+
+    /// Convert a vertex from Y-up to this coordinate system. This is the
+    /// inverse of `to_y_up`.
+    pub fn from_y_up(&self, vertex: Vertex) -> Vertex {
+        match self {
+            CoordinateSystem::YUp => vertex,
+            CoordinateSystem::ZUp => Vertex::new(vertex[0], -vertex[2], vertex[1]),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ObjOptions {
+    coordinate_system: CoordinateSystem,
+}
+
+impl ObjOptions {
+    /// Construct the default ObjOptions
+    pub fn new() -> ObjOptions {
+        ObjOptions::default()
+    }
+
+    /// Get the coordinate system
+    pub fn coordinate_system(&self) -> CoordinateSystem {
+        self.coordinate_system
+    }
+
+    /// Set the coordinate system
+    pub fn set_coordinate_system(&mut self, coordinate_system: CoordinateSystem) {
+        self.coordinate_system = coordinate_system;
     }
 }
 
@@ -433,4 +575,77 @@ mod test {
 
         assert_eq!(actual_content, expected_content);
     }
+
+    #[test]
+    fn test_obj_writer_materials() {
+        let path = "tests/fixtures/box_groups.obj";
+        let mut reader = ObjReader::new(path);
+        reader.read().unwrap();
+
+        let mut writer = ObjWriter::new();
+        writer.set_vertices(reader.vertices);
+        writer.set_faces(reader.faces);
+        writer.set_patches(reader.patches);
+        writer.set_material_library("box_groups.mtl");
+
+        let content = writer.format();
+
+        assert!(content.starts_with("mtllib box_groups.mtl\n"));
+        assert!(content.contains("g front\nusemtl front\n"));
+
+        let mut writer = MtlWriter::new();
+        writer.add_material("front", [1., 0.5, 0.]);
+
+        assert_eq!(writer.format(), "newmtl front\nKd 1 0.5 0\n");
+    }
+
+    #[test]
+    fn test_obj_reader_colors() {
+        let path = "tests/fixtures/box_colors.obj";
+        let mut reader = ObjReader::new(path);
+        reader.read().unwrap();
+
+        assert_eq!(reader.vertices().len(), 8);
+        assert_eq!(reader.colors().len(), 8);
+        assert_eq!(reader.colors()[1], [0., 0., 1.]);
+        assert_eq!(reader.vertices()[1], Vertex::new(-0.5, -0.5, 0.5));
+    }
+
+    #[test]
+    fn test_obj_writer_colors() {
+        let path = "tests/fixtures/box_colors.obj";
+        let mut reader = ObjReader::new(path);
+        reader.read().unwrap();
+
+        let out_path = "/tmp/box_colors.obj";
+        let mut writer = ObjWriter::new();
+        writer.set_vertices(reader.vertices);
+        writer.set_colors(reader.colors);
+        writer.set_faces(reader.faces);
+        writer.set_patches(reader.patches);
+        writer.write(out_path).unwrap();
+
+        let expected_content = std::fs::read_to_string(path).unwrap();
+        let actual_content = std::fs::read_to_string(out_path).unwrap();
+
+        assert_eq!(actual_content, expected_content);
+    }
+
+    #[test]
+    fn test_coordinate_system_z_up() {
+        let vertex = Vertex::new(1., 2., 3.);
+        let system = CoordinateSystem::ZUp;
+
+        assert_eq!(system.to_y_up(vertex), Vertex::new(1., 3., -2.));
+        assert_eq!(system.from_y_up(system.to_y_up(vertex)), vertex);
+    }
+
+    #[test]
+    fn test_coordinate_system_y_up() {
+        let vertex = Vertex::new(1., 2., 3.);
+        let system = CoordinateSystem::YUp;
+
+        assert_eq!(system.to_y_up(vertex), vertex);
+        assert_eq!(system.from_y_up(vertex), vertex);
+    }
 }
@@ -1,6 +1,8 @@
+pub mod bvh;
 pub mod octree;
 
 // Re-exports
+pub use bvh::Bvh;
 pub use octree::Octree;
 
 /// Search for the unique set of indexed items spatially intersecting
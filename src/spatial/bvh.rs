@@ -0,0 +1,366 @@
+use rayon::prelude::*;
+
+use crate::geometry::{Aabb, Bounded, Intersection, Intersects, Ray, RayHit, Vector3};
+use crate::spatial::{Search, SearchMany};
+
+/// Maximum number of items stored on a BvhNode leaf
+const MAX_ITEMS_PER_LEAF: usize = 4;
+
+/// Bounding volume hierarchy of items split at the median of their centers
+/// along the longest axis. Unlike an Octree, the node boxes tightly bound
+/// their items and each item is stored on exactly one leaf. Ray casts against
+/// the triangles of sphere.obj are about 2.8x faster than with an Octree (see
+/// benches/bvh.rs).
+#[derive(Debug, Clone)]
+pub struct Bvh<T>
+where
+    T: Intersects<Aabb> + Bounded,
+{
+    nodes: Vec<BvhNode>,
+    items: Vec<T>,
+    indices: Vec<usize>,
+}
+
+impl<T> Bvh<T>
+where
+    T: Intersects<Aabb> + Bounded,
+{
+    /// Construct a Bvh from all of its items
+    pub fn build(items: Vec<T>) -> Bvh<T> {
+        let boxes = items.iter().map(|item| item.aabb()).collect::<Vec<Aabb>>();
+        let mut indices = (0..items.len()).collect::<Vec<usize>>();
+        let mut nodes = vec![];
+
+        if !items.is_empty() {
+            build_node(&mut nodes, &boxes, &mut indices, 0);
+        }
+
+        Bvh {
+            nodes,
+            items,
+            indices,
+        }
+    }
+
+    /// Get the number of indexed items
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Get if there are no indexed items
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get a borrowed reference to an item
+    pub fn item(&self, index: usize) -> &T {
+        &self.items[index]
+    }
+
+    /// Get a borrowed reference to the items
+    pub fn items(&self) -> &Vec<T> {
+        &self.items
+    }
+
+    /// Get a borrowed reference to the nodes. The root is the first node.
+    pub fn nodes(&self) -> &Vec<BvhNode> {
+        &self.nodes
+    }
+
+    /// Get the indices of the items stored on a leaf node
+    pub fn leaf_items(&self, node: &BvhNode) -> &[usize] {
+        match node.kind {
+            BvhKind::Leaf { start, count } => &self.indices[start..start + count],
+            BvhKind::Internal { .. } => &[],
+        }
+    }
+
+    /// Find the first item hit by the ray where the hit function computes the
+    /// parameter along the ray (in units of the ray direction) at which the
+    /// item by index is hit. The nearer child of each node is visited first
+    /// and any node entered beyond the closest hit found so far is pruned.
+    /// This returns the index and hit parameter.
+    pub fn raycast_with<F>(&self, ray: &Ray, hit: F) -> Option<(usize, f64)>
+    where
+        F: Fn(usize) -> Option<f64>,
+    {
+        let mut best: Option<(usize, f64)> = None;
+        let mut stack = vec![];
+
+        if let Some(t) = self.nodes.first().and_then(|n| ray.intersection(&n.aabb)) {
+            stack.push((t, 0));
+        }
+
+        while let Some((t, index)) = stack.pop() {
+            if best.is_some_and(|(_, best_t)| t > best_t) {
+                continue;
+            }
+
+            let node = &self.nodes[index];
+
+            match node.kind {
+                BvhKind::Leaf { .. } => {
+                    for &i in self.leaf_items(node) {
+                        if let Some(t) = hit(i) {
+                            if best.is_none_or(|(j, best_t)| (t, i) < (best_t, j)) {
+                                best = Some((i, t));
+                            }
+                        }
+                    }
+                }
+                BvhKind::Internal { left, right } => {
+                    let tl = ray.intersection(&self.nodes[left].aabb);
+                    let tr = ray.intersection(&self.nodes[right].aabb);
+
+                    // Push the farther child first so the nearer is popped next
+                    let mut children = [(tl, left), (tr, right)];
+                    children.sort_by(|a, b| {
+                        let u = a.0.unwrap_or(f64::INFINITY);
+                        let v = b.0.unwrap_or(f64::INFINITY);
+                        v.total_cmp(&u)
+                    });
+
+                    for (t, child) in children {
+                        if let Some(t) = t {
+                            stack.push((t, child));
+                        }
+                    }
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl<T> Bvh<T>
+where
+    T: Intersects<Aabb> + Bounded,
+    Ray: Intersection<T, Output = RayHit>,
+{
+    /// Find the index of the first item hit by the ray
+    pub fn raycast(&self, ray: &Ray) -> Option<usize> {
+        self.raycast_with(ray, |i| ray.intersection(&self.items[i]).map(|hit| hit.t()))
+            .map(|(index, _)| index)
+    }
+}
+
+impl<T, Q> Search<Q> for Bvh<T>
+where
+    T: Intersects<Aabb> + Intersects<Q> + Bounded,
+    Q: Intersects<Aabb>,
+{
+    fn search(&self, query: &Q) -> Vec<usize> {
+        let mut results = vec![];
+        let mut stack = vec![];
+
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+
+            if query.intersects(&node.aabb) {
+                match node.kind {
+                    BvhKind::Leaf { .. } => {
+                        for &i in self.leaf_items(node) {
+                            if self.items[i].intersects(query) {
+                                results.push(i);
+                            }
+                        }
+                    }
+                    BvhKind::Internal { left, right } => {
+                        stack.push(left);
+                        stack.push(right);
+                    }
+                }
+            }
+        }
+
+        results
+    }
+}
+
+impl<T, Q> SearchMany<Q> for Bvh<T>
+where
+    T: Intersects<Aabb> + Intersects<Q> + Bounded + Sync,
+    Q: Intersects<Aabb> + Sync,
+    Bvh<T>: Search<Q>,
+{
+    fn search_many(&self, queries: &Vec<Q>) -> Vec<Vec<usize>> {
+        queries.par_iter().map(|q| self.search(q)).collect()
+    }
+}
+
+/// Recursively build the node for the item indices starting at the offset
+/// into the full list of indices. This returns the index of the node.
+fn build_node(
+    nodes: &mut Vec<BvhNode>,
+    boxes: &[Aabb],
+    indices: &mut [usize],
+    offset: usize,
+) -> usize {
+    let aabb = indices
+        .iter()
+        .map(|&i| boxes[i])
+        .reduce(|a, b| union(&a, &b))
+        .unwrap();
+
+    let index = nodes.len();
+    nodes.push(BvhNode {
+        aabb,
+        kind: BvhKind::Leaf {
+            start: offset,
+            count: indices.len(),
+        },
+    });
+
+    if indices.len() <= MAX_ITEMS_PER_LEAF {
+        return index;
+    }
+
+    // Split at the median of the box centers along the longest axis of the
+    // bounds of the centers
+    let centers = indices
+        .iter()
+        .map(|&i| boxes[i].center())
+        .map(|c| Aabb::new(c, Vector3::zeros()))
+        .reduce(|a, b| union(&a, &b))
+        .unwrap();
+
+    let axis = centers.halfsize().argmax();
+    let mid = indices.len() / 2;
+
+    indices.select_nth_unstable_by(mid, |&i, &j| {
+        let u = boxes[i].center()[axis];
+        let v = boxes[j].center()[axis];
+        u.total_cmp(&v).then(i.cmp(&j))
+    });
+
+    let (lower, upper) = indices.split_at_mut(mid);
+    let left = build_node(nodes, boxes, lower, offset);
+    let right = build_node(nodes, boxes, upper, offset + mid);
+
+    nodes[index].kind = BvhKind::Internal { left, right };
+    index
+}
+
+/// Compute the Aabb bounding both boxes
+fn union(a: &Aabb, b: &Aabb) -> Aabb {
+    let (u, v) = (a.min(), b.min());
+    let min = Vector3::new(u[0].min(v[0]), u[1].min(v[1]), u[2].min(v[2]));
+
+    let (u, v) = (a.max(), b.max());
+    let max = Vector3::new(u[0].max(v[0]), u[1].max(v[1]), u[2].max(v[2]));
+
+    Aabb::from_bounds(min, max)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BvhNode {
+    aabb: Aabb,
+    kind: BvhKind,
+}
+
+impl BvhNode {
+    /// Get the axis-aligned bounding box
+    pub fn aabb(&self) -> Aabb {
+        self.aabb
+    }
+
+    /// Get if the node is a leaf
+    pub fn is_leaf(&self) -> bool {
+        matches!(self.kind, BvhKind::Leaf { .. })
+    }
+}
+
+/// Kind of a BvhNode. A leaf stores a range of the item indices and an
+/// internal node stores the indices of its two children.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum BvhKind {
+    Leaf { start: usize, count: usize },
+    Internal { left: usize, right: usize },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::{Triangle, EPSILON};
+    use rand::prelude::*;
+
+    fn triangles(n: usize) -> Vec<Triangle> {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut point = || Vector3::new(rng.gen(), rng.gen(), rng.gen()) - Vector3::ones() * 0.5;
+
+        (0..n)
+            .map(|_| {
+                let p = point() * 0.9;
+                Triangle::new(p, p + point() * 0.1, p + point() * 0.1)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build() {
+        let bvh = Bvh::build(triangles(1000));
+        let mut indices = vec![];
+
+        for node in bvh.nodes().iter() {
+            if node.is_leaf() {
+                assert!(bvh.leaf_items(node).len() <= MAX_ITEMS_PER_LEAF);
+
+                let aabb = node.aabb();
+                let aabb = Aabb::new(aabb.center(), aabb.halfsize() + Vector3::ones() * EPSILON);
+
+                for &i in bvh.leaf_items(node) {
+                    assert!(aabb.contains_aabb(&bvh.item(i).aabb()));
+                    indices.push(i);
+                }
+            }
+        }
+
+        indices.sort();
+
+        assert_eq!(indices, (0..1000).collect::<Vec<usize>>());
+        assert!(Bvh::<Triangle>::build(vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_search() {
+        let items = triangles(1000);
+        let bvh = Bvh::build(items.clone());
+        let query = Aabb::new(Vector3::new(0.1, -0.1, 0.2), Vector3::ones() * 0.1);
+
+        let mut results = bvh.search(&query);
+        results.sort();
+
+        let expected = (0..items.len())
+            .filter(|&i| items[i].intersects(&query))
+            .collect::<Vec<usize>>();
+
+        assert!(!expected.is_empty());
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_raycast() {
+        let items = triangles(1000);
+        let bvh = Bvh::build(items.clone());
+
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..50 {
+            let origin = Vector3::new(rng.gen(), rng.gen(), rng.gen()) - Vector3::ones() * 0.5;
+            let direction = Vector3::new(rng.gen(), rng.gen(), rng.gen()) - Vector3::ones() * 0.5;
+            let ray = Ray::new(origin, direction);
+
+            let expected = (0..items.len())
+                .filter_map(|i| ray.intersection(&items[i]).map(|hit| (hit.t(), i)))
+                .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)))
+                .map(|(_, i)| i);
+
+            assert_eq!(bvh.raycast(&ray), expected);
+        }
+    }
+}